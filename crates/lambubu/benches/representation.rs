@@ -0,0 +1,120 @@
+use criterion::{Criterion, criterion_group, criterion_main};
+use lambubu::{BruijnLevelsTerm, Term, TermArena, closure::CompiledTerm};
+use std::{collections::HashSet, hint::black_box, rc::Rc};
+
+fn church(n: usize) -> Term {
+    let mut body = Term::var("x");
+    for _ in 0..n {
+        body = Term::app(Term::var("f"), body);
+    }
+    Term::abs("f", Term::abs("x", body))
+}
+
+fn add() -> Term {
+    Term::abs(
+        "m",
+        Term::abs(
+            "n",
+            Term::abs(
+                "f",
+                Term::abs(
+                    "x",
+                    Term::app(
+                        Term::app(Term::var("n"), Term::var("f")),
+                        Term::app(Term::app(Term::var("m"), Term::var("f")), Term::var("x")),
+                    ),
+                ),
+            ),
+        ),
+    )
+}
+
+fn workloads() -> Vec<(&'static str, Term)> {
+    vec![
+        (
+            "add_20_30",
+            Term::app(Term::app(add(), church(20)), church(30)),
+        ),
+        ("exp_3_3", Term::app(church(3), church(3))),
+    ]
+}
+
+fn normalize_levels(term: Term) -> Rc<BruijnLevelsTerm> {
+    let mut b = BruijnLevelsTerm::from_open_term(term);
+    while !b.is_normal_form() {
+        b = b.reduce_step_normal_order();
+    }
+    b
+}
+
+fn normalize_arena(term: Term) -> (TermArena, lambubu::arena::TermId) {
+    let mut arena = TermArena::new();
+    let mut id = arena.insert_term(term);
+    while !arena.is_normal_form(id) {
+        id = arena.reduce_step_normal_order(id);
+    }
+    (arena, id)
+}
+
+fn collect_nodes(term: &Rc<BruijnLevelsTerm>, seen: &mut HashSet<*const BruijnLevelsTerm>) {
+    if !seen.insert(Rc::as_ptr(term)) {
+        return;
+    }
+    match term.as_ref() {
+        BruijnLevelsTerm::Var(_, _) => {}
+        BruijnLevelsTerm::Abs(_, body, _) => collect_nodes(body, seen),
+        BruijnLevelsTerm::Apply(t1, t2) => {
+            collect_nodes(t1, seen);
+            collect_nodes(t2, seen);
+        }
+    }
+}
+
+fn report_memory() {
+    for (name, term) in workloads() {
+        let mut b = BruijnLevelsTerm::from_open_term(term.clone());
+        let mut history = vec![b.clone()];
+        while !b.is_normal_form() {
+            b = b.reduce_step_normal_order();
+            history.push(b.clone());
+        }
+        let mut seen = HashSet::new();
+        for step in &history {
+            collect_nodes(step, &mut seen);
+        }
+        let (arena, _) = normalize_arena(term);
+        println!(
+            "{name}: {rc} rc nodes allocated, {interned} arena nodes",
+            rc = seen.len(),
+            interned = arena.len()
+        );
+    }
+}
+
+fn bench_normalize(c: &mut Criterion) {
+    report_memory();
+    for (name, term) in workloads() {
+        let mut group = c.benchmark_group(name);
+        group.bench_function("levels", |b| {
+            b.iter(|| normalize_levels(black_box(term.clone())))
+        });
+        group.bench_function("arena", |b| {
+            b.iter(|| normalize_arena(black_box(term.clone())))
+        });
+        group.bench_function("closure", |b| {
+            b.iter(|| {
+                CompiledTerm::new(&BruijnLevelsTerm::from_open_term(black_box(term.clone())))
+                    .normalize()
+                    .unwrap()
+            })
+        });
+        let compiled = CompiledTerm::new(&BruijnLevelsTerm::from_open_term(term.clone()));
+        group.bench_function("closure_precompiled", |b| {
+            b.iter(|| black_box(&compiled).normalize().unwrap())
+        });
+        group.finish();
+    }
+}
+
+criterion_group!(benches, bench_normalize);
+criterion_main!(benches);
@@ -0,0 +1,311 @@
+use crate::{BruijnLevelsTerm, Term};
+use std::{collections::HashMap, rc::Rc};
+
+/// Substitution results remembered before the memo is cleared.
+const MAX_SUBSTITUTIONS: usize = 1 << 16;
+
+/// The levels and names of the enclosing abstractions during read-back.
+type Binders = Vec<(u16, Rc<str>)>;
+
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct TermId(u32);
+
+impl TermId {
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// A node in nameless form. Only free variables keep their name, as it is
+/// what identifies them.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum ArenaNode {
+    Var(u16, Option<Rc<str>>),
+    Abs(u16, TermId),
+    Apply(TermId, TermId),
+}
+
+/// Hash-consing store for [`BruijnLevelsTerm`] nodes.
+///
+/// Nodes are interned in nameless form, so two [`TermId`]s from the same
+/// arena are equal exactly when the terms they denote are alpha-equivalent.
+/// An abstraction keeps the binder name it was first interned with, which is
+/// the name it reads back with.
+#[derive(Clone, Debug, Default)]
+pub struct TermArena {
+    nodes: Vec<ArenaNode>,
+    index: HashMap<ArenaNode, TermId>,
+    names: Vec<Option<Rc<str>>>,
+    normal_forms: Vec<Option<bool>>,
+    substitutions: HashMap<(TermId, u16, TermId, u16), TermId>,
+}
+
+impl TermArena {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn get(&self, id: TermId) -> &ArenaNode {
+        &self.nodes[id.index()]
+    }
+
+    /// The binder name of an abstraction.
+    pub fn name(&self, id: TermId) -> Option<&Rc<str>> {
+        self.names[id.index()].as_ref()
+    }
+
+    pub fn intern(&mut self, node: ArenaNode) -> TermId {
+        if let Some(id) = self.index.get(&node) {
+            return *id;
+        }
+        let id = TermId(self.nodes.len() as u32);
+        self.nodes.push(node.clone());
+        self.names.push(None);
+        self.normal_forms.push(None);
+        self.index.insert(node, id);
+        id
+    }
+
+    /// A variable, named if it is free.
+    pub fn var(&mut self, level: u16, name: Option<Rc<str>>) -> TermId {
+        self.intern(ArenaNode::Var(level, name))
+    }
+
+    pub fn abs(&mut self, level: u16, body: TermId, name: Rc<str>) -> TermId {
+        let id = self.intern(ArenaNode::Abs(level, body));
+        self.names[id.index()].get_or_insert(name);
+        id
+    }
+
+    pub fn app(&mut self, t1: TermId, t2: TermId) -> TermId {
+        self.intern(ArenaNode::Apply(t1, t2))
+    }
+
+    pub fn insert(&mut self, term: &BruijnLevelsTerm) -> TermId {
+        self.insert_bound(term, &mut Vec::new())
+    }
+
+    fn insert_bound(&mut self, term: &BruijnLevelsTerm, bound: &mut Vec<u16>) -> TermId {
+        match term {
+            BruijnLevelsTerm::Var(lvl, _) if bound.contains(lvl) => self.var(*lvl, None),
+            BruijnLevelsTerm::Var(lvl, name) => self.var(*lvl, Some(name.clone())),
+            BruijnLevelsTerm::Abs(lvl, body, name) => {
+                bound.push(*lvl);
+                let body = self.insert_bound(body, bound);
+                bound.pop();
+                self.abs(*lvl, body, name.clone())
+            }
+            BruijnLevelsTerm::Apply(t1, t2) => {
+                let t1 = self.insert_bound(t1, bound);
+                let t2 = self.insert_bound(t2, bound);
+                self.app(t1, t2)
+            }
+        }
+    }
+
+    pub fn insert_term(&mut self, term: Term) -> TermId {
+        self.insert(&BruijnLevelsTerm::from_open_term(term))
+    }
+
+    /// Reads a term back, naming every bound variable after its binder.
+    pub fn to_levels(&self, id: TermId) -> Rc<BruijnLevelsTerm> {
+        self.to_levels_shared(id, &mut Vec::new(), &mut HashMap::new())
+    }
+
+    fn to_levels_shared(
+        &self,
+        id: TermId,
+        binders: &mut Binders,
+        built: &mut HashMap<(TermId, Binders), Rc<BruijnLevelsTerm>>,
+    ) -> Rc<BruijnLevelsTerm> {
+        let key = (id, binders.clone());
+        if let Some(term) = built.get(&key) {
+            return term.clone();
+        }
+        let term = Rc::new(match self.get(id) {
+            ArenaNode::Var(lvl, Some(name)) => BruijnLevelsTerm::Var(*lvl, name.clone()),
+            ArenaNode::Var(lvl, None) => {
+                let name = binders.iter().rev().find(|(binder, _)| binder == lvl);
+                BruijnLevelsTerm::Var(*lvl, name.map_or_else(|| Rc::from(""), |(_, n)| n.clone()))
+            }
+            ArenaNode::Abs(lvl, body) => {
+                let name = self.binder_name(id);
+                binders.push((*lvl, name.clone()));
+                let body = self.to_levels_shared(*body, binders, built);
+                binders.pop();
+                BruijnLevelsTerm::Abs(*lvl, body, name)
+            }
+            ArenaNode::Apply(t1, t2) => BruijnLevelsTerm::Apply(
+                self.to_levels_shared(*t1, binders, built),
+                self.to_levels_shared(*t2, binders, built),
+            ),
+        });
+        built.insert(key, term.clone());
+        term
+    }
+
+    pub fn to_term(&self, id: TermId) -> Term {
+        Term::from((*self.to_levels(id)).clone())
+    }
+
+    fn binder_name(&self, id: TermId) -> Rc<str> {
+        self.name(id).cloned().unwrap_or_else(|| Rc::from(""))
+    }
+
+    fn shift(&mut self, id: TermId, from: u16, by: u16) -> TermId {
+        if by == 0 {
+            return id;
+        }
+        match self.get(id).clone() {
+            ArenaNode::Var(lvl, name) if lvl >= from => self.var(lvl + by, name),
+            ArenaNode::Abs(lvl, body) => {
+                let name = self.binder_name(id);
+                let body = self.shift(body, from, by);
+                self.abs(if lvl >= from { lvl + by } else { lvl }, body, name)
+            }
+            ArenaNode::Apply(t1, t2) => {
+                let t1 = self.shift(t1, from, by);
+                let t2 = self.shift(t2, from, by);
+                self.app(t1, t2)
+            }
+            _ => id,
+        }
+    }
+
+    pub fn substitute(&mut self, id: TermId, what: u16, with: TermId) -> TermId {
+        self.substitute_at(id, what, with, what + 1)
+    }
+
+    fn substitute_at(&mut self, id: TermId, what: u16, with: TermId, depth: u16) -> TermId {
+        if let Some(result) = self.substitutions.get(&(id, what, with, depth)) {
+            return *result;
+        }
+        if self.substitutions.len() >= MAX_SUBSTITUTIONS {
+            self.substitutions.clear();
+        }
+        let result = match self.get(id).clone() {
+            ArenaNode::Var(lvl, _) if lvl == what => self.shift(with, what, depth - what - 1),
+            ArenaNode::Var(lvl, name) if lvl > what => self.var(lvl - 1, name),
+            ArenaNode::Abs(lvl, body) => {
+                let name = self.binder_name(id);
+                let body = self.substitute_at(body, what, with, depth + 1);
+                self.abs(lvl - 1, body, name)
+            }
+            ArenaNode::Apply(t1, t2) => {
+                let t1 = self.substitute_at(t1, what, with, depth);
+                let t2 = self.substitute_at(t2, what, with, depth);
+                self.app(t1, t2)
+            }
+            _ => id,
+        };
+        self.substitutions.insert((id, what, with, depth), result);
+        result
+    }
+
+    pub fn is_value(&self, id: TermId) -> bool {
+        matches!(self.get(id), ArenaNode::Var(_, _) | ArenaNode::Abs(_, _))
+    }
+
+    pub fn is_normal_form(&mut self, id: TermId) -> bool {
+        if let Some(result) = self.normal_forms[id.index()] {
+            return result;
+        }
+        let result = match self.get(id).clone() {
+            ArenaNode::Var(_, _) => true,
+            ArenaNode::Abs(_, body) => self.is_normal_form(body),
+            ArenaNode::Apply(t1, t2) => match self.get(t1) {
+                ArenaNode::Abs(_, _) => false,
+                _ => self.is_normal_form(t1) && self.is_normal_form(t2),
+            },
+        };
+        self.normal_forms[id.index()] = Some(result);
+        result
+    }
+
+    pub fn reduce_step_call_by_name(&mut self, id: TermId) -> TermId {
+        match self.get(id).clone() {
+            ArenaNode::Apply(t1, t2) => match self.get(t1).clone() {
+                ArenaNode::Abs(lvl, body) => self.substitute(body, lvl, t2),
+                _ => {
+                    let t1 = self.reduce_step_call_by_name(t1);
+                    self.app(t1, t2)
+                }
+            },
+            _ => id,
+        }
+    }
+
+    pub fn reduce_step_normal_order(&mut self, id: TermId) -> TermId {
+        match self.get(id).clone() {
+            ArenaNode::Apply(t1, t2) => match self.get(t1).clone() {
+                ArenaNode::Abs(lvl, body) => self.substitute(body, lvl, t2),
+                _ if !self.is_normal_form(t1) => {
+                    let t1 = self.reduce_step_normal_order(t1);
+                    self.app(t1, t2)
+                }
+                _ => {
+                    let t2 = self.reduce_step_normal_order(t2);
+                    self.app(t1, t2)
+                }
+            },
+            ArenaNode::Abs(lvl, body) => {
+                let name = self.binder_name(id);
+                let body = self.reduce_step_normal_order(body);
+                self.abs(lvl, body, name)
+            }
+            _ => id,
+        }
+    }
+
+    pub fn reduce_step_call_by_value(&mut self, id: TermId) -> TermId {
+        match self.get(id).clone() {
+            ArenaNode::Var(_, _) | ArenaNode::Abs(_, _) => id,
+            ArenaNode::Apply(t1, t2) => {
+                if let ArenaNode::Abs(lvl, body) = self.get(t1).clone() {
+                    if self.is_value(t2) {
+                        self.substitute(body, lvl, t2)
+                    } else {
+                        let t2 = self.reduce_step_call_by_value(t2);
+                        self.app(t1, t2)
+                    }
+                } else {
+                    let t1 = self.reduce_step_call_by_value(t1);
+                    self.app(t1, t2)
+                }
+            }
+        }
+    }
+
+    pub fn reduce_step_applicative_order(&mut self, id: TermId) -> TermId {
+        match self.get(id).clone() {
+            ArenaNode::Apply(t1, t2) => {
+                if !self.is_normal_form(t1) {
+                    let t1 = self.reduce_step_applicative_order(t1);
+                    self.app(t1, t2)
+                } else if !self.is_normal_form(t2) {
+                    let t2 = self.reduce_step_applicative_order(t2);
+                    self.app(t1, t2)
+                } else {
+                    match self.get(t1).clone() {
+                        ArenaNode::Abs(lvl, body) => self.substitute(body, lvl, t2),
+                        _ => id,
+                    }
+                }
+            }
+            ArenaNode::Abs(lvl, body) => {
+                let name = self.binder_name(id);
+                let body = self.reduce_step_applicative_order(body);
+                self.abs(lvl, body, name)
+            }
+            _ => id,
+        }
+    }
+}
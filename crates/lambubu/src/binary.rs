@@ -0,0 +1,371 @@
+//! Compact binary format for sequences of terms.
+//!
+//! A stream starts with the magic bytes `LMBU` and a version byte, followed by
+//! records. Every record is a tag byte and its operands, all integers being
+//! LEB128 varints:
+//!
+//! - `0 len bytes`: appends a UTF-8 name to the name table
+//! - `1 level name`: appends a variable node
+//! - `2 level name body`: appends an abstraction node
+//! - `3 function argument`: appends an application node
+//! - `4 node`: emits the term rooted at `node`
+//!
+//! Terms are stored with de Bruijn levels, free variables taking the lowest
+//! levels. Node operands are back-references, written as the distance from
+//! the node being defined, and identical subterms are written once per stream,
+//! also across terms.
+
+use crate::{BruijnLevelsTerm, Term, levels::ScopeError};
+use std::{
+    collections::HashMap,
+    io::{self, Read, Write},
+    rc::Rc,
+};
+
+pub const MAGIC: &[u8; 4] = b"LMBU";
+pub const VERSION: u8 = 1;
+
+const TAG_NAME: u8 = 0;
+const TAG_VAR: u8 = 1;
+const TAG_ABS: u8 = 2;
+const TAG_APPLY: u8 = 3;
+const TAG_TERM: u8 = 4;
+
+/// Nodes a term read by [`BinaryReader::read_term`] may expand to by default.
+pub const DEFAULT_MAX_TERM_SIZE: usize = 1 << 20;
+
+#[derive(Debug, thiserror::Error)]
+pub enum BinaryError {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error("not a lambubu binary stream")]
+    BadMagic,
+    #[error("unsupported format version {0}")]
+    UnsupportedVersion(u8),
+    #[error("unknown record tag {0}")]
+    UnknownTag(u8),
+    #[error("undefined {kind} reference {reference}")]
+    BadReference { kind: &'static str, reference: u64 },
+    #[error("name is not valid UTF-8")]
+    InvalidName,
+    #[error("level {0} does not fit in 16 bits")]
+    LevelOverflow(u64),
+    #[error("level {level} is not in scope at depth {depth}")]
+    LevelOutOfScope { level: u16, depth: u16 },
+    #[error("term expands to more than {limit} nodes")]
+    TermTooLarge { limit: usize },
+}
+
+fn write_varint(writer: &mut impl Write, mut value: u64) -> io::Result<()> {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            return writer.write_all(&[byte]);
+        }
+        writer.write_all(&[byte | 0x80])?;
+    }
+}
+
+fn read_byte(reader: &mut impl Read) -> io::Result<Option<u8>> {
+    let mut byte = [0];
+    loop {
+        return match reader.read(&mut byte) {
+            Ok(0) => Ok(None),
+            Ok(_) => Ok(Some(byte[0])),
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => Err(err),
+        };
+    }
+}
+
+fn read_varint(reader: &mut impl Read) -> io::Result<u64> {
+    let mut value = 0;
+    let mut shift = 0;
+    loop {
+        let byte = read_byte(reader)?.ok_or(io::ErrorKind::UnexpectedEof)?;
+        if shift >= 64 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "varint too long",
+            ));
+        }
+        value |= ((byte & 0x7f) as u64) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+        shift += 7;
+    }
+}
+
+/// A record as written, with its operands resolved to stream indices, so
+/// identical subterms are written once per stream.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+enum Record {
+    Var(u16, u64),
+    Abs(u16, u64, u64),
+    Apply(u64, u64),
+}
+
+pub struct BinaryWriter<W: Write> {
+    writer: W,
+    nodes: HashMap<Record, u64>,
+    names: HashMap<Rc<str>, u64>,
+}
+
+impl<W: Write> BinaryWriter<W> {
+    pub fn new(mut writer: W) -> io::Result<Self> {
+        writer.write_all(MAGIC)?;
+        writer.write_all(&[VERSION])?;
+        Ok(Self {
+            writer,
+            nodes: HashMap::new(),
+            names: HashMap::new(),
+        })
+    }
+
+    pub fn write_term(&mut self, term: &Term) -> io::Result<()> {
+        self.write_levels(&BruijnLevelsTerm::from_open_term(term.clone()))
+    }
+
+    pub fn write_levels(&mut self, term: &BruijnLevelsTerm) -> io::Result<()> {
+        let node = self.write_node(term, &mut HashMap::new())?;
+        self.writer.write_all(&[TAG_TERM])?;
+        write_varint(&mut self.writer, self.nodes.len() as u64 - node)
+    }
+
+    fn write_name(&mut self, name: &Rc<str>) -> io::Result<u64> {
+        if let Some(index) = self.names.get(name) {
+            return Ok(*index);
+        }
+        self.writer.write_all(&[TAG_NAME])?;
+        write_varint(&mut self.writer, name.len() as u64)?;
+        self.writer.write_all(name.as_bytes())?;
+        let index = self.names.len() as u64;
+        self.names.insert(name.clone(), index);
+        Ok(index)
+    }
+
+    /// Writes `term` unless an identical record is already in the stream.
+    /// `written` remembers the nodes of this term, so shared subterms are
+    /// visited once.
+    fn write_node(
+        &mut self,
+        term: &BruijnLevelsTerm,
+        written: &mut HashMap<*const BruijnLevelsTerm, u64>,
+    ) -> io::Result<u64> {
+        if let Some(index) = written.get(&(term as *const _)) {
+            return Ok(*index);
+        }
+        let record = match term {
+            BruijnLevelsTerm::Var(lvl, name) => Record::Var(*lvl, self.write_name(name)?),
+            BruijnLevelsTerm::Abs(lvl, body, name) => {
+                let body = self.write_node(body, written)?;
+                Record::Abs(*lvl, body, self.write_name(name)?)
+            }
+            BruijnLevelsTerm::Apply(t1, t2) => {
+                let t1 = self.write_node(t1, written)?;
+                Record::Apply(t1, self.write_node(t2, written)?)
+            }
+        };
+        let index = match self.nodes.get(&record) {
+            Some(index) => *index,
+            None => {
+                let index = self.nodes.len() as u64;
+                match record {
+                    Record::Var(lvl, name) => {
+                        self.writer.write_all(&[TAG_VAR])?;
+                        write_varint(&mut self.writer, lvl as u64)?;
+                        write_varint(&mut self.writer, name)?;
+                    }
+                    Record::Abs(lvl, body, name) => {
+                        self.writer.write_all(&[TAG_ABS])?;
+                        write_varint(&mut self.writer, lvl as u64)?;
+                        write_varint(&mut self.writer, name)?;
+                        write_varint(&mut self.writer, index - body)?;
+                    }
+                    Record::Apply(t1, t2) => {
+                        self.writer.write_all(&[TAG_APPLY])?;
+                        write_varint(&mut self.writer, index - t1)?;
+                        write_varint(&mut self.writer, index - t2)?;
+                    }
+                }
+                self.nodes.insert(record, index);
+                index
+            }
+        };
+        written.insert(term, index);
+        Ok(index)
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+pub struct BinaryReader<R: Read> {
+    reader: R,
+    nodes: Vec<Rc<BruijnLevelsTerm>>,
+    names: Vec<Rc<str>>,
+    max_term_size: usize,
+}
+
+impl<R: Read> BinaryReader<R> {
+    pub fn new(mut reader: R) -> Result<Self, BinaryError> {
+        let mut header = [0; 5];
+        reader
+            .read_exact(&mut header)
+            .map_err(|err| match err.kind() {
+                io::ErrorKind::UnexpectedEof => BinaryError::BadMagic,
+                _ => BinaryError::Io(err),
+            })?;
+        if &header[..4] != MAGIC {
+            return Err(BinaryError::BadMagic);
+        }
+        if header[4] != VERSION {
+            return Err(BinaryError::UnsupportedVersion(header[4]));
+        }
+        Ok(Self {
+            reader,
+            nodes: Vec::new(),
+            names: Vec::new(),
+            max_term_size: DEFAULT_MAX_TERM_SIZE,
+        })
+    }
+
+    /// Bounds the number of nodes of a term returned by
+    /// [`read_term`](Self::read_term). A stream can share subterms, so a few
+    /// records may describe a term with exponentially many nodes once the
+    /// sharing is expanded.
+    pub fn with_max_term_size(mut self, max_term_size: usize) -> Self {
+        self.max_term_size = max_term_size;
+        self
+    }
+
+    fn node(&mut self) -> Result<Rc<BruijnLevelsTerm>, BinaryError> {
+        let distance = read_varint(&mut self.reader)?;
+        if distance == 0 || distance > self.nodes.len() as u64 {
+            return Err(BinaryError::BadReference {
+                kind: "node",
+                reference: distance,
+            });
+        }
+        Ok(self.nodes[self.nodes.len() - distance as usize].clone())
+    }
+
+    fn level(&mut self) -> Result<u16, BinaryError> {
+        let level = read_varint(&mut self.reader)?;
+        u16::try_from(level).map_err(|_| BinaryError::LevelOverflow(level))
+    }
+
+    fn name(&mut self) -> Result<Rc<str>, BinaryError> {
+        let index = read_varint(&mut self.reader)?;
+        self.names
+            .get(index as usize)
+            .cloned()
+            .ok_or(BinaryError::BadReference {
+                kind: "name",
+                reference: index,
+            })
+    }
+
+    /// Reads the next term, keeping the sharing of the stream. Returns `None`
+    /// at the end of the stream.
+    pub fn read_levels(&mut self) -> Result<Option<Rc<BruijnLevelsTerm>>, BinaryError> {
+        loop {
+            let Some(tag) = read_byte(&mut self.reader)? else {
+                return Ok(None);
+            };
+            let node = match tag {
+                TAG_NAME => {
+                    let len = read_varint(&mut self.reader)?;
+                    // The length is untrusted, so the buffer only grows with
+                    // the bytes actually read.
+                    let mut bytes = Vec::new();
+                    (&mut self.reader).take(len).read_to_end(&mut bytes)?;
+                    if bytes.len() as u64 != len {
+                        return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into());
+                    }
+                    let name = String::from_utf8(bytes).map_err(|_| BinaryError::InvalidName)?;
+                    self.names.push(name.into());
+                    continue;
+                }
+                TAG_VAR => {
+                    let lvl = self.level()?;
+                    BruijnLevelsTerm::Var(lvl, self.name()?)
+                }
+                TAG_ABS => {
+                    let lvl = self.level()?;
+                    let name = self.name()?;
+                    BruijnLevelsTerm::Abs(lvl, self.node()?, name)
+                }
+                TAG_APPLY => {
+                    let t1 = self.node()?;
+                    let t2 = self.node()?;
+                    BruijnLevelsTerm::Apply(t1, t2)
+                }
+                TAG_TERM => {
+                    let term = self.node()?;
+                    term.validate_scope().map_err(|err| match err {
+                        ScopeError::UnboundVariable { level, depth, .. }
+                        | ScopeError::MisplacedAbstraction { level, depth, .. } => {
+                            BinaryError::LevelOutOfScope { level, depth }
+                        }
+                        ScopeError::TooDeep { .. } => BinaryError::LevelOverflow(1 << 16),
+                    })?;
+                    return Ok(Some(term));
+                }
+                tag => return Err(BinaryError::UnknownTag(tag)),
+            };
+            self.nodes.push(Rc::new(node));
+        }
+    }
+
+    /// Reads the next term as a tree. Fails with
+    /// [`BinaryError::TermTooLarge`] instead of expanding a term past the
+    /// size limit; [`read_levels`](Self::read_levels) keeps the sharing and
+    /// has no such limit.
+    pub fn read_term(&mut self) -> Result<Option<Term>, BinaryError> {
+        let Some(term) = self.read_levels()? else {
+            return Ok(None);
+        };
+        if expanded_size(&term, &mut HashMap::new()) > self.max_term_size {
+            return Err(BinaryError::TermTooLarge {
+                limit: self.max_term_size,
+            });
+        }
+        Ok(Some(Term::from(term.as_ref().clone())))
+    }
+}
+
+/// Number of nodes of `term` with its sharing expanded, saturating at
+/// `usize::MAX`.
+fn expanded_size(
+    term: &Rc<BruijnLevelsTerm>,
+    sizes: &mut HashMap<*const BruijnLevelsTerm, usize>,
+) -> usize {
+    if let Some(size) = sizes.get(&Rc::as_ptr(term)) {
+        return *size;
+    }
+    let size = match term.as_ref() {
+        BruijnLevelsTerm::Var(_, _) => 1,
+        BruijnLevelsTerm::Abs(_, body, _) => expanded_size(body, sizes).saturating_add(1),
+        BruijnLevelsTerm::Apply(t1, t2) => expanded_size(t1, sizes)
+            .saturating_add(expanded_size(t2, sizes))
+            .saturating_add(1),
+    };
+    sizes.insert(Rc::as_ptr(term), size);
+    size
+}
+
+impl<R: Read> Iterator for BinaryReader<R> {
+    type Item = Result<Term, BinaryError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_term().transpose()
+    }
+}
@@ -0,0 +1,219 @@
+use crate::{BruijnLevelsTerm, Term};
+use std::rc::Rc;
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum BlcError {
+    #[error("free variable `{0}` cannot be encoded")]
+    FreeVariable(String),
+    #[error("unexpected end of input")]
+    UnexpectedEnd,
+    #[error("index {index} is unbound at depth {depth}")]
+    UnboundIndex { index: usize, depth: usize },
+    #[error("term is not a list: {0}")]
+    NotAList(Term),
+    #[error("term is not a bit: {0}")]
+    NotABit(Term),
+    #[error("byte has {0} bits instead of 8")]
+    NotAByte(usize),
+}
+
+fn binder_name(level: u16) -> Rc<str> {
+    let letter = (b'a' + (level % 26) as u8) as char;
+    let primes = "'".repeat(level as usize / 26);
+    format!("{letter}{primes}").into()
+}
+
+fn true_term() -> Term {
+    Term::abs("x", Term::abs("y", Term::var("x")))
+}
+
+fn false_term() -> Term {
+    Term::abs("x", Term::abs("y", Term::var("y")))
+}
+
+fn cons(head: Term, tail: Term) -> Term {
+    Term::abs("f", Term::app(Term::app(Term::var("f"), head), tail))
+}
+
+/// Encodes a closed term in Tromp's binary lambda calculus: `00` for an
+/// abstraction, `01` for an application and `1ⁱ0` for the variable with
+/// de Bruijn index `i`, counted from 1.
+pub fn encode(term: &Term) -> Result<Vec<bool>, BlcError> {
+    if let Some(name) = term.free_vars().into_iter().next() {
+        return Err(BlcError::FreeVariable(name.to_string()));
+    }
+    let mut bits = Vec::new();
+    encode_levels(&BruijnLevelsTerm::from(term.clone()), 0, &mut bits);
+    Ok(bits)
+}
+
+fn encode_levels(term: &BruijnLevelsTerm, depth: u16, bits: &mut Vec<bool>) {
+    match term {
+        BruijnLevelsTerm::Var(lvl, _) => {
+            bits.extend(std::iter::repeat_n(true, (depth - lvl) as usize));
+            bits.push(false);
+        }
+        BruijnLevelsTerm::Abs(_, body, _) => {
+            bits.extend([false, false]);
+            encode_levels(body, depth + 1, bits);
+        }
+        BruijnLevelsTerm::Apply(t1, t2) => {
+            bits.extend([false, true]);
+            encode_levels(t1, depth, bits);
+            encode_levels(t2, depth, bits);
+        }
+    }
+}
+
+/// Decodes one term from the front of `bits`, returning it together with the
+/// number of bits it used.
+pub fn decode(bits: &[bool]) -> Result<(Term, usize), BlcError> {
+    let mut position = 0;
+    let term = decode_levels(bits, &mut position, 0)?;
+    Ok((Term::from(term), position))
+}
+
+fn decode_levels(
+    bits: &[bool],
+    position: &mut usize,
+    depth: u16,
+) -> Result<BruijnLevelsTerm, BlcError> {
+    let mut next = || {
+        let bit = bits.get(*position).copied().ok_or(BlcError::UnexpectedEnd);
+        *position += 1;
+        bit
+    };
+    if next()? {
+        let mut index = 1;
+        while next()? {
+            index += 1;
+        }
+        if index > depth as usize {
+            return Err(BlcError::UnboundIndex {
+                index,
+                depth: depth as usize,
+            });
+        }
+        let level = depth - index as u16;
+        return Ok(BruijnLevelsTerm::Var(level, binder_name(level)));
+    }
+    if next()? {
+        let t1 = decode_levels(bits, position, depth)?;
+        let t2 = decode_levels(bits, position, depth)?;
+        Ok(BruijnLevelsTerm::Apply(Rc::new(t1), Rc::new(t2)))
+    } else {
+        let body = decode_levels(bits, position, depth + 1)?;
+        Ok(BruijnLevelsTerm::Abs(
+            depth,
+            Rc::new(body),
+            binder_name(depth),
+        ))
+    }
+}
+
+pub fn bits_to_string(bits: &[bool]) -> String {
+    bits.iter()
+        .map(|bit| if *bit { '1' } else { '0' })
+        .collect()
+}
+
+/// Reads the `0` and `1` characters of `s`, skipping whitespace. Returns
+/// `None` on any other character.
+pub fn bits_from_str(s: &str) -> Option<Vec<bool>> {
+    s.chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| match c {
+            '0' => Some(false),
+            '1' => Some(true),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Packs bits most significant first, padding the last byte with zeros.
+pub fn bits_to_bytes(bits: &[bool]) -> Vec<u8> {
+    bits.chunks(8)
+        .map(|chunk| {
+            chunk
+                .iter()
+                .enumerate()
+                .fold(0, |byte, (i, bit)| byte | ((*bit as u8) << (7 - i)))
+        })
+        .collect()
+}
+
+pub fn bytes_to_bits(bytes: &[u8]) -> Vec<bool> {
+    bytes
+        .iter()
+        .flat_map(|byte| (0..8).rev().map(move |i| byte & (1 << i) != 0))
+        .collect()
+}
+
+/// Church-encoded list of bits, where `0` is `λx.λy.x` and `1` is `λx.λy.y`.
+pub fn bits_list(bits: &[bool]) -> Term {
+    bits.iter().rev().fold(false_term(), |tail, bit| {
+        cons(if *bit { false_term() } else { true_term() }, tail)
+    })
+}
+
+/// Church-encoded list of bytes, each a list of 8 bits.
+pub fn bytes_list(bytes: &[u8]) -> Term {
+    bytes.iter().rev().fold(false_term(), |tail, byte| {
+        cons(bits_list(&bytes_to_bits(&[*byte])), tail)
+    })
+}
+
+/// Splits a list in normal form into its elements.
+pub fn decode_list(term: &Term) -> Result<Vec<Term>, BlcError> {
+    let mut elements = Vec::new();
+    let mut current = term;
+    loop {
+        if current.alpha_eq(&false_term()) {
+            return Ok(elements);
+        }
+        let Term::Abs(f, body) = current else {
+            return Err(BlcError::NotAList(current.clone()));
+        };
+        let Term::Apply(pair, tail) = body.as_ref() else {
+            return Err(BlcError::NotAList(current.clone()));
+        };
+        let Term::Apply(selector, head) = pair.as_ref() else {
+            return Err(BlcError::NotAList(current.clone()));
+        };
+        if !matches!(selector.as_ref(), Term::Var(v) if v == f)
+            || head.is_free_variable(f)
+            || tail.is_free_variable(f)
+        {
+            return Err(BlcError::NotAList(current.clone()));
+        }
+        elements.push(head.as_ref().clone());
+        current = tail;
+    }
+}
+
+pub fn decode_bit(term: &Term) -> Result<bool, BlcError> {
+    if term.alpha_eq(&true_term()) {
+        Ok(false)
+    } else if term.alpha_eq(&false_term()) {
+        Ok(true)
+    } else {
+        Err(BlcError::NotABit(term.clone()))
+    }
+}
+
+pub fn decode_bits_list(term: &Term) -> Result<Vec<bool>, BlcError> {
+    decode_list(term)?.iter().map(decode_bit).collect()
+}
+
+pub fn decode_bytes_list(term: &Term) -> Result<Vec<u8>, BlcError> {
+    decode_list(term)?
+        .iter()
+        .map(|byte| {
+            let bits = decode_bits_list(byte)?;
+            if bits.len() != 8 {
+                return Err(BlcError::NotAByte(bits.len()));
+            }
+            Ok(bits_to_bytes(&bits)[0])
+        })
+        .collect()
+}
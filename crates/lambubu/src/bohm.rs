@@ -0,0 +1,83 @@
+use crate::Term;
+use std::{
+    fmt::{self, Display, Formatter},
+    rc::Rc,
+};
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum BohmTree {
+    Bottom,
+    Truncated,
+    Node {
+        binders: Vec<Rc<str>>,
+        head: Rc<str>,
+        args: Vec<BohmTree>,
+    },
+}
+
+impl BohmTree {
+    /// Unfolds the Böhm tree of `term` down to `depth` levels, giving each
+    /// subterm `max_steps` head reduction steps to reach head normal form
+    /// before it is considered unsolvable.
+    pub fn new(term: Term, depth: usize, max_steps: usize) -> Self {
+        if depth == 0 {
+            return Self::Truncated;
+        }
+        let mut term = term;
+        let mut steps = 0;
+        while !term.is_hnf() {
+            if steps >= max_steps {
+                return Self::Bottom;
+            }
+            term = term.reduce_step_head();
+            steps += 1;
+        }
+
+        let mut binders = Vec::new();
+        while let Term::Abs(name, body) = term {
+            binders.push(name);
+            term = Rc::unwrap_or_clone(body);
+        }
+        let mut args = Vec::new();
+        while let Term::Apply(t1, t2) = term {
+            args.push(Self::new(Rc::unwrap_or_clone(t2), depth - 1, max_steps));
+            term = Rc::unwrap_or_clone(t1);
+        }
+        args.reverse();
+        let Term::Var(head) = term else {
+            unreachable!()
+        };
+        Self::Node {
+            binders,
+            head,
+            args,
+        }
+    }
+}
+
+impl Display for BohmTree {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            BohmTree::Bottom => write!(f, "⊥"),
+            BohmTree::Truncated => write!(f, "…"),
+            BohmTree::Node {
+                binders,
+                head,
+                args,
+            } => {
+                for binder in binders {
+                    write!(f, "λ{binder}.")?;
+                }
+                if args.is_empty() {
+                    write!(f, "{head}")
+                } else {
+                    write!(f, "({head}")?;
+                    for arg in args {
+                        write!(f, " {arg}")?;
+                    }
+                    write!(f, ")")
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,227 @@
+use crate::{Strategy, sync::BruijnLevelsTerm};
+use dashmap::DashMap;
+use std::{
+    ops::ControlFlow,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum CacheError {
+    #[error("max steps exceeded after {0} steps")]
+    MaxStepsExceeded(usize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: usize,
+    pub misses: usize,
+    pub entries: usize,
+}
+
+/// Memo table from alpha-normalized terms to their results under a strategy.
+/// It holds [`sync`](crate::sync) terms, so one cache can be shared by the
+/// threads of a parallel evaluation.
+///
+/// Once `capacity` entries are stored, new results are computed but no longer
+/// remembered.
+#[derive(Debug)]
+pub struct NormalFormCache {
+    entries: DashMap<(Strategy, BruijnLevelsTerm), Arc<BruijnLevelsTerm>>,
+    capacity: usize,
+    hits: AtomicUsize,
+    misses: AtomicUsize,
+}
+
+impl NormalFormCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: DashMap::new(),
+            capacity,
+            hits: AtomicUsize::new(0),
+            misses: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn get(
+        &self,
+        term: &BruijnLevelsTerm,
+        strategy: Strategy,
+    ) -> Option<Arc<BruijnLevelsTerm>> {
+        let result = self
+            .entries
+            .get(&(strategy, term.alpha_normalized()))
+            .map(|entry| entry.value().clone());
+        match result {
+            Some(_) => self.hits.fetch_add(1, Ordering::Relaxed),
+            None => self.misses.fetch_add(1, Ordering::Relaxed),
+        };
+        result
+    }
+
+    pub fn insert(
+        &self,
+        term: &BruijnLevelsTerm,
+        strategy: Strategy,
+        result: Arc<BruijnLevelsTerm>,
+    ) {
+        if self.entries.len() < self.capacity {
+            self.entries
+                .insert((strategy, term.alpha_normalized()), result);
+        }
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            entries: self.entries.len(),
+        }
+    }
+
+    pub fn clear(&self) {
+        self.entries.clear();
+        self.hits.store(0, Ordering::Relaxed);
+        self.misses.store(0, Ordering::Relaxed);
+    }
+
+    pub fn normalize(
+        &self,
+        term: Arc<BruijnLevelsTerm>,
+        strategy: Strategy,
+        max_steps: usize,
+    ) -> Result<Arc<BruijnLevelsTerm>, CacheError> {
+        self.normalize_counting(term, strategy, max_steps)
+            .map(|(result, _)| result)
+    }
+
+    /// Like [`normalize`](Self::normalize), also returning the number of
+    /// steps taken. Steps saved by cache hits are not counted.
+    pub fn normalize_counting(
+        &self,
+        term: Arc<BruijnLevelsTerm>,
+        strategy: Strategy,
+        max_steps: usize,
+    ) -> Result<(Arc<BruijnLevelsTerm>, usize), CacheError> {
+        let mut steps = 0;
+        let result = self.evaluate(term, strategy, &mut steps, max_steps)?;
+        Ok((result, steps))
+    }
+
+    /// Counts a step and contracts the redex `(λlvl.body) arg`.
+    fn contract(
+        lvl: u16,
+        body: &Arc<BruijnLevelsTerm>,
+        arg: Arc<BruijnLevelsTerm>,
+        steps: &mut usize,
+        max_steps: usize,
+    ) -> Result<Arc<BruijnLevelsTerm>, CacheError> {
+        if *steps >= max_steps {
+            return Err(CacheError::MaxStepsExceeded(*steps));
+        }
+        *steps += 1;
+        Ok(body.clone().substitute(lvl, arg))
+    }
+
+    /// Evaluates `term`, looping on contracted redexes instead of recursing,
+    /// so the stack grows with the depth of the term and not with the number
+    /// of steps. Every term on the way shares the final result.
+    fn evaluate(
+        &self,
+        mut term: Arc<BruijnLevelsTerm>,
+        strategy: Strategy,
+        steps: &mut usize,
+        max_steps: usize,
+    ) -> Result<Arc<BruijnLevelsTerm>, CacheError> {
+        let mut reduced = Vec::new();
+        let result = loop {
+            if term.is_terminal(strategy) {
+                break term;
+            }
+            if let Some(result) = self.get(&term, strategy) {
+                break result;
+            }
+            let next = match (term.as_ref(), strategy) {
+                (_, Strategy::NormalOrderEta | Strategy::ApplicativeOrderEta) => {
+                    let mut current = term.clone();
+                    while !current.is_terminal(strategy) {
+                        if *steps >= max_steps {
+                            return Err(CacheError::MaxStepsExceeded(*steps));
+                        }
+                        *steps += 1;
+                        current = current.reduce_step(strategy);
+                    }
+                    ControlFlow::Break(current)
+                }
+                (BruijnLevelsTerm::Abs(lvl, body, name), _) => {
+                    ControlFlow::Break(Arc::new(BruijnLevelsTerm::Abs(
+                        *lvl,
+                        self.evaluate(body.clone(), strategy, steps, max_steps)?,
+                        name.clone(),
+                    )))
+                }
+                (
+                    BruijnLevelsTerm::Apply(t1, t2),
+                    Strategy::CallByName | Strategy::NormalOrder | Strategy::Head,
+                ) => {
+                    let head = self.evaluate(t1.clone(), Strategy::CallByName, steps, max_steps)?;
+                    match (head.as_ref(), strategy) {
+                        (BruijnLevelsTerm::Abs(lvl, body, _), _) => ControlFlow::Continue(
+                            Self::contract(*lvl, body, t2.clone(), steps, max_steps)?,
+                        ),
+                        (_, Strategy::CallByName | Strategy::Head) => {
+                            ControlFlow::Break(Arc::new(BruijnLevelsTerm::Apply(head, t2.clone())))
+                        }
+                        _ => ControlFlow::Break(Arc::new(BruijnLevelsTerm::Apply(
+                            self.evaluate(head, strategy, steps, max_steps)?,
+                            self.evaluate(t2.clone(), strategy, steps, max_steps)?,
+                        ))),
+                    }
+                }
+                (BruijnLevelsTerm::Apply(t1, t2), Strategy::CallByValue) => {
+                    let head = self.evaluate(t1.clone(), strategy, steps, max_steps)?;
+                    match head.as_ref() {
+                        BruijnLevelsTerm::Abs(lvl, body, _) => {
+                            let arg = self.evaluate(t2.clone(), strategy, steps, max_steps)?;
+                            if arg.is_value() {
+                                ControlFlow::Continue(Self::contract(
+                                    *lvl, body, arg, steps, max_steps,
+                                )?)
+                            } else {
+                                ControlFlow::Break(Arc::new(BruijnLevelsTerm::Apply(
+                                    head.clone(),
+                                    arg,
+                                )))
+                            }
+                        }
+                        _ => {
+                            ControlFlow::Break(Arc::new(BruijnLevelsTerm::Apply(head, t2.clone())))
+                        }
+                    }
+                }
+                (BruijnLevelsTerm::Apply(t1, t2), Strategy::ApplicativeOrder) => {
+                    let head = self.evaluate(t1.clone(), strategy, steps, max_steps)?;
+                    let arg = self.evaluate(t2.clone(), strategy, steps, max_steps)?;
+                    match head.as_ref() {
+                        BruijnLevelsTerm::Abs(lvl, body, _) => ControlFlow::Continue(
+                            Self::contract(*lvl, body, arg, steps, max_steps)?,
+                        ),
+                        _ => ControlFlow::Break(Arc::new(BruijnLevelsTerm::Apply(head, arg))),
+                    }
+                }
+                (BruijnLevelsTerm::Var(_, _), _) => ControlFlow::Break(term.clone()),
+            };
+            reduced.push(term);
+            match next {
+                ControlFlow::Continue(contractum) => term = contractum,
+                ControlFlow::Break(result) => break result,
+            }
+        };
+        for term in reduced {
+            self.insert(&term, strategy, result.clone());
+        }
+        Ok(result)
+    }
+}
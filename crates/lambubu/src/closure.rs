@@ -0,0 +1,192 @@
+use crate::{BruijnLevelsTerm, Term};
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ClosureError {
+    #[error("max steps exceeded after {0} steps")]
+    MaxStepsExceeded(usize),
+}
+
+type Env = Rc<Vec<Thunk>>;
+type Code = dyn Fn(&Env, &Runtime) -> Result<Value, ClosureError>;
+
+#[derive(Clone)]
+enum Value {
+    Lambda(Rc<Code>, Env, Rc<str>),
+    Neutral(Rc<Neutral>),
+}
+
+enum Neutral {
+    Var(u16, Rc<str>),
+    Apply(Rc<Neutral>, Thunk),
+}
+
+enum ThunkState {
+    Delayed(Rc<Code>, Env),
+    Forced(Value),
+}
+
+#[derive(Clone)]
+struct Thunk(Rc<RefCell<ThunkState>>);
+
+impl Thunk {
+    fn forced(value: Value) -> Self {
+        Self(Rc::new(RefCell::new(ThunkState::Forced(value))))
+    }
+
+    fn delayed(code: Rc<Code>, env: Env) -> Self {
+        Self(Rc::new(RefCell::new(ThunkState::Delayed(code, env))))
+    }
+
+    fn force(&self, runtime: &Runtime) -> Result<Value, ClosureError> {
+        let (code, env) = match &*self.0.borrow() {
+            ThunkState::Forced(value) => return Ok(value.clone()),
+            ThunkState::Delayed(code, env) => (code.clone(), env.clone()),
+        };
+        let value = code(&env, runtime)?;
+        *self.0.borrow_mut() = ThunkState::Forced(value.clone());
+        Ok(value)
+    }
+}
+
+struct Runtime {
+    steps: Cell<usize>,
+    max_steps: Option<usize>,
+}
+
+impl Runtime {
+    fn apply(&self, function: Value, argument: Thunk) -> Result<Value, ClosureError> {
+        match function {
+            Value::Lambda(body, env, _) => {
+                let steps = self.steps.get();
+                if self.max_steps.is_some_and(|max| steps >= max) {
+                    return Err(ClosureError::MaxStepsExceeded(steps));
+                }
+                self.steps.set(steps + 1);
+                let mut env = (*env).clone();
+                env.push(argument);
+                body(&Rc::new(env), self)
+            }
+            Value::Neutral(neutral) => {
+                Ok(Value::Neutral(Rc::new(Neutral::Apply(neutral, argument))))
+            }
+        }
+    }
+
+    fn quote(&self, value: Value, depth: u16) -> Result<Rc<BruijnLevelsTerm>, ClosureError> {
+        match value {
+            Value::Lambda(body, env, name) => {
+                let var = Value::Neutral(Rc::new(Neutral::Var(depth, name.clone())));
+                let mut env = (*env).clone();
+                env.push(Thunk::forced(var));
+                let body = self.quote(body(&Rc::new(env), self)?, depth + 1)?;
+                Ok(Rc::new(BruijnLevelsTerm::Abs(depth, body, name)))
+            }
+            Value::Neutral(neutral) => self.quote_neutral(&neutral, depth),
+        }
+    }
+
+    fn quote_neutral(
+        &self,
+        neutral: &Neutral,
+        depth: u16,
+    ) -> Result<Rc<BruijnLevelsTerm>, ClosureError> {
+        match neutral {
+            Neutral::Var(lvl, name) => Ok(Rc::new(BruijnLevelsTerm::Var(*lvl, name.clone()))),
+            Neutral::Apply(function, argument) => Ok(Rc::new(BruijnLevelsTerm::Apply(
+                self.quote_neutral(function, depth)?,
+                self.quote(argument.force(self)?, depth)?,
+            ))),
+        }
+    }
+}
+
+fn compile(term: &BruijnLevelsTerm) -> Rc<Code> {
+    match term {
+        BruijnLevelsTerm::Var(lvl, _) => {
+            let lvl = *lvl as usize;
+            Rc::new(move |env: &Env, runtime: &Runtime| env[lvl].force(runtime))
+        }
+        BruijnLevelsTerm::Abs(_, body, name) => {
+            let body = compile(body);
+            let name = name.clone();
+            Rc::new(move |env: &Env, _: &Runtime| {
+                Ok(Value::Lambda(body.clone(), env.clone(), name.clone()))
+            })
+        }
+        BruijnLevelsTerm::Apply(t1, t2) => {
+            let function = compile(t1);
+            match t2.as_ref() {
+                // a variable argument shares the thunk it is bound to
+                BruijnLevelsTerm::Var(lvl, _) => {
+                    let lvl = *lvl as usize;
+                    Rc::new(move |env: &Env, runtime: &Runtime| {
+                        runtime.apply(function(env, runtime)?, env[lvl].clone())
+                    })
+                }
+                _ => {
+                    let argument = compile(t2);
+                    Rc::new(move |env: &Env, runtime: &Runtime| {
+                        let thunk = Thunk::delayed(argument.clone(), env.clone());
+                        runtime.apply(function(env, runtime)?, thunk)
+                    })
+                }
+            }
+        }
+    }
+}
+
+/// A term compiled once into a tree of Rust closures over an environment
+/// vector indexed by de Bruijn level.
+///
+/// Running it normalizes by evaluation: arguments are passed as shared,
+/// lazily forced thunks, so the normal form is the one normal order reaches,
+/// and the result is read back by evaluating under binders. Steps count the
+/// β-reductions actually performed, which with sharing can be far fewer than
+/// normal order needs.
+pub struct CompiledTerm {
+    code: Rc<Code>,
+    free: Vec<Rc<str>>,
+    max_steps: Option<usize>,
+}
+
+impl CompiledTerm {
+    pub fn new(term: &BruijnLevelsTerm) -> Self {
+        Self {
+            code: compile(term),
+            free: term.free_level_names(),
+            max_steps: None,
+        }
+    }
+
+    pub fn with_max_steps(mut self, max_steps: usize) -> Self {
+        self.max_steps = Some(max_steps);
+        self
+    }
+
+    /// Returns the normal form and the number of β-steps performed.
+    pub fn normalize(&self) -> Result<(Rc<BruijnLevelsTerm>, usize), ClosureError> {
+        let runtime = Runtime {
+            steps: Cell::new(0),
+            max_steps: self.max_steps,
+        };
+        let env: Vec<_> = (0..)
+            .zip(&self.free)
+            .map(|(lvl, name)| {
+                Thunk::forced(Value::Neutral(Rc::new(Neutral::Var(lvl, name.clone()))))
+            })
+            .collect();
+        let depth = env.len() as u16;
+        let value = (self.code)(&Rc::new(env), &runtime)?;
+        let term = runtime.quote(value, depth)?;
+        Ok((term, runtime.steps.get()))
+    }
+
+    pub fn normalize_term(&self) -> Result<(Term, usize), ClosureError> {
+        self.normalize()
+            .map(|(term, steps)| (Term::from((*term).clone()), steps))
+    }
+}
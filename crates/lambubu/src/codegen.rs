@@ -0,0 +1,222 @@
+//! Export of closed terms as JavaScript, Python or Haskell source.
+//!
+//! Variables keep their names, with `'` written as `_` in JavaScript and
+//! Python and host keywords prefixed with `_`. A definition `NAME` becomes
+//! `def_NAME` and the n-th term becomes `termn`, which cannot clash with
+//! variables since those never contain `_` or digits.
+//!
+//! JavaScript and Python terms are curried arrow functions and lambdas. In
+//! Haskell, terms are values of an untyped `Value` type, and `main` prints
+//! each term decoded as a Church numeral, or `Nothing`.
+
+use crate::Term;
+use std::fmt::Write;
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Target {
+    JavaScript,
+    Python,
+    Haskell,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum CodegenError {
+    #[error("free variable `{0}` cannot be exported")]
+    FreeVariable(String),
+}
+
+const JAVASCRIPT_KEYWORDS: &[&str] = &[
+    "arguments",
+    "await",
+    "break",
+    "case",
+    "catch",
+    "class",
+    "const",
+    "continue",
+    "debugger",
+    "default",
+    "delete",
+    "do",
+    "else",
+    "enum",
+    "eval",
+    "export",
+    "extends",
+    "false",
+    "finally",
+    "for",
+    "function",
+    "if",
+    "implements",
+    "import",
+    "in",
+    "instanceof",
+    "interface",
+    "let",
+    "new",
+    "null",
+    "package",
+    "private",
+    "protected",
+    "public",
+    "return",
+    "static",
+    "super",
+    "switch",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typeof",
+    "var",
+    "void",
+    "while",
+    "with",
+    "yield",
+];
+
+const PYTHON_KEYWORDS: &[&str] = &[
+    "and", "as", "assert", "async", "await", "break", "class", "continue", "def", "del", "elif",
+    "else", "except", "finally", "for", "from", "global", "if", "import", "in", "is", "lambda",
+    "nonlocal", "not", "or", "pass", "raise", "return", "try", "while", "with", "yield",
+];
+
+const HASKELL_KEYWORDS: &[&str] = &[
+    "case", "class", "data", "default", "deriving", "do", "else", "foreign", "if", "import", "in",
+    "infix", "infixl", "infixr", "instance", "let", "module", "newtype", "of", "then", "type",
+    "where",
+];
+
+const HASKELL_PRELUDE: &str = "\
+data Value = Fun (Value -> Value) | Num Integer | Stuck
+
+infixl 9 %
+
+(%) :: Value -> Value -> Value
+Fun f % x = f x
+_ % _ = Stuck
+
+toNumeral :: Value -> Maybe Integer
+toNumeral n = case n % Fun inc % Num 0 of
+  Num k -> Just k
+  _ -> Nothing
+  where
+    inc (Num k) = Num (k + 1)
+    inc _ = Stuck
+";
+
+impl Target {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Target::JavaScript => "javascript",
+            Target::Python => "python",
+            Target::Haskell => "haskell",
+        }
+    }
+
+    fn variable(&self, name: &str) -> String {
+        let (keywords, name) = match self {
+            Target::JavaScript => (JAVASCRIPT_KEYWORDS, name.replace('\'', "_")),
+            Target::Python => (PYTHON_KEYWORDS, name.replace('\'', "_")),
+            Target::Haskell => (HASKELL_KEYWORDS, name.to_owned()),
+        };
+        if keywords.contains(&name.as_str()) {
+            format!("_{name}")
+        } else {
+            name
+        }
+    }
+
+    /// Translates a closed term into a single expression.
+    pub fn expression(&self, term: &Term) -> Result<String, CodegenError> {
+        if let Some(name) = term.free_vars().into_iter().next() {
+            return Err(CodegenError::FreeVariable(name.to_string()));
+        }
+        let mut out = String::new();
+        self.write_expression(term, &mut out);
+        Ok(out)
+    }
+
+    fn write_expression(&self, term: &Term, out: &mut String) {
+        match (self, term) {
+            (_, Term::Var(v)) => out.push_str(&self.variable(v)),
+            (Target::JavaScript, Term::Abs(v, body)) => {
+                write!(out, "({}) => ", self.variable(v)).unwrap();
+                self.write_expression(body, out);
+            }
+            (Target::Python, Term::Abs(v, body)) => {
+                write!(out, "lambda {}: ", self.variable(v)).unwrap();
+                self.write_expression(body, out);
+            }
+            (Target::Haskell, Term::Abs(v, body)) => {
+                write!(out, "Fun (\\{} -> ", self.variable(v)).unwrap();
+                self.write_expression(body, out);
+                out.push(')');
+            }
+            (Target::JavaScript | Target::Python, Term::Apply(t1, t2)) => {
+                self.write_parenthesized(t1, matches!(t1.as_ref(), Term::Abs(_, _)), out);
+                self.write_parenthesized(t2, true, out);
+            }
+            (Target::Haskell, Term::Apply(t1, t2)) => {
+                self.write_expression(t1, out);
+                out.push_str(" % ");
+                self.write_parenthesized(t2, matches!(t2.as_ref(), Term::Apply(_, _)), out);
+            }
+        }
+    }
+
+    fn write_parenthesized(&self, term: &Term, parenthesize: bool, out: &mut String) {
+        if parenthesize {
+            out.push('(');
+        }
+        self.write_expression(term, out);
+        if parenthesize {
+            out.push(')');
+        }
+    }
+
+    fn write_binding(&self, name: &str, expression: &str, out: &mut String) {
+        match self {
+            Target::JavaScript => writeln!(out, "const {name} = {expression};"),
+            Target::Python => writeln!(out, "{name} = {expression}"),
+            Target::Haskell => writeln!(out, "\n{name} :: Value\n{name} = {expression}"),
+        }
+        .unwrap();
+    }
+
+    /// Generates a source file binding every definition and term. For
+    /// Haskell, the file is a program printing the terms as numerals.
+    pub fn generate(
+        &self,
+        definitions: &[(&str, &Term)],
+        terms: &[Term],
+    ) -> Result<String, CodegenError> {
+        let mut out = match self {
+            Target::JavaScript => "// Generated by lambubu\n",
+            Target::Python => "# Generated by lambubu\n",
+            Target::Haskell => "-- Generated by lambubu\n",
+        }
+        .to_owned();
+        if *self == Target::Haskell {
+            out.push('\n');
+            out.push_str(HASKELL_PRELUDE);
+        }
+        for (name, term) in definitions {
+            self.write_binding(&format!("def_{name}"), &self.expression(term)?, &mut out);
+        }
+        for (i, term) in terms.iter().enumerate() {
+            self.write_binding(&format!("term{}", i + 1), &self.expression(term)?, &mut out);
+        }
+        if *self == Target::Haskell {
+            let names: Vec<_> = (1..=terms.len()).map(|i| format!("term{i}")).collect();
+            write!(
+                out,
+                "\nmain :: IO ()\nmain = mapM_ (print . toNumeral) [{}]\n",
+                names.join(", ")
+            )
+            .unwrap();
+        }
+        Ok(out)
+    }
+}
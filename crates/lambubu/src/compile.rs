@@ -6,7 +6,12 @@ use pest::{Parser, Span};
 use pest_derive::Parser;
 use std::rc::Rc;
 
+/// The rules every grammar of the workspace builds on. Other crates keep a
+/// copy next to their own grammars and check it against this one.
+pub const BASE_GRAMMAR: &str = include_str!("base.pest");
+
 #[derive(Parser)]
+#[grammar = "base.pest"]
 #[grammar = "grammar.pest"]
 struct LambdaParser;
 
@@ -0,0 +1,211 @@
+use crate::{BruijnLevelsTerm, Strategy, Term};
+#[cfg(feature = "cache")]
+use crate::{NormalFormCache, sync};
+#[cfg(feature = "cache")]
+use std::sync::Arc;
+use std::{
+    collections::{HashMap, VecDeque, hash_map::DefaultHasher},
+    hash::{Hash, Hasher},
+};
+
+/// Intermediate terms sampled per growth window. A sample costs time linear
+/// in the size of the term, so terms are not sampled at every step.
+pub const SAMPLES_PER_WINDOW: usize = 32;
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum EvaluationError {
+    #[error("diverges: cycle of length {length} at step {step}")]
+    Cycle { length: usize, step: usize },
+    #[error("diverges: term size grew from {from} to {to} over the last {steps} steps")]
+    UnboundedGrowth {
+        from: usize,
+        to: usize,
+        steps: usize,
+    },
+    #[error("max steps exceeded after {0} steps")]
+    MaxStepsExceeded(usize),
+}
+
+/// Step-bounded evaluator that explains non-termination.
+///
+/// [`SAMPLES_PER_WINDOW`] times per `growth_window` steps, the intermediate
+/// term is remembered by a hash taken up to alpha-equivalence, so a term that
+/// reduces back to an earlier one is reported as a cycle. On a hash match the
+/// earlier term is recomputed from the start and compared, so only a hash per
+/// sample is kept, and the exact cycle is then found by stepping from it. When
+/// the step budget runs out, the sizes sampled over the last `growth_window`
+/// steps decide whether the term is reported as growing without bound.
+///
+/// With a [`NormalFormCache`], the term is first normalized through the
+/// cache, so subterms already evaluated are looked up instead of reduced
+/// again. Only when that runs out of steps is the term stepped to explain why.
+#[derive(Clone, Debug)]
+pub struct Evaluator {
+    strategy: Strategy,
+    max_steps: usize,
+    growth_window: usize,
+    #[cfg(feature = "cache")]
+    cache: Option<Arc<NormalFormCache>>,
+}
+
+impl Evaluator {
+    pub fn new(strategy: Strategy) -> Self {
+        Self {
+            strategy,
+            max_steps: 10_000,
+            growth_window: 1_000,
+            #[cfg(feature = "cache")]
+            cache: None,
+        }
+    }
+
+    pub fn with_max_steps(mut self, max_steps: usize) -> Self {
+        self.max_steps = max_steps;
+        self
+    }
+
+    pub fn with_growth_window(mut self, growth_window: usize) -> Self {
+        self.growth_window = growth_window;
+        self
+    }
+
+    #[cfg(feature = "cache")]
+    pub fn with_cache(mut self, cache: Arc<NormalFormCache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    pub fn evaluate(&self, mut term: Term) -> Result<(Term, usize), EvaluationError> {
+        #[cfg(feature = "cache")]
+        if let Some(cache) = &self.cache {
+            let levels = sync::BruijnLevelsTerm::from_open_term(sync::Term::from(&term));
+            if let Ok((result, steps)) =
+                cache.normalize_counting(levels, self.strategy, self.max_steps)
+            {
+                return Ok((Term::from(&sync::Term::from((*result).clone())), steps));
+            }
+        }
+        let start = term.clone();
+        let mut seen: HashMap<u64, Vec<usize>> = HashMap::new();
+        let mut sizes = VecDeque::new();
+        let interval = (self.growth_window / SAMPLES_PER_WINDOW).max(1);
+        let mut step = 0;
+        loop {
+            if term.is_terminal(self.strategy) {
+                return Ok((term, step));
+            }
+            if step % interval == 0 || step >= self.max_steps {
+                let (fingerprint, size) = sample(&term);
+                let candidates = seen.entry(fingerprint).or_default();
+                if let Some(&previous) = candidates.iter().find(|&&previous| {
+                    alpha_key(&self.replay(&start, previous)) == alpha_key(&term)
+                }) {
+                    return Err(self.locate_cycle(&start, previous));
+                }
+                candidates.push(step);
+                while sizes
+                    .front()
+                    .is_some_and(|&(first, _)| first + self.growth_window < step)
+                {
+                    sizes.pop_front();
+                }
+                sizes.push_back((step, size));
+            }
+            if step >= self.max_steps {
+                return Err(self.explain_timeout(sizes.make_contiguous(), step));
+            }
+            term = term.reduce_step(self.strategy);
+            step += 1;
+        }
+    }
+
+    fn replay(&self, start: &Term, steps: usize) -> Term {
+        (0..steps).fold(start.clone(), |term, _| term.reduce_step(self.strategy))
+    }
+
+    /// The sampled term at step `previous` comes back later, so the terms
+    /// repeat from there on. Finds the shortest period and the first step at
+    /// which a term repeats, as stepping one at a time would report them.
+    fn locate_cycle(&self, start: &Term, previous: usize) -> EvaluationError {
+        let first = self.replay(start, previous);
+        let key = alpha_key(&first);
+        let mut later = first.reduce_step(self.strategy);
+        let mut length = 1;
+        while alpha_key(&later) != key {
+            later = later.reduce_step(self.strategy);
+            length += 1;
+        }
+        let mut earlier = start.clone();
+        let mut later = self.replay(start, length);
+        let mut step = length;
+        while alpha_key(&earlier) != alpha_key(&later) {
+            earlier = earlier.reduce_step(self.strategy);
+            later = later.reduce_step(self.strategy);
+            step += 1;
+        }
+        EvaluationError::Cycle { length, step }
+    }
+
+    /// `window` holds the step and size of the samples taken over the last
+    /// `growth_window` steps.
+    fn explain_timeout(&self, window: &[(usize, usize)], steps: usize) -> EvaluationError {
+        let quarter = window.len() / 4;
+        if quarter > 0 {
+            let peaks: Vec<usize> = window
+                .chunks(quarter)
+                .take(4)
+                .map(|chunk| chunk.iter().map(|&(_, size)| size).max().unwrap())
+                .collect();
+            let (first, from) = window[0];
+            let (last, to) = *window.last().unwrap();
+            if peaks.windows(2).all(|pair| pair[0] < pair[1]) && to > from {
+                return EvaluationError::UnboundedGrowth {
+                    from,
+                    to,
+                    steps: last - first,
+                };
+            }
+        }
+        EvaluationError::MaxStepsExceeded(steps)
+    }
+}
+
+fn alpha_key(term: &Term) -> BruijnLevelsTerm {
+    BruijnLevelsTerm::from_open_term(term.clone()).alpha_normalized()
+}
+
+/// Hashes `term` up to alpha-equivalence and counts its nodes in one pass,
+/// without building its alpha-normal form. A bound variable hashes as the
+/// depth of its binder and a free one by name.
+fn sample(term: &Term) -> (u64, usize) {
+    fn visit<'a>(
+        term: &'a Term,
+        depth: usize,
+        binders: &mut HashMap<&'a str, Vec<usize>>,
+        hasher: &mut DefaultHasher,
+    ) -> usize {
+        match term {
+            Term::Var(name) => {
+                match binders.get(name.as_ref()).and_then(|levels| levels.last()) {
+                    Some(level) => (0u8, *level).hash(hasher),
+                    None => (1u8, name).hash(hasher),
+                }
+                1
+            }
+            Term::Abs(name, body) => {
+                2u8.hash(hasher);
+                binders.entry(name).or_default().push(depth);
+                let size = visit(body, depth + 1, binders, hasher);
+                binders.get_mut(name.as_ref()).unwrap().pop();
+                size + 1
+            }
+            Term::Apply(t1, t2) => {
+                3u8.hash(hasher);
+                visit(t1, depth, binders, hasher) + visit(t2, depth, binders, hasher) + 1
+            }
+        }
+    }
+    let mut hasher = DefaultHasher::new();
+    let size = visit(term, 0, &mut HashMap::new(), &mut hasher);
+    (hasher.finish(), size)
+}
@@ -9,15 +9,48 @@ pub trait MutableTermEnvironment: TermEnvironment {
     fn add_term(&mut self, name: String, term: Term);
 }
 
+/// Named terms that remember the order their names were first added in.
 #[derive(Clone, Debug, Default)]
-pub struct RegistryEnvironment {
+struct Definitions {
     terms: HashMap<String, Term>,
+    order: Vec<String>,
+}
+
+impl Definitions {
+    fn get(&self, name: &str) -> Option<&Term> {
+        self.terms.get(name)
+    }
+
+    /// Redefining a name replaces its term but keeps its position.
+    fn insert(&mut self, name: String, term: Term) {
+        if self.terms.insert(name.clone(), term).is_none() {
+            self.order.push(name);
+        }
+    }
+
+    fn in_order(&self) -> Vec<(&str, &Term)> {
+        self.order
+            .iter()
+            .map(|name| (name.as_str(), &self.terms[name]))
+            .collect()
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct RegistryEnvironment {
+    terms: Definitions,
 }
 
 impl RegistryEnvironment {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Terms added with [`MutableTermEnvironment::add_term`], in the order
+    /// they were first defined.
+    pub fn definitions(&self) -> Vec<(&str, &Term)> {
+        self.terms.in_order()
+    }
 }
 
 impl TermEnvironment for RegistryEnvironment {
@@ -35,7 +68,7 @@ impl MutableTermEnvironment for RegistryEnvironment {
 #[derive(Default)]
 pub struct CompoundEnvironment {
     envs: Vec<Box<dyn TermEnvironment>>,
-    terms: HashMap<String, Term>,
+    terms: Definitions,
 }
 
 impl MutableTermEnvironment for CompoundEnvironment {
@@ -48,13 +81,19 @@ impl CompoundEnvironment {
     pub fn new(envs: Vec<Box<dyn TermEnvironment>>) -> Self {
         Self {
             envs,
-            terms: HashMap::new(),
+            terms: Definitions::default(),
         }
     }
 
     pub fn decompose(self) -> Vec<Box<dyn TermEnvironment>> {
         self.envs
     }
+
+    /// Terms added with [`MutableTermEnvironment::add_term`], in the order
+    /// they were first defined.
+    pub fn definitions(&self) -> Vec<(&str, &Term)> {
+        self.terms.in_order()
+    }
 }
 
 impl TermEnvironment for CompoundEnvironment {
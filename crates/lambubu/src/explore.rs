@@ -0,0 +1,232 @@
+use crate::{
+    Path, Strategy,
+    levels::GenericBruijnLevelsTerm,
+    pointer::{Pointer, RcPointer},
+    term::GenericTerm,
+    trace::json_string,
+};
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    fmt::Write,
+};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GraphNode<P: Pointer = RcPointer> {
+    pub term: GenericTerm<P>,
+    pub depth: usize,
+    pub normal_form: bool,
+    pub strategies: Vec<Strategy>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GraphEdge {
+    pub from: usize,
+    pub to: usize,
+    pub path: Path,
+    pub strategies: Vec<Strategy>,
+}
+
+/// Graph of every β-reduction sequence starting from a term.
+///
+/// Nodes are alpha-equivalence classes of terms, explored breadth-first up to
+/// `max_depth` steps from the root and at most `max_nodes` nodes. Each node
+/// and edge lists the strategies whose reduction sequence passes through it.
+#[derive(Clone, Debug)]
+pub struct ReductionGraph<P: Pointer = RcPointer> {
+    nodes: Vec<GraphNode<P>>,
+    edges: Vec<GraphEdge>,
+    complete: bool,
+}
+
+fn key<P: Pointer>(term: &GenericTerm<P>) -> GenericBruijnLevelsTerm<P> {
+    GenericBruijnLevelsTerm::from_open_term(term.clone()).alpha_normalized()
+}
+
+fn dot_string(s: &str) -> String {
+    format!(
+        "\"{}\"",
+        s.replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('\n', "\\n")
+    )
+}
+
+impl<P: Pointer> ReductionGraph<P> {
+    pub fn explore(term: GenericTerm<P>, max_depth: usize, max_nodes: usize) -> Self {
+        let mut graph = Self {
+            nodes: Vec::new(),
+            edges: Vec::new(),
+            complete: true,
+        };
+        let mut index = HashMap::from([(key(&term), 0)]);
+        graph.nodes.push(GraphNode {
+            normal_form: term.is_normal_form(),
+            term,
+            depth: 0,
+            strategies: Vec::new(),
+        });
+        let mut queue = VecDeque::from([0]);
+        while let Some(from) = queue.pop_front() {
+            let node = &graph.nodes[from];
+            let paths: Vec<Path> = node.term.redexes().into_iter().map(|(p, _)| p).collect();
+            if !paths.is_empty() && node.depth >= max_depth {
+                graph.complete = false;
+                continue;
+            }
+            let (term, depth) = (node.term.clone(), node.depth);
+            for path in paths {
+                let next = term.clone().contract_at(&path).unwrap();
+                let to = match index.get(&key(&next)) {
+                    Some(to) => *to,
+                    None if graph.nodes.len() >= max_nodes => {
+                        graph.complete = false;
+                        continue;
+                    }
+                    None => {
+                        let to = graph.nodes.len();
+                        index.insert(key(&next), to);
+                        graph.nodes.push(GraphNode {
+                            normal_form: next.is_normal_form(),
+                            term: next,
+                            depth: depth + 1,
+                            strategies: Vec::new(),
+                        });
+                        queue.push_back(to);
+                        to
+                    }
+                };
+                graph.edges.push(GraphEdge {
+                    from,
+                    to,
+                    path,
+                    strategies: Vec::new(),
+                });
+            }
+        }
+        for strategy in Strategy::ALL {
+            graph.mark_strategy(strategy);
+        }
+        graph
+    }
+
+    fn mark_strategy(&mut self, strategy: Strategy) {
+        let mut node = 0;
+        let mut visited = HashSet::new();
+        while visited.insert(node) {
+            self.nodes[node].strategies.push(strategy);
+            let Some((path, _)) = self.nodes[node].term.next_redex(strategy) else {
+                break;
+            };
+            let Some(edge) = self
+                .edges
+                .iter_mut()
+                .find(|edge| edge.from == node && edge.path == path)
+            else {
+                break;
+            };
+            edge.strategies.push(strategy);
+            node = edge.to;
+        }
+    }
+
+    pub fn nodes(&self) -> &[GraphNode<P>] {
+        &self.nodes
+    }
+
+    pub fn edges(&self) -> &[GraphEdge] {
+        &self.edges
+    }
+
+    pub fn root(&self) -> &GraphNode<P> {
+        &self.nodes[0]
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.complete
+    }
+
+    pub fn normal_forms(&self) -> impl Iterator<Item = &GraphNode<P>> {
+        self.nodes.iter().filter(|node| node.normal_form)
+    }
+
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph reductions {\n    node [shape=box];\n");
+        for (i, node) in self.nodes.iter().enumerate() {
+            let mut label = node.term.to_string();
+            if !node.strategies.is_empty() {
+                let names: Vec<String> = node.strategies.iter().map(|s| format!("{s:?}")).collect();
+                write!(label, "\n{}", names.join(", ")).unwrap();
+            }
+            write!(dot, "    n{i} [label={}", dot_string(&label)).unwrap();
+            if node.normal_form {
+                dot.push_str(", peripheries=2");
+            }
+            dot.push_str("];\n");
+        }
+        for edge in &self.edges {
+            write!(
+                dot,
+                "    n{} -> n{} [label={}",
+                edge.from,
+                edge.to,
+                dot_string(&edge.path.to_string())
+            )
+            .unwrap();
+            if !edge.strategies.is_empty() {
+                dot.push_str(", style=bold");
+            }
+            dot.push_str("];\n");
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    pub fn to_json(&self) -> String {
+        let strategies = |strategies: &[Strategy]| {
+            let names: Vec<String> = strategies
+                .iter()
+                .map(|s| json_string(&format!("{s:?}")))
+                .collect();
+            format!("[{}]", names.join(","))
+        };
+        let nodes: Vec<String> = self
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(i, node)| {
+                format!(
+                    "{{\"id\":{i},\"term\":{},\"depth\":{},\"normal_form\":{},\"strategies\":{}}}",
+                    json_string(&node.term.to_string()),
+                    node.depth,
+                    node.normal_form,
+                    strategies(&node.strategies)
+                )
+            })
+            .collect();
+        let edges: Vec<String> = self
+            .edges
+            .iter()
+            .map(|edge| {
+                let path: Vec<String> = edge
+                    .path
+                    .directions()
+                    .iter()
+                    .map(|direction| json_string(direction.name()))
+                    .collect();
+                format!(
+                    "{{\"from\":{},\"to\":{},\"path\":[{}],\"strategies\":{}}}",
+                    edge.from,
+                    edge.to,
+                    path.join(","),
+                    strategies(&edge.strategies)
+                )
+            })
+            .collect();
+        format!(
+            "{{\"nodes\":[{}],\"edges\":[{}],\"complete\":{}}}",
+            nodes.join(","),
+            edges.join(","),
+            self.complete
+        )
+    }
+}
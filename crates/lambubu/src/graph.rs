@@ -0,0 +1,352 @@
+use crate::Term;
+use std::{
+    collections::{BTreeSet, HashMap},
+    rc::Rc,
+};
+
+pub type NodeId = usize;
+
+#[derive(Clone, Debug)]
+enum Node {
+    Free(Rc<str>),
+    Bound(usize),
+    Abs(usize, Rc<str>, NodeId),
+    Apply(NodeId, NodeId),
+    Indirection(NodeId),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum GraphError {
+    #[error("max steps exceeded after {0} steps")]
+    MaxStepsExceeded(usize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GraphStats {
+    pub steps: usize,
+    pub allocated: usize,
+    pub live: usize,
+}
+
+/// Lazy graph reducer over a template-instantiation graph.
+///
+/// Each β-step instantiates the body of the abstraction, sharing the argument
+/// node instead of copying it, and overwrites the redex with an indirection to
+/// the result, so every shared subterm is reduced at most once.
+#[derive(Clone, Debug)]
+pub struct GraphMachine {
+    nodes: Vec<Node>,
+    normalized: Vec<bool>,
+    root: NodeId,
+    binders: usize,
+    steps: usize,
+    max_steps: Option<usize>,
+}
+
+impl GraphMachine {
+    pub fn new(term: &Term) -> Self {
+        let mut machine = Self {
+            nodes: Vec::new(),
+            normalized: Vec::new(),
+            root: 0,
+            binders: 0,
+            steps: 0,
+            max_steps: None,
+        };
+        machine.root = machine.build(term, &HashMap::new());
+        machine
+    }
+
+    pub fn with_max_steps(mut self, max_steps: usize) -> Self {
+        self.max_steps = Some(max_steps);
+        self
+    }
+
+    fn alloc(&mut self, node: Node) -> NodeId {
+        self.nodes.push(node);
+        self.normalized.push(false);
+        self.nodes.len() - 1
+    }
+
+    fn fresh_binder(&mut self) -> usize {
+        self.binders += 1;
+        self.binders - 1
+    }
+
+    fn build(&mut self, term: &Term, scope: &HashMap<Rc<str>, usize>) -> NodeId {
+        match term {
+            Term::Var(name) => match scope.get(name) {
+                Some(binder) => self.alloc(Node::Bound(*binder)),
+                None => self.alloc(Node::Free(name.clone())),
+            },
+            Term::Abs(name, body) => {
+                let binder = self.fresh_binder();
+                let mut scope = scope.clone();
+                scope.insert(name.clone(), binder);
+                let body = self.build(body, &scope);
+                self.alloc(Node::Abs(binder, name.clone(), body))
+            }
+            Term::Apply(t1, t2) => {
+                let t1 = self.build(t1, scope);
+                let t2 = self.build(t2, scope);
+                self.alloc(Node::Apply(t1, t2))
+            }
+        }
+    }
+
+    fn follow(&self, mut node: NodeId) -> NodeId {
+        while let Node::Indirection(next) = self.nodes[node] {
+            node = next;
+        }
+        node
+    }
+
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    pub fn size(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn live_size(&self) -> usize {
+        let mut seen = vec![false; self.nodes.len()];
+        let mut stack = vec![self.root];
+        let mut live = 0;
+        while let Some(node) = stack.pop() {
+            let node = self.follow(node);
+            if seen[node] {
+                continue;
+            }
+            seen[node] = true;
+            live += 1;
+            match self.nodes[node] {
+                Node::Abs(_, _, body) => stack.push(body),
+                Node::Apply(t1, t2) => {
+                    stack.push(t1);
+                    stack.push(t2);
+                }
+                _ => {}
+            }
+        }
+        live
+    }
+
+    pub fn stats(&self) -> GraphStats {
+        GraphStats {
+            steps: self.steps,
+            allocated: self.size(),
+            live: self.live_size(),
+        }
+    }
+
+    pub fn is_whnf(&self) -> bool {
+        let mut node = self.follow(self.root);
+        let mut applied = false;
+        while let Node::Apply(t1, _) = self.nodes[node] {
+            node = self.follow(t1);
+            applied = true;
+        }
+        !(applied && matches!(self.nodes[node], Node::Abs(_, _, _)))
+    }
+
+    pub fn whnf(&mut self) -> Result<(), GraphError> {
+        self.root = self.whnf_node(self.root)?;
+        Ok(())
+    }
+
+    pub fn normalize(&mut self) -> Result<(), GraphError> {
+        self.root = self.normalize_node(self.root)?;
+        Ok(())
+    }
+
+    fn whnf_node(&mut self, node: NodeId) -> Result<NodeId, GraphError> {
+        let top = self.follow(node);
+        let mut spine = Vec::new();
+        let mut current = top;
+        loop {
+            match self.nodes[current] {
+                Node::Apply(t1, _) => {
+                    spine.push(current);
+                    current = self.follow(t1);
+                }
+                Node::Abs(binder, _, body) if !spine.is_empty() => {
+                    if self.max_steps.is_some_and(|max| self.steps >= max) {
+                        return Err(GraphError::MaxStepsExceeded(self.steps));
+                    }
+                    let redex = spine.pop().unwrap();
+                    let Node::Apply(_, arg) = self.nodes[redex] else {
+                        unreachable!()
+                    };
+                    let result = self.instantiate(body, binder, arg);
+                    self.nodes[redex] = Node::Indirection(result);
+                    self.steps += 1;
+                    current = self.follow(result);
+                }
+                _ => break,
+            }
+        }
+        Ok(self.follow(top))
+    }
+
+    fn normalize_node(&mut self, node: NodeId) -> Result<NodeId, GraphError> {
+        let node = self.whnf_node(node)?;
+        if self.normalized[node] {
+            return Ok(node);
+        }
+        match self.nodes[node] {
+            Node::Abs(_, _, body) => {
+                self.normalize_node(body)?;
+            }
+            Node::Apply(t1, t2) => {
+                self.normalize_node(t1)?;
+                self.normalize_node(t2)?;
+            }
+            _ => {}
+        }
+        self.normalized[node] = true;
+        Ok(node)
+    }
+
+    fn free_binders(
+        &self,
+        node: NodeId,
+        memo: &mut HashMap<NodeId, Rc<BTreeSet<usize>>>,
+    ) -> Rc<BTreeSet<usize>> {
+        let node = self.follow(node);
+        if let Some(result) = memo.get(&node) {
+            return result.clone();
+        }
+        let result = match &self.nodes[node] {
+            Node::Free(_) => BTreeSet::new(),
+            Node::Bound(binder) => BTreeSet::from([*binder]),
+            Node::Abs(binder, _, body) => {
+                let mut result = (*self.free_binders(*body, memo)).clone();
+                result.remove(binder);
+                result
+            }
+            Node::Apply(t1, t2) => {
+                let mut result = (*self.free_binders(*t1, memo)).clone();
+                result.extend(self.free_binders(*t2, memo).iter());
+                result
+            }
+            Node::Indirection(_) => unreachable!(),
+        };
+        let result = Rc::new(result);
+        memo.insert(node, result.clone());
+        result
+    }
+
+    fn instantiate(&mut self, body: NodeId, binder: usize, arg: NodeId) -> NodeId {
+        let mut remap = HashMap::from([(binder, arg)]);
+        self.copy(body, &mut remap, &mut HashMap::new(), &mut HashMap::new())
+    }
+
+    fn copy(
+        &mut self,
+        node: NodeId,
+        remap: &mut HashMap<usize, NodeId>,
+        copies: &mut HashMap<NodeId, NodeId>,
+        free: &mut HashMap<NodeId, Rc<BTreeSet<usize>>>,
+    ) -> NodeId {
+        let node = self.follow(node);
+        if let Some(copy) = copies.get(&node) {
+            return *copy;
+        }
+        if !self
+            .free_binders(node, free)
+            .iter()
+            .any(|binder| remap.contains_key(binder))
+        {
+            return node;
+        }
+        let copy = match self.nodes[node].clone() {
+            Node::Bound(binder) => remap[&binder],
+            Node::Abs(binder, name, body) => {
+                let fresh = self.fresh_binder();
+                let var = self.alloc(Node::Bound(fresh));
+                remap.insert(binder, var);
+                let body = self.copy(body, remap, copies, free);
+                self.alloc(Node::Abs(fresh, name, body))
+            }
+            Node::Apply(t1, t2) => {
+                let t1 = self.copy(t1, remap, copies, free);
+                let t2 = self.copy(t2, remap, copies, free);
+                self.alloc(Node::Apply(t1, t2))
+            }
+            Node::Free(_) | Node::Indirection(_) => unreachable!(),
+        };
+        copies.insert(node, copy);
+        copy
+    }
+
+    fn free_names(
+        &self,
+        node: NodeId,
+        memo: &mut HashMap<NodeId, Rc<BTreeSet<Rc<str>>>>,
+    ) -> Rc<BTreeSet<Rc<str>>> {
+        let node = self.follow(node);
+        if let Some(result) = memo.get(&node) {
+            return result.clone();
+        }
+        let result = match &self.nodes[node] {
+            Node::Free(name) => BTreeSet::from([name.clone()]),
+            Node::Bound(_) => BTreeSet::new(),
+            Node::Abs(_, _, body) => (*self.free_names(*body, memo)).clone(),
+            Node::Apply(t1, t2) => {
+                let mut result = (*self.free_names(*t1, memo)).clone();
+                result.extend(self.free_names(*t2, memo).iter().cloned());
+                result
+            }
+            Node::Indirection(_) => unreachable!(),
+        };
+        let result = Rc::new(result);
+        memo.insert(node, result.clone());
+        result
+    }
+
+    pub fn to_term(&self) -> Term {
+        self.read_back(
+            self.root,
+            &mut HashMap::new(),
+            &mut HashMap::new(),
+            &mut HashMap::new(),
+        )
+    }
+
+    fn read_back(
+        &self,
+        node: NodeId,
+        names: &mut HashMap<usize, Rc<str>>,
+        free: &mut HashMap<NodeId, Rc<BTreeSet<usize>>>,
+        free_names: &mut HashMap<NodeId, Rc<BTreeSet<Rc<str>>>>,
+    ) -> Term {
+        let node = self.follow(node);
+        match &self.nodes[node] {
+            Node::Free(name) => Term::Var(name.clone()),
+            Node::Bound(binder) => Term::Var(names[binder].clone()),
+            Node::Abs(binder, name, body) => {
+                let used = self.free_binders(*body, free);
+                let free_in_body = self.free_names(*body, free_names);
+                let mut name = name.clone();
+                while free_in_body.contains(&name)
+                    || used
+                        .iter()
+                        .any(|other| other != binder && names.get(other) == Some(&name))
+                {
+                    name = format!("{name}'").into();
+                }
+                names.insert(*binder, name.clone());
+                Term::Abs(
+                    name,
+                    Rc::new(self.read_back(*body, names, free, free_names)),
+                )
+            }
+            Node::Apply(t1, t2) => Term::Apply(
+                Rc::new(self.read_back(*t1, names, free, free_names)),
+                Rc::new(self.read_back(*t2, names, free, free_names)),
+            ),
+            Node::Indirection(_) => unreachable!(),
+        }
+    }
+}
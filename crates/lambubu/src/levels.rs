@@ -1,67 +1,144 @@
-use crate::Term;
+use crate::{
+    Strategy,
+    pointer::{ArcPointer, Pointer, RcPointer},
+    term::GenericTerm,
+};
 use std::{
     collections::{HashMap, HashSet},
+    fmt::{self, Debug, Formatter},
+    hash::{Hash, Hasher},
     rc::Rc,
+    sync::Arc,
 };
 
-#[derive(Clone, Debug, Eq, Hash, PartialEq)]
-pub enum BruijnLevelsTerm {
-    Var(u16, Rc<str>),
-    Abs(u16, Rc<BruijnLevelsTerm>, Rc<str>),
-    Apply(Rc<BruijnLevelsTerm>, Rc<BruijnLevelsTerm>),
+/// A lambda term with de Bruijn levels, built from the pointer kind `P`.
+pub enum GenericBruijnLevelsTerm<P: Pointer> {
+    Var(u16, P::Name),
+    Abs(u16, P::Ptr<GenericBruijnLevelsTerm<P>>, P::Name),
+    Apply(
+        P::Ptr<GenericBruijnLevelsTerm<P>>,
+        P::Ptr<GenericBruijnLevelsTerm<P>>,
+    ),
+}
+
+pub type BruijnLevelsTerm = GenericBruijnLevelsTerm<RcPointer>;
+
+/// A level that does not fit where it occurs, as reported by
+/// [`GenericBruijnLevelsTerm::validate_scope`].
+#[derive(Clone, Debug, Eq, PartialEq, thiserror::Error)]
+pub enum ScopeError {
+    #[error("variable `{name}` has unbound level {level}")]
+    UnboundVariable {
+        name: String,
+        level: u16,
+        depth: u16,
+    },
+    #[error("abstraction `{name}` has level {level} instead of {depth}")]
+    MisplacedAbstraction {
+        name: String,
+        level: u16,
+        depth: u16,
+    },
+    #[error("abstraction `{name}` leaves no level for its body")]
+    TooDeep { name: String },
+}
+
+impl<P: Pointer> Clone for GenericBruijnLevelsTerm<P> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Var(lvl, name) => Self::Var(*lvl, name.clone()),
+            Self::Abs(lvl, body, name) => Self::Abs(*lvl, body.clone(), name.clone()),
+            Self::Apply(t1, t2) => Self::Apply(t1.clone(), t2.clone()),
+        }
+    }
+}
+
+impl<P: Pointer> PartialEq for GenericBruijnLevelsTerm<P> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Var(l1, n1), Self::Var(l2, n2)) => l1 == l2 && n1 == n2,
+            (Self::Abs(l1, b1, n1), Self::Abs(l2, b2, n2)) => l1 == l2 && **b1 == **b2 && n1 == n2,
+            (Self::Apply(a1, b1), Self::Apply(a2, b2)) => **a1 == **a2 && **b1 == **b2,
+            _ => false,
+        }
+    }
 }
 
-impl From<BruijnLevelsTerm> for Term {
-    fn from(value: BruijnLevelsTerm) -> Self {
-        match value {
-            BruijnLevelsTerm::Var(_, name) => Term::Var(name),
-            BruijnLevelsTerm::Abs(_, body, name) => {
-                Term::Abs(name, Rc::new((*body).clone().into()))
+impl<P: Pointer> Eq for GenericBruijnLevelsTerm<P> {}
+
+impl<P: Pointer> Hash for GenericBruijnLevelsTerm<P> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            Self::Var(lvl, name) => {
+                lvl.hash(state);
+                name.hash(state);
+            }
+            Self::Abs(lvl, body, name) => {
+                lvl.hash(state);
+                (**body).hash(state);
+                name.hash(state);
             }
-            BruijnLevelsTerm::Apply(t1, t2) => {
-                Term::Apply(Rc::new((*t1).clone().into()), Rc::new((*t2).clone().into()))
+            Self::Apply(t1, t2) => {
+                (**t1).hash(state);
+                (**t2).hash(state);
             }
         }
     }
 }
 
-impl From<Term> for BruijnLevelsTerm {
-    fn from(value: Term) -> Self {
-        (*BruijnLevelsTerm::from_term(Rc::new(value), &HashMap::new(), 0)).clone()
+impl<P: Pointer> Debug for GenericBruijnLevelsTerm<P> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Var(lvl, name) => f.debug_tuple("Var").field(lvl).field(name).finish(),
+            Self::Abs(lvl, body, name) => f
+                .debug_tuple("Abs")
+                .field(lvl)
+                .field(&**body)
+                .field(name)
+                .finish(),
+            Self::Apply(t1, t2) => f.debug_tuple("Apply").field(&**t1).field(&**t2).finish(),
+        }
+    }
+}
+
+impl<P: Pointer> From<GenericBruijnLevelsTerm<P>> for GenericTerm<P> {
+    fn from(value: GenericBruijnLevelsTerm<P>) -> Self {
+        value.to_term(&mut HashMap::new())
     }
 }
 
-impl From<Rc<Term>> for BruijnLevelsTerm {
-    fn from(value: Rc<Term>) -> Self {
-        (*BruijnLevelsTerm::from_term(value, &HashMap::new(), 0)).clone()
+impl<P: Pointer> From<GenericTerm<P>> for GenericBruijnLevelsTerm<P> {
+    fn from(value: GenericTerm<P>) -> Self {
+        P::unwrap_or_clone(Self::from_term(&value, &HashMap::new(), 0))
     }
 }
 
-impl BruijnLevelsTerm {
-    pub fn from_open_term(term: Term) -> Rc<BruijnLevelsTerm> {
+impl<P: Pointer> GenericBruijnLevelsTerm<P> {
+    pub fn from_open_term(term: GenericTerm<P>) -> P::Ptr<Self> {
         let mut free_vars: Vec<String> = Vec::new();
         Self::collect_free_vars(&term, &HashSet::new(), &mut free_vars);
         let mut dict = HashMap::new();
         for (i, v) in free_vars.iter().enumerate() {
             dict.insert(v.clone(), i as u16);
         }
-        Self::from_term(Rc::new(term), &dict, free_vars.len() as u16)
+        Self::from_term(&term, &dict, free_vars.len() as u16)
     }
 
-    fn collect_free_vars(term: &Term, bound: &HashSet<String>, free: &mut Vec<String>) {
+    fn collect_free_vars(term: &GenericTerm<P>, bound: &HashSet<String>, free: &mut Vec<String>) {
         match term {
-            Term::Var(v) => {
+            GenericTerm::Var(v) => {
                 let s = v.to_string();
                 if !bound.contains(&s) && !free.contains(&s) {
                     free.push(s);
                 }
             }
-            Term::Abs(v, body) => {
+            GenericTerm::Abs(v, body) => {
                 let mut new_bound = bound.clone();
                 new_bound.insert(v.to_string());
                 Self::collect_free_vars(body, &new_bound, free);
             }
-            Term::Apply(t1, t2) => {
+            GenericTerm::Apply(t1, t2) => {
                 Self::collect_free_vars(t1, bound, free);
                 Self::collect_free_vars(t2, bound, free);
             }
@@ -69,56 +146,124 @@ impl BruijnLevelsTerm {
     }
 
     fn from_term(
-        term: Rc<Term>,
+        term: &GenericTerm<P>,
         dictionary: &HashMap<String, u16>,
         depth: u16,
-    ) -> Rc<BruijnLevelsTerm> {
-        match term.as_ref() {
-            Term::Var(name) => Rc::new(BruijnLevelsTerm::Var(
-                *dictionary.get(name.as_ref()).unwrap(),
-                name.clone(),
-            )),
-            Term::Abs(name, body) => {
+    ) -> P::Ptr<Self> {
+        match term {
+            GenericTerm::Var(name) => {
+                P::new(Self::Var(*dictionary.get(&**name).unwrap(), name.clone()))
+            }
+            GenericTerm::Abs(name, body) => {
                 let mut new_hash = dictionary.clone();
                 new_hash.insert(name.to_string(), depth);
-                Rc::new(BruijnLevelsTerm::Abs(
+                P::new(Self::Abs(
                     depth,
-                    Self::from_term(body.clone(), &new_hash, depth + 1),
+                    Self::from_term(body, &new_hash, depth + 1),
                     name.clone(),
                 ))
             }
-            Term::Apply(t1, t2) => Rc::new(BruijnLevelsTerm::Apply(
-                BruijnLevelsTerm::from_term(t1.clone(), dictionary, depth),
-                BruijnLevelsTerm::from_term(t2.clone(), dictionary, depth),
+            GenericTerm::Apply(t1, t2) => P::new(Self::Apply(
+                Self::from_term(t1, dictionary, depth),
+                Self::from_term(t2, dictionary, depth),
             )),
         }
     }
 
-    pub fn substitute(
-        self: Rc<BruijnLevelsTerm>,
-        what: u16,
-        with: Rc<BruijnLevelsTerm>,
-    ) -> Rc<BruijnLevelsTerm> {
-        match self.as_ref() {
-            BruijnLevelsTerm::Var(val, _) if *val == what => with,
-            BruijnLevelsTerm::Abs(lvl, body, name) => {
-                let new_body = Self::substitute(body.clone(), what, with);
-                if Rc::ptr_eq(&new_body, body) {
-                    self
-                } else {
-                    Rc::new(BruijnLevelsTerm::Abs(*lvl, new_body, name.clone()))
+    fn to_term(&self, names: &mut HashMap<u16, P::Name>) -> GenericTerm<P> {
+        match self {
+            Self::Var(lvl, name) => GenericTerm::Var(names.get(lvl).unwrap_or(name).clone()),
+            Self::Abs(lvl, body, name) => {
+                let mut used = HashSet::new();
+                body.collect_free_levels(&HashSet::from([*lvl]), &mut used);
+                let taken: HashSet<&P::Name> = used
+                    .iter()
+                    .map(|(lvl, name)| names.get(lvl).unwrap_or(name))
+                    .collect();
+                let mut fresh = name.clone();
+                while taken.contains(&fresh) {
+                    fresh = format!("{fresh}'").into();
+                }
+                let shadowed = names.insert(*lvl, fresh.clone());
+                let body = body.to_term(names);
+                match shadowed {
+                    Some(name) => names.insert(*lvl, name),
+                    None => names.remove(lvl),
+                };
+                GenericTerm::Abs(fresh, P::new(body))
+            }
+            Self::Apply(t1, t2) => {
+                GenericTerm::Apply(P::new(t1.to_term(names)), P::new(t2.to_term(names)))
+            }
+        }
+    }
+
+    fn collect_free_levels(&self, bound: &HashSet<u16>, free: &mut HashSet<(u16, P::Name)>) {
+        match self {
+            Self::Var(lvl, name) => {
+                if !bound.contains(lvl) {
+                    free.insert((*lvl, name.clone()));
                 }
             }
-            BruijnLevelsTerm::Apply(a, b) => {
-                let new_a = Self::substitute(a.clone(), what, with.clone());
-                let new_b = Self::substitute(b.clone(), what, with);
-                if Rc::ptr_eq(&new_a, a) && Rc::ptr_eq(&new_b, b) {
-                    self
+            Self::Abs(lvl, body, _) => {
+                let mut bound = bound.clone();
+                bound.insert(*lvl);
+                body.collect_free_levels(&bound, free);
+            }
+            Self::Apply(t1, t2) => {
+                t1.collect_free_levels(bound, free);
+                t2.collect_free_levels(bound, free);
+            }
+        }
+    }
+
+    fn shift(term: P::Ptr<Self>, from: u16, by: u16) -> P::Ptr<Self> {
+        if by == 0 {
+            return term;
+        }
+        match term.as_ref() {
+            Self::Var(lvl, name) if *lvl >= from => P::new(Self::Var(lvl + by, name.clone())),
+            Self::Abs(lvl, body, name) => P::new(Self::Abs(
+                if *lvl >= from { lvl + by } else { *lvl },
+                Self::shift(body.clone(), from, by),
+                name.clone(),
+            )),
+            Self::Apply(t1, t2) => P::new(Self::Apply(
+                Self::shift(t1.clone(), from, by),
+                Self::shift(t2.clone(), from, by),
+            )),
+            _ => term,
+        }
+    }
+
+    fn substituted(term: P::Ptr<Self>, what: u16, with: P::Ptr<Self>) -> P::Ptr<Self> {
+        Self::substitute_at(term, what, &with, what + 1)
+    }
+
+    fn substitute_at(
+        term: P::Ptr<Self>,
+        what: u16,
+        with: &P::Ptr<Self>,
+        depth: u16,
+    ) -> P::Ptr<Self> {
+        match term.as_ref() {
+            Self::Var(lvl, _) if *lvl == what => Self::shift(with.clone(), what, depth - what - 1),
+            Self::Var(lvl, name) if *lvl > what => P::new(Self::Var(lvl - 1, name.clone())),
+            Self::Abs(lvl, body, name) => P::new(Self::Abs(
+                lvl - 1,
+                Self::substitute_at(body.clone(), what, with, depth + 1),
+                name.clone(),
+            )),
+            Self::Apply(a, b) => {
+                let new_a = Self::substitute_at(a.clone(), what, with, depth);
+                let new_b = Self::substitute_at(b.clone(), what, with, depth);
+                if P::ptr_eq(&new_a, a) && P::ptr_eq(&new_b, b) {
+                    term
                 } else {
-                    Rc::new(BruijnLevelsTerm::Apply(new_a, new_b))
+                    P::new(Self::Apply(new_a, new_b))
                 }
             }
-            _ => self,
+            _ => term,
         }
     }
 
@@ -137,57 +282,73 @@ impl BruijnLevelsTerm {
         }
     }
 
-    pub fn reduce_step_call_by_name(self: Rc<BruijnLevelsTerm>) -> Rc<BruijnLevelsTerm> {
-        match self.as_ref() {
+    pub fn is_whnf(&self) -> bool {
+        match self {
+            Self::Apply(t1, _) => !matches!(t1.as_ref(), Self::Abs(_, _, _)) && t1.is_whnf(),
+            _ => true,
+        }
+    }
+
+    pub fn is_hnf(&self) -> bool {
+        match self {
+            Self::Abs(_, body, _) => body.is_hnf(),
+            other => other.is_whnf(),
+        }
+    }
+
+    fn head_step(term: P::Ptr<Self>) -> P::Ptr<Self> {
+        match term.as_ref() {
+            Self::Abs(lvl, body, name) => {
+                P::new(Self::Abs(*lvl, Self::head_step(body.clone()), name.clone()))
+            }
+            _ => Self::call_by_name_step(term),
+        }
+    }
+
+    fn call_by_name_step(term: P::Ptr<Self>) -> P::Ptr<Self> {
+        match term.as_ref() {
             Self::Apply(t1, t2) => match t1.as_ref() {
-                Self::Abs(lvl, body, _) => body.clone().substitute(*lvl, t2.clone()),
-                _ => Rc::new(Self::Apply(
-                    t1.clone().reduce_step_call_by_name(),
-                    t2.clone(),
-                )),
+                Self::Abs(lvl, body, _) => Self::substituted(body.clone(), *lvl, t2.clone()),
+                _ => P::new(Self::Apply(Self::call_by_name_step(t1.clone()), t2.clone())),
             },
-            _ => self,
+            _ => term,
         }
     }
 
-    pub fn reduce_step_normal_order(self: Rc<BruijnLevelsTerm>) -> Rc<BruijnLevelsTerm> {
-        match self.as_ref() {
+    fn normal_order_step(term: P::Ptr<Self>) -> P::Ptr<Self> {
+        match term.as_ref() {
             Self::Apply(t1, t2) => match t1.as_ref() {
-                Self::Abs(lvl, body, _) => body.clone().substitute(*lvl, t2.clone()),
-                _ if !t1.is_normal_form() => Rc::new(Self::Apply(
-                    t1.clone().reduce_step_normal_order(),
-                    t2.clone(),
-                )),
-                _ => Rc::new(Self::Apply(
-                    t1.clone(),
-                    t2.clone().reduce_step_normal_order(),
-                )),
+                Self::Abs(lvl, body, _) => Self::substituted(body.clone(), *lvl, t2.clone()),
+                _ if !t1.is_normal_form() => {
+                    P::new(Self::Apply(Self::normal_order_step(t1.clone()), t2.clone()))
+                }
+                _ => P::new(Self::Apply(t1.clone(), Self::normal_order_step(t2.clone()))),
             },
-            Self::Abs(lvl, body, name) => Rc::new(Self::Abs(
+            Self::Abs(lvl, body, name) => P::new(Self::Abs(
                 *lvl,
-                body.clone().reduce_step_normal_order(),
+                Self::normal_order_step(body.clone()),
                 name.clone(),
             )),
-            _ => self,
+            _ => term,
         }
     }
 
-    pub fn reduce_step_call_by_value(self: Rc<BruijnLevelsTerm>) -> Rc<BruijnLevelsTerm> {
-        match self.as_ref() {
-            Self::Var(_, _) | Self::Abs(_, _, _) => self,
+    fn call_by_value_step(term: P::Ptr<Self>) -> P::Ptr<Self> {
+        match term.as_ref() {
+            Self::Var(_, _) | Self::Abs(_, _, _) => term,
             Self::Apply(t1, t2) => {
                 if let Self::Abs(lvl, body, _) = t1.as_ref() {
                     if t2.is_value() {
-                        body.clone().substitute(*lvl, t2.clone())
+                        Self::substituted(body.clone(), *lvl, t2.clone())
                     } else {
-                        Rc::new(Self::Apply(
+                        P::new(Self::Apply(
                             t1.clone(),
-                            t2.clone().reduce_step_call_by_value(),
+                            Self::call_by_value_step(t2.clone()),
                         ))
                     }
                 } else {
-                    Rc::new(Self::Apply(
-                        t1.clone().reduce_step_call_by_value(),
+                    P::new(Self::Apply(
+                        Self::call_by_value_step(t1.clone()),
                         t2.clone(),
                     ))
                 }
@@ -195,32 +356,437 @@ impl BruijnLevelsTerm {
         }
     }
 
-    pub fn reduce_step_applicative_order(self: Rc<BruijnLevelsTerm>) -> Rc<BruijnLevelsTerm> {
-        match self.as_ref() {
+    fn applicative_order_step(term: P::Ptr<Self>) -> P::Ptr<Self> {
+        match term.as_ref() {
             Self::Apply(t1, t2) => {
                 if !t1.is_normal_form() {
-                    Rc::new(Self::Apply(
-                        t1.clone().reduce_step_applicative_order(),
+                    P::new(Self::Apply(
+                        Self::applicative_order_step(t1.clone()),
                         t2.clone(),
                     ))
                 } else if !t2.is_normal_form() {
-                    Rc::new(Self::Apply(
+                    P::new(Self::Apply(
+                        t1.clone(),
+                        Self::applicative_order_step(t2.clone()),
+                    ))
+                } else {
+                    match t1.as_ref() {
+                        Self::Abs(lvl, body, _) => {
+                            Self::substituted(body.clone(), *lvl, t2.clone())
+                        }
+                        _ => term,
+                    }
+                }
+            }
+            Self::Abs(lvl, body, name) => P::new(Self::Abs(
+                *lvl,
+                Self::applicative_order_step(body.clone()),
+                name.clone(),
+            )),
+            _ => term,
+        }
+    }
+
+    fn developed(term: P::Ptr<Self>) -> P::Ptr<Self> {
+        match term.as_ref() {
+            Self::Apply(t1, t2) => match t1.as_ref() {
+                Self::Abs(lvl, body, _) => Self::substituted(
+                    Self::developed(body.clone()),
+                    *lvl,
+                    Self::developed(t2.clone()),
+                ),
+                _ => P::new(Self::Apply(
+                    Self::developed(t1.clone()),
+                    Self::developed(t2.clone()),
+                )),
+            },
+            Self::Abs(lvl, body, name) => {
+                P::new(Self::Abs(*lvl, Self::developed(body.clone()), name.clone()))
+            }
+            _ => term,
+        }
+    }
+
+    fn occurs(&self, lvl: u16) -> bool {
+        match self {
+            Self::Var(v, _) => *v == lvl,
+            Self::Abs(_, body, _) => body.occurs(lvl),
+            Self::Apply(t1, t2) => t1.occurs(lvl) || t2.occurs(lvl),
+        }
+    }
+
+    fn lower(term: P::Ptr<Self>, from: u16) -> P::Ptr<Self> {
+        match term.as_ref() {
+            Self::Var(lvl, name) if *lvl > from => P::new(Self::Var(lvl - 1, name.clone())),
+            Self::Abs(lvl, body, name) => P::new(Self::Abs(
+                if *lvl > from { lvl - 1 } else { *lvl },
+                Self::lower(body.clone(), from),
+                name.clone(),
+            )),
+            Self::Apply(t1, t2) => P::new(Self::Apply(
+                Self::lower(t1.clone(), from),
+                Self::lower(t2.clone(), from),
+            )),
+            _ => term,
+        }
+    }
+
+    fn is_eta_redex(&self) -> bool {
+        match self {
+            Self::Abs(lvl, body, _) => match body.as_ref() {
+                Self::Apply(t1, t2) => {
+                    matches!(t2.as_ref(), Self::Var(v, _) if v == lvl) && !t1.occurs(*lvl)
+                }
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    fn contract_eta(term: P::Ptr<Self>) -> P::Ptr<Self> {
+        match term.as_ref() {
+            Self::Abs(lvl, body, _) => match body.as_ref() {
+                Self::Apply(t1, _) => Self::lower(t1.clone(), *lvl),
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        }
+    }
+
+    pub fn is_eta_normal_form(&self) -> bool {
+        !self.is_eta_redex()
+            && match self {
+                Self::Var(_, _) => true,
+                Self::Abs(_, body, _) => body.is_eta_normal_form(),
+                Self::Apply(t1, t2) => t1.is_eta_normal_form() && t2.is_eta_normal_form(),
+            }
+    }
+
+    pub fn is_beta_eta_normal_form(&self) -> bool {
+        self.is_normal_form() && self.is_eta_normal_form()
+    }
+
+    fn eta_step(term: P::Ptr<Self>) -> P::Ptr<Self> {
+        if term.is_eta_redex() {
+            return Self::contract_eta(term);
+        }
+        match term.as_ref() {
+            Self::Abs(lvl, body, name) => {
+                P::new(Self::Abs(*lvl, Self::eta_step(body.clone()), name.clone()))
+            }
+            Self::Apply(t1, t2) if !t1.is_eta_normal_form() => {
+                P::new(Self::Apply(Self::eta_step(t1.clone()), t2.clone()))
+            }
+            Self::Apply(t1, t2) => P::new(Self::Apply(t1.clone(), Self::eta_step(t2.clone()))),
+            _ => term,
+        }
+    }
+
+    fn eta_expanded(term: P::Ptr<Self>, depth: u16) -> P::Ptr<Self> {
+        let mut taken = HashSet::new();
+        term.collect_free_levels(&HashSet::new(), &mut taken);
+        let mut name = P::Name::from("x");
+        while taken.iter().any(|(_, other)| *other == name) {
+            name = format!("{name}'").into();
+        }
+        P::new(Self::Abs(
+            depth,
+            P::new(Self::Apply(
+                Self::shift(term, depth, 1),
+                P::new(Self::Var(depth, name.clone())),
+            )),
+            name,
+        ))
+    }
+
+    fn normal_order_eta_step(term: P::Ptr<Self>) -> P::Ptr<Self> {
+        if term.is_eta_redex() {
+            return Self::contract_eta(term);
+        }
+        match term.as_ref() {
+            Self::Apply(t1, t2) => match t1.as_ref() {
+                Self::Abs(lvl, body, _) => Self::substituted(body.clone(), *lvl, t2.clone()),
+                _ if !t1.is_beta_eta_normal_form() => P::new(Self::Apply(
+                    Self::normal_order_eta_step(t1.clone()),
+                    t2.clone(),
+                )),
+                _ => P::new(Self::Apply(
+                    t1.clone(),
+                    Self::normal_order_eta_step(t2.clone()),
+                )),
+            },
+            Self::Abs(lvl, body, name) => P::new(Self::Abs(
+                *lvl,
+                Self::normal_order_eta_step(body.clone()),
+                name.clone(),
+            )),
+            _ => term,
+        }
+    }
+
+    fn applicative_order_eta_step(term: P::Ptr<Self>) -> P::Ptr<Self> {
+        match term.as_ref() {
+            Self::Apply(t1, t2) => {
+                if !t1.is_beta_eta_normal_form() {
+                    P::new(Self::Apply(
+                        Self::applicative_order_eta_step(t1.clone()),
+                        t2.clone(),
+                    ))
+                } else if !t2.is_beta_eta_normal_form() {
+                    P::new(Self::Apply(
                         t1.clone(),
-                        t2.clone().reduce_step_applicative_order(),
+                        Self::applicative_order_eta_step(t2.clone()),
                     ))
                 } else {
                     match t1.as_ref() {
-                        Self::Abs(lvl, body, _) => body.clone().substitute(*lvl, t2.clone()),
-                        _ => self,
+                        Self::Abs(lvl, body, _) => {
+                            Self::substituted(body.clone(), *lvl, t2.clone())
+                        }
+                        _ => term,
                     }
                 }
             }
-            Self::Abs(lvl, body, name) => Rc::new(Self::Abs(
+            Self::Abs(lvl, body, name) if !body.is_beta_eta_normal_form() => P::new(Self::Abs(
                 *lvl,
-                body.clone().reduce_step_applicative_order(),
+                Self::applicative_order_eta_step(body.clone()),
                 name.clone(),
             )),
-            _ => self,
+            _ if term.is_eta_redex() => Self::contract_eta(term),
+            _ => term,
+        }
+    }
+
+    fn step(term: P::Ptr<Self>, strategy: Strategy) -> P::Ptr<Self> {
+        match strategy {
+            Strategy::CallByName => Self::call_by_name_step(term),
+            Strategy::NormalOrder => Self::normal_order_step(term),
+            Strategy::CallByValue => Self::call_by_value_step(term),
+            Strategy::ApplicativeOrder => Self::applicative_order_step(term),
+            Strategy::Head => Self::head_step(term),
+            Strategy::NormalOrderEta => Self::normal_order_eta_step(term),
+            Strategy::ApplicativeOrderEta => Self::applicative_order_eta_step(term),
+        }
+    }
+
+    pub fn is_terminal(&self, strategy: Strategy) -> bool {
+        match strategy {
+            Strategy::NormalOrder | Strategy::ApplicativeOrder => self.is_normal_form(),
+            Strategy::CallByName => self.is_whnf(),
+            Strategy::Head => self.is_hnf(),
+            Strategy::NormalOrderEta | Strategy::ApplicativeOrderEta => {
+                self.is_beta_eta_normal_form()
+            }
+            Strategy::CallByValue => match self {
+                Self::Apply(t1, t2) => match t1.as_ref() {
+                    Self::Abs(_, _, _) => !t2.is_value() && t2.is_terminal(strategy),
+                    _ => t1.is_terminal(strategy),
+                },
+                _ => true,
+            },
+        }
+    }
+
+    pub fn alpha_normalized(&self) -> Self {
+        self.erase_bound_names(&HashSet::new())
+    }
+
+    fn erase_bound_names(&self, bound: &HashSet<u16>) -> Self {
+        match self {
+            Self::Var(lvl, _) if bound.contains(lvl) => Self::Var(*lvl, P::Name::from("")),
+            Self::Var(lvl, name) => Self::Var(*lvl, name.clone()),
+            Self::Abs(lvl, body, _) => {
+                let mut bound = bound.clone();
+                bound.insert(*lvl);
+                Self::Abs(
+                    *lvl,
+                    P::new(body.erase_bound_names(&bound)),
+                    P::Name::from(""),
+                )
+            }
+            Self::Apply(t1, t2) => Self::Apply(
+                P::new(t1.erase_bound_names(bound)),
+                P::new(t2.erase_bound_names(bound)),
+            ),
+        }
+    }
+
+    /// Names of the free levels `0..k` below the outermost abstraction, or
+    /// `freeN` for a level that does not occur.
+    pub(crate) fn free_level_names(&self) -> Vec<P::Name> {
+        let mut free = vec![None; self.free_level_count() as usize];
+        self.collect_free_names(&mut free);
+        free.into_iter()
+            .enumerate()
+            .map(|(lvl, name)| name.unwrap_or_else(|| format!("free{lvl}").into()))
+            .collect()
+    }
+
+    pub(crate) fn free_level_count(&self) -> u16 {
+        self.outermost_level()
+            .unwrap_or_else(|| self.max_level().map_or(0, |lvl| lvl.saturating_add(1)))
+    }
+
+    /// Checks that abstraction levels match their depth above the free
+    /// levels, and that every variable is bound or free. Shared subterms are
+    /// checked once per depth.
+    pub fn validate_scope(&self) -> Result<(), ScopeError> {
+        self.check_scope(self.free_level_count(), &mut HashSet::new())
+    }
+
+    fn check_scope(
+        &self,
+        depth: u16,
+        checked: &mut HashSet<(*const Self, u16)>,
+    ) -> Result<(), ScopeError> {
+        if !checked.insert((self, depth)) {
+            return Ok(());
+        }
+        match self {
+            Self::Var(lvl, _) if *lvl < depth => Ok(()),
+            Self::Var(lvl, name) => Err(ScopeError::UnboundVariable {
+                name: name.to_string(),
+                level: *lvl,
+                depth,
+            }),
+            Self::Abs(lvl, _, name) if *lvl != depth => Err(ScopeError::MisplacedAbstraction {
+                name: name.to_string(),
+                level: *lvl,
+                depth,
+            }),
+            Self::Abs(_, body, name) => {
+                let depth = depth.checked_add(1).ok_or_else(|| ScopeError::TooDeep {
+                    name: name.to_string(),
+                })?;
+                body.check_scope(depth, checked)
+            }
+            Self::Apply(t1, t2) => {
+                t1.check_scope(depth, checked)?;
+                t2.check_scope(depth, checked)
+            }
+        }
+    }
+
+    fn outermost_level(&self) -> Option<u16> {
+        self.first_abstraction_level(&mut HashSet::new())
+    }
+
+    // `visited` holds the shared subterms already searched, so a DAG is
+    // walked in time linear in its nodes rather than in its expanded size.
+    fn first_abstraction_level(&self, visited: &mut HashSet<*const Self>) -> Option<u16> {
+        if !visited.insert(self) {
+            return None;
+        }
+        match self {
+            Self::Var(_, _) => None,
+            Self::Abs(lvl, _, _) => Some(*lvl),
+            Self::Apply(t1, t2) => t1
+                .first_abstraction_level(visited)
+                .or_else(|| t2.first_abstraction_level(visited)),
+        }
+    }
+
+    fn max_level(&self) -> Option<u16> {
+        self.max_level_unvisited(&mut HashSet::new())
+    }
+
+    fn max_level_unvisited(&self, visited: &mut HashSet<*const Self>) -> Option<u16> {
+        if !visited.insert(self) {
+            return None;
+        }
+        match self {
+            Self::Var(lvl, _) => Some(*lvl),
+            Self::Abs(_, body, _) => body.max_level_unvisited(visited),
+            Self::Apply(t1, t2) => t1
+                .max_level_unvisited(visited)
+                .max(t2.max_level_unvisited(visited)),
+        }
+    }
+
+    fn collect_free_names(&self, free: &mut [Option<P::Name>]) {
+        match self {
+            Self::Var(lvl, name) => {
+                if let Some(slot) = free.get_mut(*lvl as usize) {
+                    slot.get_or_insert_with(|| name.clone());
+                }
+            }
+            Self::Abs(_, body, _) => body.collect_free_names(free),
+            Self::Apply(t1, t2) => {
+                t1.collect_free_names(free);
+                t2.collect_free_names(free);
+            }
         }
     }
 }
+
+// Methods that take the term behind its pointer, as `self: Rc<Self>` only
+// works for a concrete pointer type.
+macro_rules! pointer_methods {
+    ($pointer:ty, $ptr:ident) => {
+        impl From<$ptr<GenericTerm<$pointer>>> for GenericBruijnLevelsTerm<$pointer> {
+            fn from(value: $ptr<GenericTerm<$pointer>>) -> Self {
+                $ptr::unwrap_or_clone(Self::from_term(&value, &HashMap::new(), 0))
+            }
+        }
+
+        impl GenericBruijnLevelsTerm<$pointer> {
+            /// Substitutes `with` for the variable bound by the abstraction at
+            /// level `what`, where `self` is that abstraction's body. Binders
+            /// inside the body move one level down, and `with` is shifted to
+            /// the depth it lands at.
+            pub fn substitute(self: $ptr<Self>, what: u16, with: $ptr<Self>) -> $ptr<Self> {
+                Self::substituted(self, what, with)
+            }
+
+            pub fn reduce_step_weak_head(self: $ptr<Self>) -> $ptr<Self> {
+                Self::call_by_name_step(self)
+            }
+
+            pub fn reduce_step_head(self: $ptr<Self>) -> $ptr<Self> {
+                Self::head_step(self)
+            }
+
+            pub fn reduce_step_call_by_name(self: $ptr<Self>) -> $ptr<Self> {
+                Self::call_by_name_step(self)
+            }
+
+            pub fn reduce_step_normal_order(self: $ptr<Self>) -> $ptr<Self> {
+                Self::normal_order_step(self)
+            }
+
+            pub fn reduce_step_call_by_value(self: $ptr<Self>) -> $ptr<Self> {
+                Self::call_by_value_step(self)
+            }
+
+            pub fn reduce_step_applicative_order(self: $ptr<Self>) -> $ptr<Self> {
+                Self::applicative_order_step(self)
+            }
+
+            pub fn complete_development(self: $ptr<Self>) -> $ptr<Self> {
+                Self::developed(self)
+            }
+
+            pub fn reduce_step_eta(self: $ptr<Self>) -> $ptr<Self> {
+                Self::eta_step(self)
+            }
+
+            pub fn eta_expand(self: $ptr<Self>, depth: u16) -> $ptr<Self> {
+                Self::eta_expanded(self, depth)
+            }
+
+            pub fn reduce_step_normal_order_eta(self: $ptr<Self>) -> $ptr<Self> {
+                Self::normal_order_eta_step(self)
+            }
+
+            pub fn reduce_step_applicative_order_eta(self: $ptr<Self>) -> $ptr<Self> {
+                Self::applicative_order_eta_step(self)
+            }
+
+            pub fn reduce_step(self: $ptr<Self>, strategy: Strategy) -> $ptr<Self> {
+                Self::step(self, strategy)
+            }
+        }
+    };
+}
+
+pointer_methods!(RcPointer, Rc);
+pointer_methods!(ArcPointer, Arc);
@@ -1,10 +1,41 @@
+pub mod arena;
+pub mod binary;
+pub mod blc;
+pub mod bohm;
+#[cfg(feature = "cache")]
+pub mod cache;
+pub mod closure;
+pub mod codegen;
 pub mod compile;
+pub mod divergence;
 pub mod env;
+pub mod explore;
+pub mod graph;
 pub mod levels;
+pub mod path;
+pub mod pointer;
+#[cfg(feature = "serde")]
+pub mod serialize;
+pub mod sexpr;
+pub mod strategy;
+pub mod sync;
 pub mod term;
+pub mod trace;
+pub mod visit;
+pub mod vm;
+pub mod zipper;
 
+pub use arena::TermArena;
+#[cfg(feature = "cache")]
+pub use cache::NormalFormCache;
 pub use compile::compile_term;
 pub use env::CompoundEnvironment;
 pub use env::RegistryEnvironment;
+pub use explore::ReductionGraph;
+pub use graph::GraphMachine;
 pub use levels::BruijnLevelsTerm;
+pub use path::Path;
+pub use strategy::Strategy;
 pub use term::Term;
+pub use trace::Trace;
+pub use zipper::Zipper;
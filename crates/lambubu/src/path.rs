@@ -0,0 +1,147 @@
+use crate::{pointer::Pointer, term::GenericTerm};
+use std::fmt::{self, Display, Formatter};
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Direction {
+    Body,
+    Function,
+    Argument,
+}
+
+impl Direction {
+    pub fn name(self) -> &'static str {
+        match self {
+            Direction::Body => "body",
+            Direction::Function => "function",
+            Direction::Argument => "argument",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Path(Vec<Direction>);
+
+impl Path {
+    pub fn root() -> Self {
+        Self::default()
+    }
+
+    pub fn directions(&self) -> &[Direction] {
+        &self.0
+    }
+
+    pub fn is_root(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn child(&self, direction: Direction) -> Self {
+        let mut directions = self.0.clone();
+        directions.push(direction);
+        Self(directions)
+    }
+}
+
+impl From<Vec<Direction>> for Path {
+    fn from(value: Vec<Direction>) -> Self {
+        Self(value)
+    }
+}
+
+impl Display for Path {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.0.is_empty() {
+            return write!(f, "ε");
+        }
+        for (i, direction) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, "/")?;
+            }
+            write!(f, "{}", direction.name())?;
+        }
+        Ok(())
+    }
+}
+
+impl<P: Pointer> GenericTerm<P> {
+    pub fn subterm_at(&self, path: &Path) -> Option<&Self> {
+        path.0
+            .iter()
+            .try_fold(self, |term, direction| match (term, direction) {
+                (Self::Abs(_, body), Direction::Body) => Some(body.as_ref()),
+                (Self::Apply(t1, _), Direction::Function) => Some(t1.as_ref()),
+                (Self::Apply(_, t2), Direction::Argument) => Some(t2.as_ref()),
+                _ => None,
+            })
+    }
+
+    pub fn replace_at(self, path: &Path, f: impl FnOnce(Self) -> Option<Self>) -> Option<Self> {
+        self.replace_at_directions(&path.0, f)
+    }
+
+    fn replace_at_directions(
+        self,
+        directions: &[Direction],
+        f: impl FnOnce(Self) -> Option<Self>,
+    ) -> Option<Self> {
+        let Some((direction, rest)) = directions.split_first() else {
+            return f(self);
+        };
+        match (self, direction) {
+            (Self::Abs(name, body), Direction::Body) => Some(Self::Abs(
+                name,
+                P::new(P::unwrap_or_clone(body).replace_at_directions(rest, f)?),
+            )),
+            (Self::Apply(t1, t2), Direction::Function) => Some(Self::Apply(
+                P::new(P::unwrap_or_clone(t1).replace_at_directions(rest, f)?),
+                t2,
+            )),
+            (Self::Apply(t1, t2), Direction::Argument) => Some(Self::Apply(
+                t1,
+                P::new(P::unwrap_or_clone(t2).replace_at_directions(rest, f)?),
+            )),
+            _ => None,
+        }
+    }
+
+    pub fn is_redex(&self) -> bool {
+        matches!(self, Self::Apply(t1, _) if matches!(t1.as_ref(), Self::Abs(_, _)))
+    }
+
+    pub fn redexes(&self) -> Vec<(Path, &Self)> {
+        let mut redexes = Vec::new();
+        self.collect_redexes(Path::root(), &mut redexes);
+        redexes
+    }
+
+    fn collect_redexes<'a>(&'a self, path: Path, redexes: &mut Vec<(Path, &'a Self)>) {
+        if self.is_redex() {
+            redexes.push((path.clone(), self));
+        }
+        match self {
+            Self::Var(_) => {}
+            Self::Abs(_, body) => body.collect_redexes(path.child(Direction::Body), redexes),
+            Self::Apply(t1, t2) => {
+                t1.collect_redexes(path.child(Direction::Function), redexes);
+                t2.collect_redexes(path.child(Direction::Argument), redexes);
+            }
+        }
+    }
+
+    pub fn contract_at(self, path: &Path) -> Option<Self> {
+        self.replace_at(path, |term| match term {
+            Self::Apply(t1, t2) => match P::unwrap_or_clone(t1) {
+                Self::Abs(name, body) => {
+                    Some(P::unwrap_or_clone(body).substitute(&name, P::unwrap_or_clone(t2)))
+                }
+                _ => None,
+            },
+            _ => None,
+        })
+    }
+
+    pub fn contract_eta_at(self, path: &Path) -> Option<Self> {
+        self.replace_at(path, |term| {
+            term.is_eta_redex().then(|| term.contract_eta())
+        })
+    }
+}
@@ -0,0 +1,90 @@
+use std::{
+    borrow::Borrow,
+    fmt::{Debug, Display},
+    hash::Hash,
+    ops::Deref,
+    rc::Rc,
+    sync::Arc,
+};
+
+/// The reference-counted pointer the term types are built from. [`Term`] and
+/// [`BruijnLevelsTerm`] use [`Rc`]; their [`sync`](crate::sync) counterparts
+/// use [`Arc`] and can cross threads.
+///
+/// [`Term`]: crate::Term
+/// [`BruijnLevelsTerm`]: crate::BruijnLevelsTerm
+pub trait Pointer: Clone + 'static {
+    type Ptr<T>: Clone + Deref<Target = T> + AsRef<T>;
+    type Name: Clone
+        + Eq
+        + Ord
+        + Hash
+        + Debug
+        + Display
+        + Deref<Target = str>
+        + Borrow<str>
+        + for<'a> From<&'a str>
+        + From<String>;
+
+    fn new<T>(value: T) -> Self::Ptr<T>;
+    fn ptr_eq<T>(a: &Self::Ptr<T>, b: &Self::Ptr<T>) -> bool;
+    fn as_ptr<T>(this: &Self::Ptr<T>) -> *const T;
+    fn make_mut<T: Clone>(this: &mut Self::Ptr<T>) -> &mut T;
+    fn unwrap_or_clone<T: Clone>(this: Self::Ptr<T>) -> T;
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct RcPointer;
+
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct ArcPointer;
+
+impl Pointer for RcPointer {
+    type Ptr<T> = Rc<T>;
+    type Name = Rc<str>;
+
+    fn new<T>(value: T) -> Rc<T> {
+        Rc::new(value)
+    }
+
+    fn ptr_eq<T>(a: &Rc<T>, b: &Rc<T>) -> bool {
+        Rc::ptr_eq(a, b)
+    }
+
+    fn as_ptr<T>(this: &Rc<T>) -> *const T {
+        Rc::as_ptr(this)
+    }
+
+    fn make_mut<T: Clone>(this: &mut Rc<T>) -> &mut T {
+        Rc::make_mut(this)
+    }
+
+    fn unwrap_or_clone<T: Clone>(this: Rc<T>) -> T {
+        Rc::unwrap_or_clone(this)
+    }
+}
+
+impl Pointer for ArcPointer {
+    type Ptr<T> = Arc<T>;
+    type Name = Arc<str>;
+
+    fn new<T>(value: T) -> Arc<T> {
+        Arc::new(value)
+    }
+
+    fn ptr_eq<T>(a: &Arc<T>, b: &Arc<T>) -> bool {
+        Arc::ptr_eq(a, b)
+    }
+
+    fn as_ptr<T>(this: &Arc<T>) -> *const T {
+        Arc::as_ptr(this)
+    }
+
+    fn make_mut<T: Clone>(this: &mut Arc<T>) -> &mut T {
+        Arc::make_mut(this)
+    }
+
+    fn unwrap_or_clone<T: Clone>(this: Arc<T>) -> T {
+        Arc::unwrap_or_clone(this)
+    }
+}
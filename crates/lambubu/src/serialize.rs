@@ -0,0 +1,289 @@
+//! Serde support, enabled by the `serde` feature.
+//!
+//! Terms are written as externally tagged enums, which map onto both JSON and
+//! CBOR:
+//!
+//! - [`Term`]: `{"var": "x"}`, `{"abs": {"name": "x", "body": ...}}`,
+//!   `{"apply": [..., ...]}`
+//! - [`BruijnLevelsTerm`]: `{"var": {"level": 0, "name": "x"}}`,
+//!   `{"abs": {"level": 0, "name": "x", "body": ...}}`, `{"apply": [..., ...]}`
+//!
+//! Level terms are checked to be well-scoped when they are read: the levels
+//! of nested abstractions must increase by one and every variable must refer
+//! to an enclosing abstraction or to a free level below the outermost one.
+//!
+//! [`SharedTerm`] and [`SharedLevelsTerm`] write a term as a node table,
+//! `{"nodes": [...], "root": n}`, in which children are indices of earlier
+//! nodes. Subterms shared through the same `Rc` are written once and are
+//! shared again after reading.
+
+use crate::{BruijnLevelsTerm, Term};
+use serde::{
+    Deserialize, Deserializer, Serialize, Serializer,
+    de::Error,
+    ser::{SerializeStructVariant, SerializeTupleVariant},
+};
+use std::{collections::HashMap, rc::Rc};
+
+impl Serialize for Term {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Term::Var(name) => serializer.serialize_newtype_variant("Term", 0, "var", &**name),
+            Term::Abs(name, body) => {
+                let mut state = serializer.serialize_struct_variant("Term", 1, "abs", 2)?;
+                state.serialize_field("name", &**name)?;
+                state.serialize_field("body", body.as_ref())?;
+                state.end()
+            }
+            Term::Apply(t1, t2) => {
+                let mut state = serializer.serialize_tuple_variant("Term", 2, "apply", 2)?;
+                state.serialize_field(t1.as_ref())?;
+                state.serialize_field(t2.as_ref())?;
+                state.end()
+            }
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "Term", rename_all = "lowercase")]
+enum TermRepr {
+    Var(String),
+    Abs { name: String, body: Box<TermRepr> },
+    Apply(Box<TermRepr>, Box<TermRepr>),
+}
+
+impl From<TermRepr> for Term {
+    fn from(value: TermRepr) -> Self {
+        match value {
+            TermRepr::Var(name) => Term::Var(name.into()),
+            TermRepr::Abs { name, body } => Term::Abs(name.into(), Rc::new((*body).into())),
+            TermRepr::Apply(t1, t2) => Term::Apply(Rc::new((*t1).into()), Rc::new((*t2).into())),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Term {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        TermRepr::deserialize(deserializer).map(Term::from)
+    }
+}
+
+impl Serialize for BruijnLevelsTerm {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            BruijnLevelsTerm::Var(lvl, name) => {
+                let mut state =
+                    serializer.serialize_struct_variant("BruijnLevelsTerm", 0, "var", 2)?;
+                state.serialize_field("level", lvl)?;
+                state.serialize_field("name", &**name)?;
+                state.end()
+            }
+            BruijnLevelsTerm::Abs(lvl, body, name) => {
+                let mut state =
+                    serializer.serialize_struct_variant("BruijnLevelsTerm", 1, "abs", 3)?;
+                state.serialize_field("level", lvl)?;
+                state.serialize_field("name", &**name)?;
+                state.serialize_field("body", body.as_ref())?;
+                state.end()
+            }
+            BruijnLevelsTerm::Apply(t1, t2) => {
+                let mut state =
+                    serializer.serialize_tuple_variant("BruijnLevelsTerm", 2, "apply", 2)?;
+                state.serialize_field(t1.as_ref())?;
+                state.serialize_field(t2.as_ref())?;
+                state.end()
+            }
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "BruijnLevelsTerm", rename_all = "lowercase")]
+enum LevelsRepr {
+    Var {
+        level: u16,
+        name: String,
+    },
+    Abs {
+        level: u16,
+        name: String,
+        body: Box<LevelsRepr>,
+    },
+    Apply(Box<LevelsRepr>, Box<LevelsRepr>),
+}
+
+impl From<LevelsRepr> for BruijnLevelsTerm {
+    fn from(value: LevelsRepr) -> Self {
+        match value {
+            LevelsRepr::Var { level, name } => BruijnLevelsTerm::Var(level, name.into()),
+            LevelsRepr::Abs { level, name, body } => {
+                BruijnLevelsTerm::Abs(level, Rc::new((*body).into()), name.into())
+            }
+            LevelsRepr::Apply(t1, t2) => {
+                BruijnLevelsTerm::Apply(Rc::new((*t1).into()), Rc::new((*t2).into()))
+            }
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for BruijnLevelsTerm {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let term = BruijnLevelsTerm::from(LevelsRepr::deserialize(deserializer)?);
+        term.validate_scope().map_err(D::Error::custom)?;
+        Ok(term)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum TermNode {
+    Var(String),
+    Abs { name: String, body: usize },
+    Apply(usize, usize),
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum LevelsNode {
+    Var {
+        level: u16,
+        name: String,
+    },
+    Abs {
+        level: u16,
+        name: String,
+        body: usize,
+    },
+    Apply(usize, usize),
+}
+
+#[derive(Serialize, Deserialize)]
+struct NodeTable<N> {
+    nodes: Vec<N>,
+    root: usize,
+}
+
+fn node<N, E: Error>(nodes: &[Rc<N>], index: usize) -> Result<Rc<N>, E> {
+    nodes
+        .get(index)
+        .cloned()
+        .ok_or_else(|| E::custom(format!("node {index} is not defined before use")))
+}
+
+/// A term written as a table of nodes so that `Rc` sharing survives a round
+/// trip.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SharedTerm(pub Rc<Term>);
+
+fn collect_term(
+    term: &Rc<Term>,
+    table: &mut Vec<TermNode>,
+    seen: &mut HashMap<*const Term, usize>,
+) -> usize {
+    if let Some(index) = seen.get(&Rc::as_ptr(term)) {
+        return *index;
+    }
+    let node = match term.as_ref() {
+        Term::Var(name) => TermNode::Var(name.to_string()),
+        Term::Abs(name, body) => TermNode::Abs {
+            name: name.to_string(),
+            body: collect_term(body, table, seen),
+        },
+        Term::Apply(t1, t2) => {
+            TermNode::Apply(collect_term(t1, table, seen), collect_term(t2, table, seen))
+        }
+    };
+    table.push(node);
+    seen.insert(Rc::as_ptr(term), table.len() - 1);
+    table.len() - 1
+}
+
+impl Serialize for SharedTerm {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut nodes = Vec::new();
+        let root = collect_term(&self.0, &mut nodes, &mut HashMap::new());
+        NodeTable { nodes, root }.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for SharedTerm {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let table = NodeTable::<TermNode>::deserialize(deserializer)?;
+        let mut built: Vec<Rc<Term>> = Vec::with_capacity(table.nodes.len());
+        for node in table.nodes {
+            let term = match node {
+                TermNode::Var(name) => Term::Var(name.into()),
+                TermNode::Abs { name, body } => Term::Abs(name.into(), self::node(&built, body)?),
+                TermNode::Apply(t1, t2) => {
+                    Term::Apply(self::node(&built, t1)?, self::node(&built, t2)?)
+                }
+            };
+            built.push(Rc::new(term));
+        }
+        Ok(SharedTerm(node(&built, table.root)?))
+    }
+}
+
+/// A level term written as a table of nodes so that `Rc` sharing survives a
+/// round trip.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SharedLevelsTerm(pub Rc<BruijnLevelsTerm>);
+
+fn collect_levels(
+    term: &Rc<BruijnLevelsTerm>,
+    table: &mut Vec<LevelsNode>,
+    seen: &mut HashMap<*const BruijnLevelsTerm, usize>,
+) -> usize {
+    if let Some(index) = seen.get(&Rc::as_ptr(term)) {
+        return *index;
+    }
+    let node = match term.as_ref() {
+        BruijnLevelsTerm::Var(level, name) => LevelsNode::Var {
+            level: *level,
+            name: name.to_string(),
+        },
+        BruijnLevelsTerm::Abs(level, body, name) => LevelsNode::Abs {
+            level: *level,
+            name: name.to_string(),
+            body: collect_levels(body, table, seen),
+        },
+        BruijnLevelsTerm::Apply(t1, t2) => LevelsNode::Apply(
+            collect_levels(t1, table, seen),
+            collect_levels(t2, table, seen),
+        ),
+    };
+    table.push(node);
+    seen.insert(Rc::as_ptr(term), table.len() - 1);
+    table.len() - 1
+}
+
+impl Serialize for SharedLevelsTerm {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut nodes = Vec::new();
+        let root = collect_levels(&self.0, &mut nodes, &mut HashMap::new());
+        NodeTable { nodes, root }.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for SharedLevelsTerm {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let table = NodeTable::<LevelsNode>::deserialize(deserializer)?;
+        let mut built: Vec<Rc<BruijnLevelsTerm>> = Vec::with_capacity(table.nodes.len());
+        for node in table.nodes {
+            let term = match node {
+                LevelsNode::Var { level, name } => BruijnLevelsTerm::Var(level, name.into()),
+                LevelsNode::Abs { level, name, body } => {
+                    BruijnLevelsTerm::Abs(level, self::node(&built, body)?, name.into())
+                }
+                LevelsNode::Apply(t1, t2) => {
+                    BruijnLevelsTerm::Apply(self::node(&built, t1)?, self::node(&built, t2)?)
+                }
+            };
+            built.push(Rc::new(term));
+        }
+        let root = node(&built, table.root)?;
+        root.validate_scope().map_err(D::Error::custom)?;
+        Ok(SharedLevelsTerm(root))
+    }
+}
@@ -0,0 +1,200 @@
+//! S-expression syntax for terms.
+//!
+//! - variables and macro names are written as in `base.pest`: `x`, `PLUS`
+//! - `(lambda (x y) body)` is `λx.λy.body`, and `(lambda x body)` or
+//!   `(λ (x) body)` may be used for a single parameter
+//! - `(f a b)` is `((f a) b)`
+//! - `(define NAME term)` defines a macro
+//! - `;` starts a comment running to the end of the line
+
+use crate::{
+    Term,
+    compile::CompilationError,
+    env::{MutableTermEnvironment, TermEnvironment},
+    term::add_prime,
+};
+use pest::Parser;
+use pest_derive::Parser;
+use std::{
+    fmt::{self, Display, Formatter},
+    rc::Rc,
+};
+
+#[derive(Parser)]
+#[grammar = "base.pest"]
+#[grammar = "sexpr.pest"]
+struct SExprParser;
+
+type Pair<'a> = pest::iterators::Pair<'a, Rule>;
+
+fn compile_pair<'a>(
+    pair: Pair<'a>,
+    env: &impl TermEnvironment,
+) -> Result<Term, CompilationError<'a>> {
+    match pair.as_rule() {
+        Rule::Variable => Ok(Term::Var(Rc::from(pair.as_str()))),
+        Rule::Abstraction => {
+            let mut inner = pair.into_inner();
+            let params: Vec<_> = inner.next().unwrap().into_inner().collect();
+            let body = compile_pair(inner.next().unwrap(), env)?;
+
+            Ok(params.iter().rev().fold(body, |body, param| {
+                Term::Abs(Rc::from(param.as_str()), Rc::new(body))
+            }))
+        }
+        Rule::Application => {
+            let mut inner = pair.into_inner().map(|x| compile_pair(x, env));
+            let mut result = inner.next().unwrap()?;
+
+            for i in inner {
+                result = Term::Apply(Rc::new(result), Rc::new(i?));
+            }
+
+            Ok(result)
+        }
+        Rule::MacrosName => {
+            env.resolve_term(pair.as_str())
+                .ok_or(CompilationError::UnknownMacros {
+                    macro_name: pair.as_str().to_owned(),
+                    span: pair.as_span(),
+                })
+        }
+        Rule::Define => Err(CompilationError::UnexpectedDefinition(pair.as_span())),
+        _ => unreachable!(),
+    }
+}
+
+pub fn compile_sexpr_term<'a>(
+    input: &'a str,
+    env: &impl TermEnvironment,
+) -> Result<Term, CompilationError<'a>> {
+    let parse_result = SExprParser::parse(Rule::Term, input)
+        .unwrap()
+        .next()
+        .unwrap();
+
+    compile_pair(parse_result, env)
+}
+
+pub fn compile_sexpr_file<'a>(
+    input: &'a str,
+    env: &mut impl MutableTermEnvironment,
+) -> Result<Vec<Term>, CompilationError<'a>> {
+    let parse_result = SExprParser::parse(Rule::File, input).unwrap();
+    let mut result = Vec::new();
+
+    for pair in parse_result {
+        match pair.as_rule() {
+            Rule::Define => {
+                let mut inner = pair.into_inner();
+                let macro_name = inner.next().unwrap().as_str();
+                let term = compile_pair(inner.next().unwrap(), env)?;
+                env.add_term(macro_name.to_owned(), term)
+            }
+            Rule::EOI => {}
+            _ => result.push(compile_pair(pair, env)?),
+        }
+    }
+
+    Ok(result)
+}
+
+const KEYWORDS: [&str; 2] = ["lambda", "define"];
+
+fn is_keyword(name: &str) -> bool {
+    KEYWORDS.contains(&name)
+}
+
+/// A term that has no S-expression form.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum SExprError {
+    #[error("free variable `{0}` is an S-expression keyword")]
+    KeywordVariable(String),
+}
+
+/// Renames the binders named like a keyword, adding primes until the name is
+/// not free in the body.
+fn rename_keyword_binders(term: &Term) -> Term {
+    match term {
+        Term::Var(_) => term.clone(),
+        Term::Abs(var, body) => {
+            let body = rename_keyword_binders(body);
+            if !is_keyword(var) {
+                return Term::Abs(var.clone(), Rc::new(body));
+            }
+            let mut fresh: Rc<str> = add_prime(var);
+            while body.is_free_variable(&fresh) {
+                fresh = add_prime(&fresh);
+            }
+            let body = body.rename_free(var, &fresh);
+            Term::Abs(fresh, Rc::new(body))
+        }
+        Term::Apply(t1, t2) => Term::Apply(
+            Rc::new(rename_keyword_binders(t1)),
+            Rc::new(rename_keyword_binders(t2)),
+        ),
+    }
+}
+
+/// Displays a term as an S-expression, merging nested abstractions and
+/// left-nested applications.
+///
+/// A variable named `lambda` or `define` would read back as a keyword, so
+/// formatting fails on one; [`Term::to_sexpr`] renames the bound ones first.
+pub struct SExpr<'a>(pub &'a Term);
+
+impl Display for SExpr<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Term::Var(v) if is_keyword(v) => Err(fmt::Error),
+            Term::Var(v) => write!(f, "{v}"),
+            Term::Abs(_, _) => {
+                let mut body = self.0;
+                write!(f, "(lambda (")?;
+                while let Term::Abs(var, inner) = body {
+                    if is_keyword(var) {
+                        return Err(fmt::Error);
+                    }
+                    let sep = if std::ptr::eq(body, self.0) { "" } else { " " };
+                    write!(f, "{sep}{var}")?;
+                    body = inner;
+                }
+                write!(f, ") {})", SExpr(body))
+            }
+            Term::Apply(_, _) => {
+                let mut args = Vec::new();
+                let mut head = self.0;
+                while let Term::Apply(t1, t2) = head {
+                    args.push(t2);
+                    head = t1;
+                }
+                write!(f, "({}", SExpr(head))?;
+                for arg in args.iter().rev() {
+                    write!(f, " {}", SExpr(arg))?;
+                }
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+/// Displays a macro definition as `(define NAME term)`, failing like [`SExpr`]
+/// on keyword variables.
+pub struct SExprDefinition<'a>(pub &'a str, pub &'a Term);
+
+impl Display for SExprDefinition<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "(define {} {})", self.0, SExpr(self.1))
+    }
+}
+
+impl Term {
+    /// Prints the term as an S-expression, priming bound variables named like
+    /// a keyword. Free ones can't be renamed and are rejected.
+    pub fn to_sexpr(&self) -> Result<String, SExprError> {
+        if let Some(keyword) = KEYWORDS.iter().find(|k| self.is_free_variable(k)) {
+            return Err(SExprError::KeywordVariable((*keyword).to_owned()));
+        }
+        Ok(SExpr(&rename_keyword_binders(self)).to_string())
+    }
+}
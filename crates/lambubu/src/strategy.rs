@@ -0,0 +1,22 @@
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Strategy {
+    CallByName,
+    NormalOrder,
+    CallByValue,
+    ApplicativeOrder,
+    Head,
+    NormalOrderEta,
+    ApplicativeOrderEta,
+}
+
+impl Strategy {
+    pub const ALL: [Strategy; 7] = [
+        Strategy::CallByName,
+        Strategy::NormalOrder,
+        Strategy::CallByValue,
+        Strategy::ApplicativeOrder,
+        Strategy::Head,
+        Strategy::NormalOrderEta,
+        Strategy::ApplicativeOrderEta,
+    ];
+}
@@ -0,0 +1,103 @@
+use crate::{
+    levels::GenericBruijnLevelsTerm,
+    pointer::{ArcPointer, Pointer, RcPointer},
+    term::GenericTerm,
+};
+use std::{collections::HashMap, rc::Rc, sync::Arc};
+
+pub type Term = GenericTerm<ArcPointer>;
+pub type BruijnLevelsTerm = GenericBruijnLevelsTerm<ArcPointer>;
+
+// Conversions between the `Rc` and `Arc` representations convert every shared
+// node once, so DAG-shaped terms stay DAG-shaped on the other side.
+
+fn convert_term<P: Pointer, Q: Pointer>(
+    term: &P::Ptr<GenericTerm<P>>,
+    converted: &mut HashMap<*const GenericTerm<P>, Q::Ptr<GenericTerm<Q>>>,
+) -> Q::Ptr<GenericTerm<Q>> {
+    if let Some(result) = converted.get(&P::as_ptr(term)) {
+        return result.clone();
+    }
+    let result = Q::new(match term.as_ref() {
+        GenericTerm::Var(name) => GenericTerm::Var(Q::Name::from(&**name)),
+        GenericTerm::Abs(name, body) => GenericTerm::Abs(
+            Q::Name::from(&**name),
+            convert_term::<P, Q>(body, converted),
+        ),
+        GenericTerm::Apply(t1, t2) => GenericTerm::Apply(
+            convert_term::<P, Q>(t1, converted),
+            convert_term::<P, Q>(t2, converted),
+        ),
+    });
+    converted.insert(P::as_ptr(term), result.clone());
+    result
+}
+
+fn convert_levels<P: Pointer, Q: Pointer>(
+    term: &P::Ptr<GenericBruijnLevelsTerm<P>>,
+    converted: &mut HashMap<*const GenericBruijnLevelsTerm<P>, Q::Ptr<GenericBruijnLevelsTerm<Q>>>,
+) -> Q::Ptr<GenericBruijnLevelsTerm<Q>> {
+    if let Some(result) = converted.get(&P::as_ptr(term)) {
+        return result.clone();
+    }
+    let result = Q::new(match term.as_ref() {
+        GenericBruijnLevelsTerm::Var(lvl, name) => {
+            GenericBruijnLevelsTerm::Var(*lvl, Q::Name::from(&**name))
+        }
+        GenericBruijnLevelsTerm::Abs(lvl, body, name) => GenericBruijnLevelsTerm::Abs(
+            *lvl,
+            convert_levels::<P, Q>(body, converted),
+            Q::Name::from(&**name),
+        ),
+        GenericBruijnLevelsTerm::Apply(t1, t2) => GenericBruijnLevelsTerm::Apply(
+            convert_levels::<P, Q>(t1, converted),
+            convert_levels::<P, Q>(t2, converted),
+        ),
+    });
+    converted.insert(P::as_ptr(term), result.clone());
+    result
+}
+
+impl Term {
+    pub fn from_rc(term: &Rc<crate::Term>) -> Arc<Term> {
+        convert_term::<RcPointer, ArcPointer>(term, &mut HashMap::new())
+    }
+
+    pub fn to_rc(self: &Arc<Term>) -> Rc<crate::Term> {
+        convert_term::<ArcPointer, RcPointer>(self, &mut HashMap::new())
+    }
+}
+
+impl BruijnLevelsTerm {
+    pub fn from_rc(term: &Rc<crate::BruijnLevelsTerm>) -> Arc<BruijnLevelsTerm> {
+        convert_levels::<RcPointer, ArcPointer>(term, &mut HashMap::new())
+    }
+
+    pub fn to_rc(self: &Arc<BruijnLevelsTerm>) -> Rc<crate::BruijnLevelsTerm> {
+        convert_levels::<ArcPointer, RcPointer>(self, &mut HashMap::new())
+    }
+}
+
+impl From<&crate::Term> for Term {
+    fn from(value: &crate::Term) -> Self {
+        Arc::unwrap_or_clone(Term::from_rc(&Rc::new(value.clone())))
+    }
+}
+
+impl From<&Term> for crate::Term {
+    fn from(value: &Term) -> Self {
+        Rc::unwrap_or_clone(Arc::new(value.clone()).to_rc())
+    }
+}
+
+impl From<&crate::BruijnLevelsTerm> for BruijnLevelsTerm {
+    fn from(value: &crate::BruijnLevelsTerm) -> Self {
+        Arc::unwrap_or_clone(BruijnLevelsTerm::from_rc(&Rc::new(value.clone())))
+    }
+}
+
+impl From<&BruijnLevelsTerm> for crate::BruijnLevelsTerm {
+    fn from(value: &BruijnLevelsTerm) -> Self {
+        Rc::unwrap_or_clone(Arc::new(value.clone()).to_rc())
+    }
+}
@@ -1,104 +1,126 @@
-use std::fmt::{self, Display, Formatter};
+use std::fmt::{self, Debug, Display, Formatter};
 use std::hash::{Hash, Hasher};
-use std::rc::Rc;
 
-use crate::levels::BruijnLevelsTerm;
+use crate::{
+    Strategy,
+    levels::GenericBruijnLevelsTerm,
+    pointer::{Pointer, RcPointer},
+};
 
 const SYMBOL_LAMBDA: char = 'λ';
 
-fn add_prime(s: &str) -> Rc<str> {
+pub(crate) fn add_prime<N: From<String>>(s: &str) -> N {
     format!("{s}'").into()
 }
 
-fn unwrap_rc(rc: Rc<Term>) -> Term {
-    Rc::try_unwrap(rc).unwrap_or_else(|rc| (*rc).clone())
+/// A lambda term with named variables, built from the pointer kind `P`.
+pub enum GenericTerm<P: Pointer> {
+    Var(P::Name),
+    Abs(P::Name, P::Ptr<GenericTerm<P>>),
+    Apply(P::Ptr<GenericTerm<P>>, P::Ptr<GenericTerm<P>>),
 }
 
-#[derive(Eq, PartialEq, Debug, Clone)]
-pub enum Term {
-    Var(Rc<str>),
-    Abs(Rc<str>, Rc<Term>),
-    Apply(Rc<Term>, Rc<Term>),
+pub type Term = GenericTerm<RcPointer>;
+
+impl<P: Pointer> Clone for GenericTerm<P> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Var(v) => Self::Var(v.clone()),
+            Self::Abs(v, body) => Self::Abs(v.clone(), body.clone()),
+            Self::Apply(t1, t2) => Self::Apply(t1.clone(), t2.clone()),
+        }
+    }
+}
+
+impl<P: Pointer> PartialEq for GenericTerm<P> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Var(v1), Self::Var(v2)) => v1 == v2,
+            (Self::Abs(v1, b1), Self::Abs(v2, b2)) => v1 == v2 && **b1 == **b2,
+            (Self::Apply(a1, b1), Self::Apply(a2, b2)) => **a1 == **a2 && **b1 == **b2,
+            _ => false,
+        }
+    }
 }
 
-impl Hash for Term {
+impl<P: Pointer> Eq for GenericTerm<P> {}
+
+impl<P: Pointer> Debug for GenericTerm<P> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Var(v) => f.debug_tuple("Var").field(v).finish(),
+            Self::Abs(v, body) => f.debug_tuple("Abs").field(v).field(&**body).finish(),
+            Self::Apply(t1, t2) => f.debug_tuple("Apply").field(&**t1).field(&**t2).finish(),
+        }
+    }
+}
+
+impl<P: Pointer> Hash for GenericTerm<P> {
     fn hash<H: Hasher>(&self, state: &mut H) {
-        BruijnLevelsTerm::from(self.clone()).hash(state)
+        GenericBruijnLevelsTerm::from(self.clone()).hash(state)
     }
 }
 
-impl Display for Term {
+impl<P: Pointer> Display for GenericTerm<P> {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
-            Term::Var(v) => write!(f, "{v}")?,
-            Term::Abs(var, term) => write!(f, "{SYMBOL_LAMBDA}{var}.{term}")?,
-            Term::Apply(term1, term2) => write!(f, "({term1} {term2})")?,
+            Self::Var(v) => write!(f, "{v}")?,
+            Self::Abs(var, term) => write!(f, "{SYMBOL_LAMBDA}{var}.{}", &**term)?,
+            Self::Apply(term1, term2) => write!(f, "({} {})", &**term1, &**term2)?,
         };
         Ok(())
     }
 }
 
-impl Term {
-    pub fn var(s: &str) -> Term {
-        Term::Var(Rc::from(s))
+impl<P: Pointer> GenericTerm<P> {
+    pub fn var(s: &str) -> Self {
+        Self::Var(P::Name::from(s))
     }
 
-    pub fn abs(v: &str, body: Term) -> Term {
-        Term::Abs(Rc::from(v), Rc::new(body))
+    pub fn abs(v: &str, body: Self) -> Self {
+        Self::Abs(P::Name::from(v), P::new(body))
     }
 
-    pub fn app(t1: Term, t2: Term) -> Term {
-        Term::Apply(Rc::new(t1), Rc::new(t2))
+    pub fn app(t1: Self, t2: Self) -> Self {
+        Self::Apply(P::new(t1), P::new(t2))
     }
 
-    fn is_free_variable(&self, what: &str) -> bool {
+    pub fn is_free_variable(&self, what: &str) -> bool {
         match self {
-            Term::Var(v) => &**v == what,
-            Term::Abs(v, body) => body.is_free_variable(what) && &**v != what,
-            Term::Apply(t1, t2) => t1.is_free_variable(what) || t2.is_free_variable(what),
+            Self::Var(v) => &**v == what,
+            Self::Abs(v, body) => body.is_free_variable(what) && &**v != what,
+            Self::Apply(t1, t2) => t1.is_free_variable(what) || t2.is_free_variable(what),
         }
     }
 
-    fn rename_free(self, from: &str, to: &str) -> Self {
-        if self.is_free_variable(from) {
-            match self {
-                Term::Var(v) if &*v == from => Term::Var(Rc::from(to)),
-                Term::Abs(v, body) if &*v != from => {
-                    Term::Abs(v, Rc::new(unwrap_rc(body).rename_free(from, to)))
-                }
-                Term::Apply(t1, t2) => Term::Apply(
-                    Rc::new(unwrap_rc(t1).rename_free(from, to)),
-                    Rc::new(unwrap_rc(t2).rename_free(from, to)),
-                ),
-                _ => self,
-            }
-        } else {
-            self
-        }
+    /// Renames the free occurrences of `from` to `to`, renaming binders that
+    /// would capture `to`.
+    pub fn rename_free(self, from: &str, to: &str) -> Self {
+        self.substitute(from, Self::var(to))
     }
 
-    pub fn substitute(self, what: &str, with: Term) -> Term {
+    pub fn substitute(self, what: &str, with: Self) -> Self {
         match self {
-            Term::Var(name) if &*name == what => with,
-            Term::Abs(variable, body) if &*variable != what => {
+            Self::Var(name) if &*name == what => with,
+            Self::Abs(variable, body) if &*variable != what => {
                 let (name, body) = if with.is_free_variable(&variable) {
-                    let mut fresh: Rc<str> = variable.clone();
+                    let mut fresh: P::Name = variable.clone();
                     while with.is_free_variable(&fresh)
                         || body.is_free_variable(&fresh)
                         || &*fresh == what
                     {
                         fresh = add_prime(&fresh);
                     }
-                    let body = unwrap_rc(body).rename_free(&variable, &fresh);
+                    let body = P::unwrap_or_clone(body).rename_free(&variable, &fresh);
                     (fresh, body)
                 } else {
-                    (variable, unwrap_rc(body))
+                    (variable, P::unwrap_or_clone(body))
                 };
-                Term::Abs(name, Rc::new(body.substitute(what, with)))
+                Self::Abs(name, P::new(body.substitute(what, with)))
             }
-            Term::Apply(term1, term2) => Term::Apply(
-                Rc::new(unwrap_rc(term1).substitute(what, with.clone())),
-                Rc::new(unwrap_rc(term2).substitute(what, with)),
+            Self::Apply(term1, term2) => Self::Apply(
+                P::new(P::unwrap_or_clone(term1).substitute(what, with.clone())),
+                P::new(P::unwrap_or_clone(term2).substitute(what, with)),
             ),
             _ => self,
         }
@@ -110,41 +132,74 @@ impl Term {
 
     pub fn is_normal_form(&self) -> bool {
         match self {
-            Term::Var(_) => true,
-            Term::Abs(_, body) => body.is_normal_form(),
-            Term::Apply(t1, t2) => match t1.as_ref() {
-                Term::Abs(_, _) => false,
+            Self::Var(_) => true,
+            Self::Abs(_, body) => body.is_normal_form(),
+            Self::Apply(t1, t2) => match t1.as_ref() {
+                Self::Abs(_, _) => false,
                 _ => t1.is_normal_form() && t2.is_normal_form(),
             },
         }
     }
 
+    pub fn is_whnf(&self) -> bool {
+        match self {
+            Self::Apply(t1, _) => !matches!(t1.as_ref(), Self::Abs(_, _)) && t1.is_whnf(),
+            _ => true,
+        }
+    }
+
+    pub fn is_hnf(&self) -> bool {
+        match self {
+            Self::Abs(_, body) => body.is_hnf(),
+            other => other.is_whnf(),
+        }
+    }
+
+    pub fn reduce_step_weak_head(self) -> Self {
+        self.reduce_step_call_by_name()
+    }
+
+    pub fn reduce_step_head(self) -> Self {
+        match self {
+            Self::Abs(name, body) => {
+                Self::Abs(name, P::new(P::unwrap_or_clone(body).reduce_step_head()))
+            }
+            other => other.reduce_step_weak_head(),
+        }
+    }
+
     pub fn reduce_step_call_by_name(self) -> Self {
         match self {
-            Self::Apply(t1, t2) => match unwrap_rc(t1) {
-                Self::Abs(var, body) => unwrap_rc(body).substitute(&var, unwrap_rc(t2)),
-                other => Self::Apply(Rc::new(other.reduce_step_call_by_name()), t2),
+            Self::Apply(t1, t2) => match P::unwrap_or_clone(t1) {
+                Self::Abs(var, body) => {
+                    P::unwrap_or_clone(body).substitute(&var, P::unwrap_or_clone(t2))
+                }
+                other => Self::Apply(P::new(other.reduce_step_call_by_name()), t2),
             },
             _ => self,
         }
     }
 
-    pub fn reduce_step_normal_order(self) -> Self {
+    pub fn reduce_step_normal_order(mut self) -> Self {
+        self.contract_leftmost();
+        self
+    }
+
+    /// Contracts the leftmost outermost redex in place and tells whether there
+    /// was one. Every node is visited at most once, where testing each
+    /// function for a normal form would make a long spine quadratic, and
+    /// nodes that are not shared are reused instead of rebuilt.
+    fn contract_leftmost(&mut self) -> bool {
         match self {
-            Self::Apply(t1, t2) => match unwrap_rc(t1) {
-                Self::Abs(name, body) => unwrap_rc(body).substitute(&name, unwrap_rc(t2)),
-                other if !other.is_normal_form() => {
-                    Self::Apply(Rc::new(other.reduce_step_normal_order()), t2)
+            Self::Apply(t1, t2) => {
+                if let Self::Abs(name, body) = t1.as_ref() {
+                    *self = body.as_ref().clone().substitute(name, t2.as_ref().clone());
+                    return true;
                 }
-                other => Self::Apply(
-                    Rc::new(other),
-                    Rc::new(unwrap_rc(t2).reduce_step_normal_order()),
-                ),
-            },
-            Self::Abs(name, body) => {
-                Self::Abs(name, Rc::new(unwrap_rc(body).reduce_step_normal_order()))
+                P::make_mut(t1).contract_leftmost() || P::make_mut(t2).contract_leftmost()
             }
-            other => other,
+            Self::Abs(_, body) => P::make_mut(body).contract_leftmost(),
+            Self::Var(_) => false,
         }
     }
 
@@ -152,18 +207,18 @@ impl Term {
         match self {
             Self::Var(_) | Self::Abs(_, _) => self,
             Self::Apply(t1, t2) => {
-                let t1_inner = unwrap_rc(t1);
+                let t1_inner = P::unwrap_or_clone(t1);
                 if let Self::Abs(t1_name, t1_body) = t1_inner {
                     if t2.is_value() {
-                        unwrap_rc(t1_body).substitute(&t1_name, unwrap_rc(t2))
+                        P::unwrap_or_clone(t1_body).substitute(&t1_name, P::unwrap_or_clone(t2))
                     } else {
                         Self::Apply(
-                            Rc::new(Self::Abs(t1_name, t1_body)),
-                            Rc::new(unwrap_rc(t2).reduce_step_call_by_value()),
+                            P::new(Self::Abs(t1_name, t1_body)),
+                            P::new(P::unwrap_or_clone(t2).reduce_step_call_by_value()),
                         )
                     }
                 } else {
-                    Self::Apply(Rc::new(t1_inner.reduce_step_call_by_value()), t2)
+                    Self::Apply(P::new(t1_inner.reduce_step_call_by_value()), t2)
                 }
             }
         }
@@ -173,21 +228,216 @@ impl Term {
         match self {
             Self::Apply(t1, t2) => {
                 if !t1.is_normal_form() {
-                    Self::Apply(Rc::new(unwrap_rc(t1).reduce_step_applicative_order()), t2)
+                    Self::Apply(
+                        P::new(P::unwrap_or_clone(t1).reduce_step_applicative_order()),
+                        t2,
+                    )
                 } else if !t2.is_normal_form() {
-                    Self::Apply(t1, Rc::new(unwrap_rc(t2).reduce_step_applicative_order()))
+                    Self::Apply(
+                        t1,
+                        P::new(P::unwrap_or_clone(t2).reduce_step_applicative_order()),
+                    )
                 } else {
-                    match unwrap_rc(t1) {
-                        Self::Abs(name, body) => unwrap_rc(body).substitute(&name, unwrap_rc(t2)),
-                        other => Self::Apply(Rc::new(other), t2),
+                    match P::unwrap_or_clone(t1) {
+                        Self::Abs(name, body) => {
+                            P::unwrap_or_clone(body).substitute(&name, P::unwrap_or_clone(t2))
+                        }
+                        other => Self::Apply(P::new(other), t2),
                     }
                 }
             }
             Self::Abs(name, body) => Self::Abs(
                 name,
-                Rc::new(unwrap_rc(body).reduce_step_applicative_order()),
+                P::new(P::unwrap_or_clone(body).reduce_step_applicative_order()),
+            ),
+            other => other,
+        }
+    }
+
+    pub fn complete_development(self) -> Self {
+        match self {
+            Self::Apply(t1, t2) => match P::unwrap_or_clone(t1) {
+                Self::Abs(name, body) => P::unwrap_or_clone(body)
+                    .complete_development()
+                    .substitute(&name, P::unwrap_or_clone(t2).complete_development()),
+                other => Self::Apply(
+                    P::new(other.complete_development()),
+                    P::new(P::unwrap_or_clone(t2).complete_development()),
+                ),
+            },
+            Self::Abs(name, body) => Self::Abs(
+                name,
+                P::new(P::unwrap_or_clone(body).complete_development()),
+            ),
+            other => other,
+        }
+    }
+
+    pub(crate) fn is_eta_redex(&self) -> bool {
+        match self {
+            Self::Abs(name, body) => match body.as_ref() {
+                Self::Apply(t1, t2) => {
+                    matches!(t2.as_ref(), Self::Var(v) if v == name) && !t1.is_free_variable(name)
+                }
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    pub(crate) fn contract_eta(self) -> Self {
+        match self {
+            Self::Abs(_, body) => match P::unwrap_or_clone(body) {
+                Self::Apply(t1, _) => P::unwrap_or_clone(t1),
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        }
+    }
+
+    pub fn is_eta_normal_form(&self) -> bool {
+        !self.is_eta_redex()
+            && match self {
+                Self::Var(_) => true,
+                Self::Abs(_, body) => body.is_eta_normal_form(),
+                Self::Apply(t1, t2) => t1.is_eta_normal_form() && t2.is_eta_normal_form(),
+            }
+    }
+
+    pub fn is_beta_eta_normal_form(&self) -> bool {
+        self.is_normal_form() && self.is_eta_normal_form()
+    }
+
+    pub fn reduce_step_eta(self) -> Self {
+        if self.is_eta_redex() {
+            return self.contract_eta();
+        }
+        match self {
+            Self::Abs(name, body) => {
+                Self::Abs(name, P::new(P::unwrap_or_clone(body).reduce_step_eta()))
+            }
+            Self::Apply(t1, t2) if !t1.is_eta_normal_form() => {
+                Self::Apply(P::new(P::unwrap_or_clone(t1).reduce_step_eta()), t2)
+            }
+            Self::Apply(t1, t2) => {
+                Self::Apply(t1, P::new(P::unwrap_or_clone(t2).reduce_step_eta()))
+            }
+            other => other,
+        }
+    }
+
+    pub fn eta_expand(self) -> Self {
+        let mut name: P::Name = P::Name::from("x");
+        while self.is_free_variable(&name) {
+            name = add_prime(&name);
+        }
+        Self::Abs(
+            name.clone(),
+            P::new(Self::Apply(P::new(self), P::new(Self::Var(name)))),
+        )
+    }
+
+    pub fn reduce_step_normal_order_eta(self) -> Self {
+        if self.is_eta_redex() {
+            return self.contract_eta();
+        }
+        match self {
+            Self::Apply(t1, t2) => match P::unwrap_or_clone(t1) {
+                Self::Abs(name, body) => {
+                    P::unwrap_or_clone(body).substitute(&name, P::unwrap_or_clone(t2))
+                }
+                other if !other.is_beta_eta_normal_form() => {
+                    Self::Apply(P::new(other.reduce_step_normal_order_eta()), t2)
+                }
+                other => Self::Apply(
+                    P::new(other),
+                    P::new(P::unwrap_or_clone(t2).reduce_step_normal_order_eta()),
+                ),
+            },
+            Self::Abs(name, body) => Self::Abs(
+                name,
+                P::new(P::unwrap_or_clone(body).reduce_step_normal_order_eta()),
+            ),
+            other => other,
+        }
+    }
+
+    pub fn reduce_step_applicative_order_eta(self) -> Self {
+        match self {
+            Self::Apply(t1, t2) => {
+                if !t1.is_beta_eta_normal_form() {
+                    Self::Apply(
+                        P::new(P::unwrap_or_clone(t1).reduce_step_applicative_order_eta()),
+                        t2,
+                    )
+                } else if !t2.is_beta_eta_normal_form() {
+                    Self::Apply(
+                        t1,
+                        P::new(P::unwrap_or_clone(t2).reduce_step_applicative_order_eta()),
+                    )
+                } else {
+                    match P::unwrap_or_clone(t1) {
+                        Self::Abs(name, body) => {
+                            P::unwrap_or_clone(body).substitute(&name, P::unwrap_or_clone(t2))
+                        }
+                        other => Self::Apply(P::new(other), t2),
+                    }
+                }
+            }
+            Self::Abs(name, body) if !body.is_beta_eta_normal_form() => Self::Abs(
+                name,
+                P::new(P::unwrap_or_clone(body).reduce_step_applicative_order_eta()),
             ),
+            other if other.is_eta_redex() => other.contract_eta(),
             other => other,
         }
     }
+
+    pub fn alpha_eq(&self, other: &Self) -> bool {
+        GenericBruijnLevelsTerm::from_open_term(self.clone()).alpha_normalized()
+            == GenericBruijnLevelsTerm::from_open_term(other.clone()).alpha_normalized()
+    }
+
+    pub fn beta_eta_eq(&self, other: &Self, max_steps: usize) -> Option<bool> {
+        let normalize = |mut term: Self| {
+            for _ in 0..max_steps {
+                if term.is_beta_eta_normal_form() {
+                    return Some(term);
+                }
+                term = term.reduce_step_normal_order_eta();
+            }
+            term.is_beta_eta_normal_form().then_some(term)
+        };
+        Some(normalize(self.clone())?.alpha_eq(&normalize(other.clone())?))
+    }
+
+    pub fn reduce_step(self, strategy: Strategy) -> Self {
+        match strategy {
+            Strategy::CallByName => self.reduce_step_call_by_name(),
+            Strategy::NormalOrder => self.reduce_step_normal_order(),
+            Strategy::CallByValue => self.reduce_step_call_by_value(),
+            Strategy::ApplicativeOrder => self.reduce_step_applicative_order(),
+            Strategy::Head => self.reduce_step_head(),
+            Strategy::NormalOrderEta => self.reduce_step_normal_order_eta(),
+            Strategy::ApplicativeOrderEta => self.reduce_step_applicative_order_eta(),
+        }
+    }
+
+    pub fn is_terminal(&self, strategy: Strategy) -> bool {
+        match strategy {
+            Strategy::NormalOrder | Strategy::ApplicativeOrder => self.is_normal_form(),
+            Strategy::CallByName => self.is_whnf(),
+            Strategy::Head => self.is_hnf(),
+            Strategy::NormalOrderEta | Strategy::ApplicativeOrderEta => {
+                self.is_beta_eta_normal_form()
+            }
+            Strategy::CallByValue => match self {
+                Self::Apply(t1, t2) => match t1.as_ref() {
+                    Self::Abs(_, _) => !t2.is_value() && t2.is_terminal(strategy),
+                    _ => t1.is_terminal(strategy),
+                },
+                _ => true,
+            },
+        }
+    }
 }
@@ -0,0 +1,296 @@
+use crate::{
+    Strategy,
+    path::{Direction, Path},
+    pointer::{Pointer, RcPointer},
+    term::GenericTerm,
+};
+use std::fmt::{self, Display, Formatter, Write};
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Rule {
+    Beta,
+    BetaValue,
+    Eta,
+}
+
+impl Rule {
+    pub fn name(self) -> &'static str {
+        match self {
+            Rule::Beta => "beta",
+            Rule::BetaValue => "beta-value",
+            Rule::Eta => "eta",
+        }
+    }
+}
+
+impl Display for Rule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Rule::Beta => write!(f, "β"),
+            Rule::BetaValue => write!(f, "βv"),
+            Rule::Eta => write!(f, "η"),
+        }
+    }
+}
+
+fn locate<P: Pointer>(term: &GenericTerm<P>, strategy: Strategy) -> Option<(Vec<Direction>, Rule)> {
+    let descend = |direction, sub: &GenericTerm<P>, strategy| {
+        locate(sub, strategy).map(|(mut path, rule)| {
+            path.push(direction);
+            (path, rule)
+        })
+    };
+    let here = |rule| Some((Vec::new(), rule));
+    match (term, strategy) {
+        (GenericTerm::Var(_), _) => None,
+        (GenericTerm::Abs(_, _), Strategy::NormalOrderEta) if term.is_eta_redex() => {
+            here(Rule::Eta)
+        }
+        (GenericTerm::Abs(_, body), Strategy::ApplicativeOrderEta) => {
+            if !body.is_beta_eta_normal_form() {
+                descend(Direction::Body, body, strategy)
+            } else if term.is_eta_redex() {
+                here(Rule::Eta)
+            } else {
+                None
+            }
+        }
+        (
+            GenericTerm::Abs(_, body),
+            Strategy::NormalOrder
+            | Strategy::ApplicativeOrder
+            | Strategy::Head
+            | Strategy::NormalOrderEta,
+        ) => descend(Direction::Body, body, strategy),
+        (GenericTerm::Abs(_, _), Strategy::CallByName | Strategy::CallByValue) => None,
+        (GenericTerm::Apply(t1, t2), _) => match strategy {
+            Strategy::CallByName | Strategy::Head => {
+                if term.is_redex() {
+                    here(Rule::Beta)
+                } else {
+                    descend(Direction::Function, t1, Strategy::CallByName)
+                }
+            }
+            Strategy::NormalOrder | Strategy::NormalOrderEta => {
+                let normal = match strategy {
+                    Strategy::NormalOrder => t1.is_normal_form(),
+                    _ => t1.is_beta_eta_normal_form(),
+                };
+                if term.is_redex() {
+                    here(Rule::Beta)
+                } else if !normal {
+                    descend(Direction::Function, t1, strategy)
+                } else {
+                    descend(Direction::Argument, t2, strategy)
+                }
+            }
+            Strategy::CallByValue => match t1.as_ref() {
+                GenericTerm::Abs(_, _) if t2.is_value() => here(Rule::BetaValue),
+                GenericTerm::Abs(_, _) => descend(Direction::Argument, t2, strategy),
+                _ => descend(Direction::Function, t1, strategy),
+            },
+            Strategy::ApplicativeOrder | Strategy::ApplicativeOrderEta => {
+                let normal = |t: &GenericTerm<P>| match strategy {
+                    Strategy::ApplicativeOrder => t.is_normal_form(),
+                    _ => t.is_beta_eta_normal_form(),
+                };
+                if !normal(t1) {
+                    descend(Direction::Function, t1, strategy)
+                } else if !normal(t2) {
+                    descend(Direction::Argument, t2, strategy)
+                } else if term.is_redex() {
+                    here(Rule::Beta)
+                } else {
+                    None
+                }
+            }
+        },
+    }
+}
+
+impl<P: Pointer> GenericTerm<P> {
+    pub fn next_redex(&self, strategy: Strategy) -> Option<(Path, Rule)> {
+        let (mut directions, rule) = locate(self, strategy)?;
+        directions.reverse();
+        Some((Path::from(directions), rule))
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TraceStep<P: Pointer = RcPointer> {
+    pub term: GenericTerm<P>,
+    pub path: Path,
+    pub rule: Rule,
+    pub redex: GenericTerm<P>,
+    pub contractum: GenericTerm<P>,
+}
+
+impl<P: Pointer> TraceStep<P> {
+    pub fn highlighted(&self) -> Highlighted<'_, P> {
+        Highlighted {
+            term: &self.term,
+            path: &self.path,
+        }
+    }
+}
+
+impl<P: Pointer> Display for TraceStep<P> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at {}: {}", self.rule, self.path, self.highlighted())
+    }
+}
+
+/// Recorded reduction sequence of a term under a strategy.
+///
+/// Each step keeps the term before the step together with the position,
+/// redex and contractum of the contraction that was performed.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Trace<P: Pointer = RcPointer> {
+    pub strategy: Strategy,
+    pub steps: Vec<TraceStep<P>>,
+    pub result: GenericTerm<P>,
+}
+
+impl<P: Pointer> Trace<P> {
+    pub fn new(term: GenericTerm<P>, strategy: Strategy, max_steps: usize) -> Self {
+        let mut steps = Vec::new();
+        let mut term = term;
+        while steps.len() < max_steps {
+            let Some((path, rule)) = term.next_redex(strategy) else {
+                break;
+            };
+            let redex = term.subterm_at(&path).unwrap().clone();
+            let contractum = match rule {
+                Rule::Beta | Rule::BetaValue => redex.clone().contract_at(&Path::root()),
+                Rule::Eta => redex.clone().contract_eta_at(&Path::root()),
+            }
+            .unwrap();
+            let next = term
+                .clone()
+                .replace_at(&path, |_| Some(contractum.clone()))
+                .unwrap();
+            steps.push(TraceStep {
+                term,
+                path,
+                rule,
+                redex,
+                contractum,
+            });
+            term = next;
+        }
+        Self {
+            strategy,
+            steps,
+            result: term,
+        }
+    }
+
+    pub fn initial(&self) -> &GenericTerm<P> {
+        self.steps.first().map_or(&self.result, |step| &step.term)
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.result.next_redex(self.strategy).is_none()
+    }
+
+    pub fn to_json(&self) -> String {
+        let mut json = String::new();
+        write!(
+            json,
+            "{{\"strategy\":{},\"initial\":{},\"steps\":[",
+            json_string(&format!("{:?}", self.strategy)),
+            json_string(&self.initial().to_string()),
+        )
+        .unwrap();
+        for (i, step) in self.steps.iter().enumerate() {
+            if i > 0 {
+                json.push(',');
+            }
+            let path: Vec<String> = step
+                .path
+                .directions()
+                .iter()
+                .map(|direction| json_string(direction.name()))
+                .collect();
+            write!(
+                json,
+                "{{\"term\":{},\"path\":[{}],\"rule\":{},\"redex\":{},\"contractum\":{}}}",
+                json_string(&step.term.to_string()),
+                path.join(","),
+                json_string(step.rule.name()),
+                json_string(&step.redex.to_string()),
+                json_string(&step.contractum.to_string()),
+            )
+            .unwrap();
+        }
+        write!(
+            json,
+            "],\"result\":{},\"complete\":{}}}",
+            json_string(&self.result.to_string()),
+            self.is_complete()
+        )
+        .unwrap();
+        json
+    }
+}
+
+impl<P: Pointer> Display for Trace<P> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (i, step) in self.steps.iter().enumerate() {
+            writeln!(f, "{i}. {step}")?;
+        }
+        write!(f, "{}. {}", self.steps.len(), self.result)
+    }
+}
+
+pub(crate) fn json_string(s: &str) -> String {
+    let mut out = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if (c as u32) < 0x20 => write!(out, "\\u{:04x}", c as u32).unwrap(),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Displays a term with the subterm at `path` wrapped in square brackets.
+pub struct Highlighted<'a, P: Pointer = RcPointer> {
+    pub term: &'a GenericTerm<P>,
+    pub path: &'a Path,
+}
+
+impl<P: Pointer> Display for Highlighted<'_, P> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        fn go<P: Pointer>(
+            term: &GenericTerm<P>,
+            path: &[Direction],
+            f: &mut Formatter<'_>,
+        ) -> fmt::Result {
+            let Some((direction, rest)) = path.split_first() else {
+                return write!(f, "[{term}]");
+            };
+            match (term, direction) {
+                (GenericTerm::Abs(name, body), Direction::Body) => {
+                    write!(f, "λ{name}.")?;
+                    go(body, rest, f)
+                }
+                (GenericTerm::Apply(t1, t2), Direction::Function) => {
+                    write!(f, "(")?;
+                    go(t1, rest, f)?;
+                    write!(f, " {})", t2.as_ref())
+                }
+                (GenericTerm::Apply(t1, t2), Direction::Argument) => {
+                    write!(f, "({} ", t1.as_ref())?;
+                    go(t2, rest, f)?;
+                    write!(f, ")")
+                }
+                _ => write!(f, "{term}"),
+            }
+        }
+        go(self.term, self.path.directions(), f)
+    }
+}
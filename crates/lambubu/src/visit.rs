@@ -0,0 +1,256 @@
+use crate::{levels::GenericBruijnLevelsTerm, pointer::Pointer, term::GenericTerm};
+use std::collections::{BTreeSet, HashSet};
+
+/// A term representation whose immediate subterms can be enumerated.
+pub trait Node: Clone {
+    fn children(&self) -> Vec<&Self>;
+    fn children_mut(&mut self) -> Vec<&mut Self>;
+}
+
+impl<P: Pointer> Node for GenericTerm<P> {
+    fn children(&self) -> Vec<&Self> {
+        match self {
+            Self::Var(_) => vec![],
+            Self::Abs(_, body) => vec![body],
+            Self::Apply(t1, t2) => vec![t1, t2],
+        }
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut Self> {
+        match self {
+            Self::Var(_) => vec![],
+            Self::Abs(_, body) => vec![P::make_mut(body)],
+            Self::Apply(t1, t2) => vec![P::make_mut(t1), P::make_mut(t2)],
+        }
+    }
+}
+
+impl<P: Pointer> Node for GenericBruijnLevelsTerm<P> {
+    fn children(&self) -> Vec<&Self> {
+        match self {
+            Self::Var(_, _) => vec![],
+            Self::Abs(_, body, _) => vec![body],
+            Self::Apply(t1, t2) => vec![t1, t2],
+        }
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut Self> {
+        match self {
+            Self::Var(_, _) => vec![],
+            Self::Abs(_, body, _) => vec![P::make_mut(body)],
+            Self::Apply(t1, t2) => vec![P::make_mut(t1), P::make_mut(t2)],
+        }
+    }
+}
+
+/// Read-only traversal. Overriding `visit` and calling [`walk`] from it
+/// continues into the children.
+pub trait Visitor<T: Node> {
+    fn visit(&mut self, term: &T) {
+        walk(self, term);
+    }
+}
+
+pub fn walk<T: Node, V: Visitor<T> + ?Sized>(visitor: &mut V, term: &T) {
+    for child in term.children() {
+        visitor.visit(child);
+    }
+}
+
+/// Mutating traversal. Shared subterms are cloned before they are changed.
+pub trait VisitorMut<T: Node> {
+    fn visit_mut(&mut self, term: &mut T) {
+        walk_mut(self, term);
+    }
+}
+
+pub fn walk_mut<T: Node, V: VisitorMut<T> + ?Sized>(visitor: &mut V, term: &mut T) {
+    for child in term.children_mut() {
+        visitor.visit_mut(child);
+    }
+}
+
+/// Pre-order iterator over a term and all of its subterms.
+pub struct Subterms<'a, T> {
+    stack: Vec<&'a T>,
+}
+
+impl<'a, T: Node> Iterator for Subterms<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let term = self.stack.pop()?;
+        self.stack.extend(term.children().into_iter().rev());
+        Some(term)
+    }
+}
+
+fn depth<T: Node>(term: &T) -> usize {
+    1 + term.children().into_iter().map(depth).max().unwrap_or(0)
+}
+
+impl<P: Pointer> GenericTerm<P> {
+    pub fn free_vars(&self) -> BTreeSet<P::Name> {
+        match self {
+            Self::Var(v) => BTreeSet::from([v.clone()]),
+            Self::Abs(v, body) => {
+                let mut free = body.free_vars();
+                free.remove(v);
+                free
+            }
+            Self::Apply(t1, t2) => {
+                let mut free = t1.free_vars();
+                free.extend(t2.free_vars());
+                free
+            }
+        }
+    }
+
+    pub fn bound_vars(&self) -> BTreeSet<P::Name> {
+        self.subterms()
+            .filter_map(|term| match term {
+                Self::Abs(v, _) => Some(v.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    pub fn subterms(&self) -> Subterms<'_, Self> {
+        Subterms { stack: vec![self] }
+    }
+
+    pub fn size(&self) -> usize {
+        self.subterms().count()
+    }
+
+    pub fn depth(&self) -> usize {
+        depth(self)
+    }
+
+    pub fn fold<A>(
+        &self,
+        var: &mut impl FnMut(&P::Name) -> A,
+        abs: &mut impl FnMut(&P::Name, A) -> A,
+        app: &mut impl FnMut(A, A) -> A,
+    ) -> A {
+        match self {
+            Self::Var(v) => var(v),
+            Self::Abs(v, body) => {
+                let body = body.fold(var, abs, app);
+                abs(v, body)
+            }
+            Self::Apply(t1, t2) => {
+                let t1 = t1.fold(var, abs, app);
+                let t2 = t2.fold(var, abs, app);
+                app(t1, t2)
+            }
+        }
+    }
+
+    pub fn map(&self, f: &mut impl FnMut(Self) -> Self) -> Self {
+        let term = match self {
+            Self::Var(_) => self.clone(),
+            Self::Abs(v, body) => Self::Abs(v.clone(), P::new(body.map(f))),
+            Self::Apply(t1, t2) => Self::Apply(P::new(t1.map(f)), P::new(t2.map(f))),
+        };
+        f(term)
+    }
+
+    pub fn accept(&self, visitor: &mut impl Visitor<Self>) {
+        visitor.visit(self);
+    }
+
+    pub fn accept_mut(&mut self, visitor: &mut impl VisitorMut<Self>) {
+        visitor.visit_mut(self);
+    }
+}
+
+impl<P: Pointer> GenericBruijnLevelsTerm<P> {
+    pub fn free_vars(&self) -> BTreeSet<P::Name> {
+        fn collect<P: Pointer>(
+            term: &GenericBruijnLevelsTerm<P>,
+            bound: &mut HashSet<u16>,
+            free: &mut BTreeSet<P::Name>,
+        ) {
+            match term {
+                GenericBruijnLevelsTerm::Var(lvl, name) => {
+                    if !bound.contains(lvl) {
+                        free.insert(name.clone());
+                    }
+                }
+                GenericBruijnLevelsTerm::Abs(lvl, body, _) => {
+                    let inserted = bound.insert(*lvl);
+                    collect(body, bound, free);
+                    if inserted {
+                        bound.remove(lvl);
+                    }
+                }
+                GenericBruijnLevelsTerm::Apply(t1, t2) => {
+                    collect(t1, bound, free);
+                    collect(t2, bound, free);
+                }
+            }
+        }
+        let mut free = BTreeSet::new();
+        collect(self, &mut HashSet::new(), &mut free);
+        free
+    }
+
+    pub fn bound_vars(&self) -> BTreeSet<P::Name> {
+        self.subterms()
+            .filter_map(|term| match term {
+                Self::Abs(_, _, name) => Some(name.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    pub fn subterms(&self) -> Subterms<'_, Self> {
+        Subterms { stack: vec![self] }
+    }
+
+    pub fn size(&self) -> usize {
+        self.subterms().count()
+    }
+
+    pub fn depth(&self) -> usize {
+        depth(self)
+    }
+
+    pub fn fold<A>(
+        &self,
+        var: &mut impl FnMut(u16, &P::Name) -> A,
+        abs: &mut impl FnMut(u16, A, &P::Name) -> A,
+        app: &mut impl FnMut(A, A) -> A,
+    ) -> A {
+        match self {
+            Self::Var(lvl, name) => var(*lvl, name),
+            Self::Abs(lvl, body, name) => {
+                let body = body.fold(var, abs, app);
+                abs(*lvl, body, name)
+            }
+            Self::Apply(t1, t2) => {
+                let t1 = t1.fold(var, abs, app);
+                let t2 = t2.fold(var, abs, app);
+                app(t1, t2)
+            }
+        }
+    }
+
+    pub fn map(&self, f: &mut impl FnMut(Self) -> Self) -> Self {
+        let term = match self {
+            Self::Var(_, _) => self.clone(),
+            Self::Abs(lvl, body, name) => Self::Abs(*lvl, P::new(body.map(f)), name.clone()),
+            Self::Apply(t1, t2) => Self::Apply(P::new(t1.map(f)), P::new(t2.map(f))),
+        };
+        f(term)
+    }
+
+    pub fn accept(&self, visitor: &mut impl Visitor<Self>) {
+        visitor.visit(self);
+    }
+
+    pub fn accept_mut(&mut self, visitor: &mut impl VisitorMut<Self>) {
+        visitor.visit_mut(self);
+    }
+}
@@ -0,0 +1,418 @@
+use crate::{BruijnLevelsTerm, Term};
+use std::{
+    fmt::{self, Display, Formatter, Write},
+    rc::Rc,
+};
+
+/// Instructions of a lazy Krivine machine with an accumulator.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Instruction {
+    /// Loads the thunk bound to a de Bruijn index.
+    Access(u32),
+    /// Binds the argument on top of the stack, with the name at an index of
+    /// the name table.
+    Grab(u32),
+    /// Pushes the accumulator as an argument, allocating a thunk for a closure.
+    Push,
+    /// Loads a closure of the code at an address in the current environment.
+    Closure(u32),
+    /// Enters the thunk in the accumulator, evaluating it at most once.
+    Apply,
+    /// Returns the function closure in the accumulator to the top frame.
+    Return,
+}
+
+impl Display for Instruction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Instruction::Access(index) => write!(f, "ACCESS {index}"),
+            Instruction::Grab(name) => write!(f, "GRAB {name}"),
+            Instruction::Push => write!(f, "PUSH"),
+            Instruction::Closure(addr) => write!(f, "CLOSURE {addr:04}"),
+            Instruction::Apply => write!(f, "APPLY"),
+            Instruction::Return => write!(f, "RETURN"),
+        }
+    }
+}
+
+/// Bytecode for a term.
+///
+/// A variable compiles to `ACCESS n; APPLY`, an abstraction to
+/// `CLOSURE body; RETURN` with a body block starting with `GRAB`, and an
+/// application to the code of its argument (`ACCESS n` or `CLOSURE block`),
+/// `PUSH`, and the code of its function.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Program {
+    code: Vec<Instruction>,
+    names: Vec<Rc<str>>,
+    free: Vec<Rc<str>>,
+    entry: usize,
+}
+
+impl Program {
+    pub fn compile(term: &BruijnLevelsTerm) -> Self {
+        let mut program = Self {
+            code: Vec::new(),
+            names: Vec::new(),
+            free: term.free_level_names(),
+            entry: 0,
+        };
+        program.entry = program.block(term, program.free.len() as u16);
+        program
+    }
+
+    fn name(&mut self, name: &Rc<str>) -> u32 {
+        let index = match self.names.iter().position(|n| n == name) {
+            Some(index) => index,
+            None => {
+                self.names.push(name.clone());
+                self.names.len() - 1
+            }
+        };
+        index as u32
+    }
+
+    fn emit(&mut self, code: Vec<Instruction>) -> u32 {
+        let addr = self.code.len();
+        self.code.extend(code);
+        addr as u32
+    }
+
+    fn block(&mut self, term: &BruijnLevelsTerm, depth: u16) -> usize {
+        let code = self.inline(term, depth);
+        self.emit(code) as usize
+    }
+
+    fn inline(&mut self, term: &BruijnLevelsTerm, depth: u16) -> Vec<Instruction> {
+        match term {
+            BruijnLevelsTerm::Var(lvl, _) => {
+                vec![
+                    Instruction::Access((depth - 1 - lvl) as u32),
+                    Instruction::Apply,
+                ]
+            }
+            BruijnLevelsTerm::Abs(_, body, name) => {
+                let mut code = vec![Instruction::Grab(self.name(name))];
+                code.extend(self.inline(body, depth + 1));
+                vec![Instruction::Closure(self.emit(code)), Instruction::Return]
+            }
+            BruijnLevelsTerm::Apply(t1, t2) => {
+                let argument = match t2.as_ref() {
+                    BruijnLevelsTerm::Var(lvl, _) => Instruction::Access((depth - 1 - lvl) as u32),
+                    _ => Instruction::Closure(self.block(t2, depth) as u32),
+                };
+                let mut code = vec![argument, Instruction::Push];
+                code.extend(self.inline(t1, depth));
+                code
+            }
+        }
+    }
+
+    pub fn code(&self) -> &[Instruction] {
+        &self.code
+    }
+
+    pub fn entry(&self) -> usize {
+        self.entry
+    }
+
+    /// One instruction per line with its address, marking the entry point
+    /// with `>` and showing binder names.
+    pub fn disassemble(&self) -> String {
+        let mut out = String::new();
+        for (addr, instruction) in self.code.iter().enumerate() {
+            let marker = if addr == self.entry { '>' } else { ' ' };
+            match instruction {
+                Instruction::Grab(name) => {
+                    writeln!(
+                        out,
+                        "{marker}{addr:04}  GRAB {}",
+                        self.names[*name as usize]
+                    )
+                }
+                _ => writeln!(out, "{marker}{addr:04}  {instruction}"),
+            }
+            .unwrap();
+        }
+        out
+    }
+}
+
+/// Instructions a [`Vm`] executes before giving up, unless
+/// [`Vm::with_max_steps`] says otherwise.
+pub const DEFAULT_MAX_STEPS: usize = 1 << 24;
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum VmError {
+    #[error("stack limit of {0} frames exceeded")]
+    StackOverflow(usize),
+    #[error("heap limit of {0} cells exceeded")]
+    HeapExhausted(usize),
+    #[error("thunk entered during its own evaluation")]
+    InfiniteLoop,
+    #[error("max steps exceeded after {0} steps")]
+    MaxStepsExceeded(usize),
+}
+
+type Env = Option<usize>;
+
+#[derive(Clone)]
+enum Value {
+    Function(usize, Env),
+    Neutral(Rc<Neutral>),
+}
+
+enum Neutral {
+    Var(u16, Rc<str>),
+    Apply(Rc<Neutral>, usize),
+}
+
+enum Thunk {
+    Delayed(usize, Env),
+    Evaluating,
+    Evaluated(Value),
+}
+
+#[derive(Clone, Copy)]
+enum Frame {
+    Argument(usize),
+    Update(usize),
+}
+
+enum Accumulator {
+    Thunk(usize),
+    Closure(usize, Env),
+}
+
+enum Control {
+    Jump(usize, Env),
+    Force(usize),
+    Return(Value),
+}
+
+/// Runs a [`Program`] to its normal form.
+///
+/// Arguments are thunks updated with their weak head normal form, so the
+/// machine reaches the normal form normal order would. Functions are read
+/// back by applying them to fresh variables. Environments and thunks live
+/// in a heap that is only freed when the next run starts.
+pub struct Vm<'a> {
+    program: &'a Program,
+    thunks: Vec<Thunk>,
+    frames: Vec<(usize, Env)>,
+    stack: Vec<Frame>,
+    stack_limit: Option<usize>,
+    heap_limit: Option<usize>,
+    max_steps: usize,
+    steps: usize,
+}
+
+impl<'a> Vm<'a> {
+    pub fn new(program: &'a Program) -> Self {
+        Self {
+            program,
+            thunks: Vec::new(),
+            frames: Vec::new(),
+            stack: Vec::new(),
+            stack_limit: None,
+            heap_limit: None,
+            max_steps: DEFAULT_MAX_STEPS,
+            steps: 0,
+        }
+    }
+
+    pub fn with_stack_limit(mut self, frames: usize) -> Self {
+        self.stack_limit = Some(frames);
+        self
+    }
+
+    pub fn with_heap_limit(mut self, cells: usize) -> Self {
+        self.heap_limit = Some(cells);
+        self
+    }
+
+    pub fn with_max_steps(mut self, max_steps: usize) -> Self {
+        self.max_steps = max_steps;
+        self
+    }
+
+    /// Instructions executed by the last run.
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    /// Thunks and environment frames allocated by the last run.
+    pub fn heap_size(&self) -> usize {
+        self.thunks.len() + self.frames.len()
+    }
+
+    fn check_heap(&self) -> Result<(), VmError> {
+        match self.heap_limit {
+            Some(limit) if self.heap_size() >= limit => Err(VmError::HeapExhausted(limit)),
+            _ => Ok(()),
+        }
+    }
+
+    fn alloc_thunk(&mut self, thunk: Thunk) -> Result<usize, VmError> {
+        self.check_heap()?;
+        self.thunks.push(thunk);
+        Ok(self.thunks.len() - 1)
+    }
+
+    fn alloc_frame(&mut self, thunk: usize, env: Env) -> Result<Env, VmError> {
+        self.check_heap()?;
+        self.frames.push((thunk, env));
+        Ok(Some(self.frames.len() - 1))
+    }
+
+    fn push(&mut self, frame: Frame) -> Result<(), VmError> {
+        match self.stack_limit {
+            Some(limit) if self.stack.len() >= limit => Err(VmError::StackOverflow(limit)),
+            _ => {
+                self.stack.push(frame);
+                Ok(())
+            }
+        }
+    }
+
+    fn lookup(&self, mut env: Env, index: u32) -> usize {
+        for _ in 0..index {
+            env = self.frames[env.unwrap()].1;
+        }
+        self.frames[env.unwrap()].0
+    }
+
+    /// Executes straight-line code up to the next `APPLY` or `RETURN`.
+    fn execute(&mut self, mut pc: usize, mut env: Env) -> Result<Control, VmError> {
+        let mut acc = None;
+        loop {
+            if self.steps >= self.max_steps {
+                return Err(VmError::MaxStepsExceeded(self.steps));
+            }
+            self.steps += 1;
+            match self.program.code[pc] {
+                Instruction::Access(index) => {
+                    acc = Some(Accumulator::Thunk(self.lookup(env, index)))
+                }
+                Instruction::Grab(_) => {
+                    let Some(Frame::Argument(argument)) = self.stack.pop() else {
+                        unreachable!("GRAB without an argument");
+                    };
+                    env = self.alloc_frame(argument, env)?;
+                }
+                Instruction::Push => {
+                    let thunk = match acc.take() {
+                        Some(Accumulator::Thunk(thunk)) => thunk,
+                        Some(Accumulator::Closure(addr, env)) => {
+                            self.alloc_thunk(Thunk::Delayed(addr, env))?
+                        }
+                        None => unreachable!("PUSH with an empty accumulator"),
+                    };
+                    self.push(Frame::Argument(thunk))?;
+                }
+                Instruction::Closure(addr) => acc = Some(Accumulator::Closure(addr as usize, env)),
+                Instruction::Apply => {
+                    let Some(Accumulator::Thunk(thunk)) = acc else {
+                        unreachable!("APPLY without a thunk");
+                    };
+                    return Ok(Control::Force(thunk));
+                }
+                Instruction::Return => {
+                    let Some(Accumulator::Closure(addr, env)) = acc else {
+                        unreachable!("RETURN without a closure");
+                    };
+                    return Ok(Control::Return(Value::Function(addr, env)));
+                }
+            }
+            pc += 1;
+        }
+    }
+
+    /// Runs until a value is returned with the stack back at `base`.
+    fn run(&mut self, base: usize, mut control: Control) -> Result<Value, VmError> {
+        loop {
+            control = match control {
+                Control::Jump(addr, env) => self.execute(addr, env)?,
+                Control::Force(thunk) => match &self.thunks[thunk] {
+                    Thunk::Evaluated(value) => Control::Return(value.clone()),
+                    Thunk::Evaluating => return Err(VmError::InfiniteLoop),
+                    Thunk::Delayed(addr, env) => {
+                        let (addr, env) = (*addr, *env);
+                        self.thunks[thunk] = Thunk::Evaluating;
+                        self.push(Frame::Update(thunk))?;
+                        Control::Jump(addr, env)
+                    }
+                },
+                Control::Return(value) if self.stack.len() == base => return Ok(value),
+                Control::Return(value) => match (self.stack[self.stack.len() - 1], value) {
+                    (Frame::Update(thunk), value) => {
+                        self.stack.pop();
+                        self.thunks[thunk] = Thunk::Evaluated(value.clone());
+                        Control::Return(value)
+                    }
+                    (Frame::Argument(_), Value::Function(addr, env)) => Control::Jump(addr, env),
+                    (Frame::Argument(argument), Value::Neutral(neutral)) => {
+                        self.stack.pop();
+                        Control::Return(Value::Neutral(Rc::new(Neutral::Apply(neutral, argument))))
+                    }
+                },
+            };
+        }
+    }
+
+    fn read_back(&mut self, value: Value, depth: u16) -> Result<Rc<BruijnLevelsTerm>, VmError> {
+        match value {
+            Value::Function(addr, env) => {
+                let Instruction::Grab(name) = self.program.code[addr] else {
+                    unreachable!("function without GRAB");
+                };
+                let name = self.program.names[name as usize].clone();
+                let var = Value::Neutral(Rc::new(Neutral::Var(depth, name.clone())));
+                let var = self.alloc_thunk(Thunk::Evaluated(var))?;
+                let base = self.stack.len();
+                self.push(Frame::Argument(var))?;
+                let body = self.run(base, Control::Jump(addr, env))?;
+                let body = self.read_back(body, depth + 1)?;
+                Ok(Rc::new(BruijnLevelsTerm::Abs(depth, body, name)))
+            }
+            Value::Neutral(neutral) => self.read_back_neutral(&neutral, depth),
+        }
+    }
+
+    fn read_back_neutral(
+        &mut self,
+        neutral: &Neutral,
+        depth: u16,
+    ) -> Result<Rc<BruijnLevelsTerm>, VmError> {
+        match neutral {
+            Neutral::Var(lvl, name) => Ok(Rc::new(BruijnLevelsTerm::Var(*lvl, name.clone()))),
+            Neutral::Apply(function, argument) => {
+                let function = self.read_back_neutral(function, depth)?;
+                let argument = self.run(self.stack.len(), Control::Force(*argument))?;
+                Ok(Rc::new(BruijnLevelsTerm::Apply(
+                    function,
+                    self.read_back(argument, depth)?,
+                )))
+            }
+        }
+    }
+
+    pub fn normalize(&mut self) -> Result<Rc<BruijnLevelsTerm>, VmError> {
+        self.thunks.clear();
+        self.frames.clear();
+        self.stack.clear();
+        self.steps = 0;
+        let mut env = None;
+        for (lvl, name) in (0..).zip(&self.program.free) {
+            let var = Value::Neutral(Rc::new(Neutral::Var(lvl, name.clone())));
+            let thunk = self.alloc_thunk(Thunk::Evaluated(var))?;
+            env = self.alloc_frame(thunk, env)?;
+        }
+        let value = self.run(0, Control::Jump(self.program.entry, env))?;
+        self.read_back(value, self.program.free.len() as u16)
+    }
+
+    pub fn normalize_term(&mut self) -> Result<Term, VmError> {
+        self.normalize().map(|term| Term::from((*term).clone()))
+    }
+}
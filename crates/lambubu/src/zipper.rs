@@ -0,0 +1,215 @@
+use crate::{
+    levels::GenericBruijnLevelsTerm,
+    path::{Direction, Path},
+    pointer::{ArcPointer, Pointer, RcPointer},
+    term::GenericTerm,
+};
+use std::{rc::Rc, sync::Arc};
+
+/// A pointer to a term that can be taken apart one level at a time.
+pub trait Zip: Clone {
+    fn child(&self, direction: Direction) -> Option<&Self>;
+    /// Replaces the child in `direction`, reusing the node if it is not
+    /// shared.
+    fn with_child(self, direction: Direction, child: Self) -> Self;
+    fn ptr_eq(&self, other: &Self) -> bool;
+}
+
+fn term_child<P: Pointer>(
+    term: &GenericTerm<P>,
+    direction: Direction,
+) -> Option<&P::Ptr<GenericTerm<P>>> {
+    match (term, direction) {
+        (GenericTerm::Abs(_, body), Direction::Body) => Some(body),
+        (GenericTerm::Apply(t1, _), Direction::Function) => Some(t1),
+        (GenericTerm::Apply(_, t2), Direction::Argument) => Some(t2),
+        _ => None,
+    }
+}
+
+fn term_with_child<P: Pointer>(
+    term: P::Ptr<GenericTerm<P>>,
+    direction: Direction,
+    child: P::Ptr<GenericTerm<P>>,
+) -> P::Ptr<GenericTerm<P>> {
+    P::new(match (P::unwrap_or_clone(term), direction) {
+        (GenericTerm::Abs(name, _), Direction::Body) => GenericTerm::Abs(name, child),
+        (GenericTerm::Apply(_, t2), Direction::Function) => GenericTerm::Apply(child, t2),
+        (GenericTerm::Apply(t1, _), Direction::Argument) => GenericTerm::Apply(t1, child),
+        _ => unreachable!(),
+    })
+}
+
+fn levels_child<P: Pointer>(
+    term: &GenericBruijnLevelsTerm<P>,
+    direction: Direction,
+) -> Option<&P::Ptr<GenericBruijnLevelsTerm<P>>> {
+    match (term, direction) {
+        (GenericBruijnLevelsTerm::Abs(_, body, _), Direction::Body) => Some(body),
+        (GenericBruijnLevelsTerm::Apply(t1, _), Direction::Function) => Some(t1),
+        (GenericBruijnLevelsTerm::Apply(_, t2), Direction::Argument) => Some(t2),
+        _ => None,
+    }
+}
+
+fn levels_with_child<P: Pointer>(
+    term: P::Ptr<GenericBruijnLevelsTerm<P>>,
+    direction: Direction,
+    child: P::Ptr<GenericBruijnLevelsTerm<P>>,
+) -> P::Ptr<GenericBruijnLevelsTerm<P>> {
+    P::new(match (P::unwrap_or_clone(term), direction) {
+        (GenericBruijnLevelsTerm::Abs(lvl, _, name), Direction::Body) => {
+            GenericBruijnLevelsTerm::Abs(lvl, child, name)
+        }
+        (GenericBruijnLevelsTerm::Apply(_, t2), Direction::Function) => {
+            GenericBruijnLevelsTerm::Apply(child, t2)
+        }
+        (GenericBruijnLevelsTerm::Apply(t1, _), Direction::Argument) => {
+            GenericBruijnLevelsTerm::Apply(t1, child)
+        }
+        _ => unreachable!(),
+    })
+}
+
+macro_rules! zip_pointer {
+    ($pointer:ty, $ptr:ident) => {
+        impl Zip for $ptr<GenericTerm<$pointer>> {
+            fn child(&self, direction: Direction) -> Option<&Self> {
+                term_child(self, direction)
+            }
+
+            fn with_child(self, direction: Direction, child: Self) -> Self {
+                term_with_child::<$pointer>(self, direction, child)
+            }
+
+            fn ptr_eq(&self, other: &Self) -> bool {
+                $ptr::ptr_eq(self, other)
+            }
+        }
+
+        impl Zip for $ptr<GenericBruijnLevelsTerm<$pointer>> {
+            fn child(&self, direction: Direction) -> Option<&Self> {
+                levels_child(self, direction)
+            }
+
+            fn with_child(self, direction: Direction, child: Self) -> Self {
+                levels_with_child::<$pointer>(self, direction, child)
+            }
+
+            fn ptr_eq(&self, other: &Self) -> bool {
+                $ptr::ptr_eq(self, other)
+            }
+        }
+    };
+}
+
+zip_pointer!(RcPointer, Rc);
+zip_pointer!(ArcPointer, Arc);
+
+/// Cursor focused on a subterm, remembering the way back to the root.
+///
+/// Moving up rebuilds only the nodes between the focus and the root, and only
+/// if the focus was replaced; every other subterm stays shared with the
+/// original term. `T` is the pointer to the nodes, such as `Rc<Term>` or
+/// `Arc<sync::BruijnLevelsTerm>`.
+#[derive(Clone, Debug)]
+pub struct Zipper<T> {
+    focus: T,
+    parents: Vec<(T, Direction)>,
+}
+
+impl<T: Zip> Zipper<T> {
+    pub fn new(root: T) -> Self {
+        Self {
+            focus: root,
+            parents: Vec::new(),
+        }
+    }
+
+    pub fn at(root: T, path: &Path) -> Option<Self> {
+        let mut zipper = Self::new(root);
+        zipper.descend(path).then_some(zipper)
+    }
+
+    pub fn focus(&self) -> &T {
+        &self.focus
+    }
+
+    pub fn path(&self) -> Path {
+        Path::from(
+            self.parents
+                .iter()
+                .map(|(_, direction)| *direction)
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    pub fn is_root(&self) -> bool {
+        self.parents.is_empty()
+    }
+
+    pub fn down_to(&mut self, direction: Direction) -> bool {
+        let Some(child) = self.focus.child(direction).cloned() else {
+            return false;
+        };
+        let parent = std::mem::replace(&mut self.focus, child);
+        self.parents.push((parent, direction));
+        true
+    }
+
+    pub fn down(&mut self) -> bool {
+        self.down_to(Direction::Body) || self.down_to(Direction::Function)
+    }
+
+    pub fn up(&mut self) -> bool {
+        let Some((parent, direction)) = self.parents.pop() else {
+            return false;
+        };
+        self.focus = match parent.child(direction) {
+            Some(child) if child.ptr_eq(&self.focus) => parent,
+            _ => parent.with_child(direction, self.focus.clone()),
+        };
+        true
+    }
+
+    fn sibling(&mut self, from: Direction, to: Direction) -> bool {
+        if !matches!(self.parents.last(), Some((_, direction)) if *direction == from) {
+            return false;
+        }
+        self.up();
+        self.down_to(to)
+    }
+
+    pub fn left(&mut self) -> bool {
+        self.sibling(Direction::Argument, Direction::Function)
+    }
+
+    pub fn right(&mut self) -> bool {
+        self.sibling(Direction::Function, Direction::Argument)
+    }
+
+    /// Moves down along `path`. On failure the zipper is left where the path
+    /// stopped matching.
+    pub fn descend(&mut self, path: &Path) -> bool {
+        path.directions()
+            .iter()
+            .all(|direction| self.down_to(*direction))
+    }
+
+    pub fn top(&mut self) {
+        while self.up() {}
+    }
+
+    pub fn replace(&mut self, term: T) -> T {
+        std::mem::replace(&mut self.focus, term)
+    }
+
+    pub fn modify(&mut self, f: impl FnOnce(T) -> T) {
+        self.focus = f(self.focus.clone());
+    }
+
+    pub fn into_root(mut self) -> T {
+        self.top();
+        self.focus
+    }
+}
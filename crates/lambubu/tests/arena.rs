@@ -0,0 +1,136 @@
+use lambubu::BruijnLevelsTerm;
+use lambubu::Term;
+use lambubu::TermArena;
+
+fn church(n: usize) -> Term {
+    let mut body = Term::var("x");
+    for _ in 0..n {
+        body = Term::app(Term::var("f"), body);
+    }
+    Term::abs("f", Term::abs("x", body))
+}
+
+fn arena_reduce_to_normal(t: Term) -> Term {
+    let mut arena = TermArena::new();
+    let mut id = arena.insert_term(t);
+    while !arena.is_normal_form(id) {
+        id = arena.reduce_step_normal_order(id);
+    }
+    arena.to_term(id)
+}
+
+fn reduce_to_normal(mut t: Term) -> Term {
+    while !t.is_normal_form() {
+        t = t.reduce_step_normal_order();
+    }
+    t
+}
+
+#[test]
+fn arena_interns_equal_terms_once() {
+    let mut arena = TermArena::new();
+    let a = arena.insert_term(church(3));
+    let b = arena.insert_term(church(3));
+    assert_eq!(a, b);
+    // f, x, (f x), (f (f x)), (f (f (f x))), λx, λf
+    assert_eq!(arena.len(), 7);
+}
+
+// λx.x and λy.y are one node, read back with the first binder name
+#[test]
+fn arena_interns_alpha_equivalent_terms_once() {
+    let mut arena = TermArena::new();
+    let a = arena.insert_term(Term::abs("x", Term::var("x")));
+    let b = arena.insert_term(Term::abs("y", Term::var("y")));
+    assert_eq!(a, b);
+    assert_eq!(arena.len(), 2);
+    assert_eq!(arena.to_term(b), Term::abs("x", Term::var("x")));
+}
+
+// λx.a and λx.b differ in a free variable, so they stay apart
+#[test]
+fn arena_keeps_free_variables_apart() {
+    let mut arena = TermArena::new();
+    let a = arena.insert_term(Term::abs("x", Term::var("a")));
+    let b = arena.insert_term(Term::abs("x", Term::var("b")));
+    assert_ne!(a, b);
+    assert_eq!(arena.to_term(b), Term::abs("x", Term::var("b")));
+}
+
+#[test]
+fn arena_distinguishes_different_terms() {
+    let mut arena = TermArena::new();
+    let a = arena.insert_term(church(2));
+    let b = arena.insert_term(church(3));
+    assert_ne!(a, b);
+}
+
+#[test]
+fn arena_round_trip() {
+    let t = Term::app(church(2), Term::abs("y", Term::var("y")));
+    let mut arena = TermArena::new();
+    let id = arena.insert_term(t.clone());
+    assert_eq!(arena.to_term(id), t);
+    assert_eq!(
+        *arena.to_levels(id),
+        *BruijnLevelsTerm::from_open_term(t.clone())
+    );
+}
+
+// (λx.x) a →_no a
+#[test]
+fn arena_basic_beta() {
+    let term = Term::app(Term::abs("x", Term::var("x")), Term::var("a"));
+    let mut arena = TermArena::new();
+    let id = arena.insert_term(term);
+    let id = arena.reduce_step_normal_order(id);
+    assert_eq!(arena.to_term(id), Term::var("a"));
+}
+
+// (λx.x x) ((λy.y) a) →_cbv (λx.x x) a
+#[test]
+fn arena_call_by_value_reduces_argument() {
+    let arg = Term::app(Term::abs("y", Term::var("y")), Term::var("a"));
+    let func = Term::abs("x", Term::app(Term::var("x"), Term::var("x")));
+    let term = Term::app(func.clone(), arg);
+    let mut arena = TermArena::new();
+    let id = arena.insert_term(term);
+    let id = arena.reduce_step_call_by_value(id);
+    assert_eq!(arena.to_term(id), Term::app(func, Term::var("a")));
+}
+
+#[test]
+fn arena_applicative_order_matches_levels() {
+    let term = Term::app(church(2), church(2));
+    let mut arena = TermArena::new();
+    let mut id = arena.insert_term(term.clone());
+    let mut b = BruijnLevelsTerm::from_open_term(term);
+    while !b.is_normal_form() {
+        b = b.reduce_step_applicative_order();
+        id = arena.reduce_step_applicative_order(id);
+        assert_eq!(arena.to_levels(id), b);
+    }
+}
+
+// 2 2 ↠ 4
+#[test]
+fn arena_church_exponent() {
+    let term = Term::app(church(2), church(2));
+    assert_eq!(arena_reduce_to_normal(term.clone()), reduce_to_normal(term));
+}
+
+// 3 3 ↠ 27
+#[test]
+fn arena_reuses_nodes_across_steps() {
+    let term = Term::app(church(3), church(3));
+    let mut arena = TermArena::new();
+    let mut id = arena.insert_term(term.clone());
+    while !arena.is_normal_form(id) {
+        id = arena.reduce_step_normal_order(id);
+    }
+    assert_eq!(arena.to_term(id), reduce_to_normal(term));
+    let normal = arena.len();
+    let again = arena.insert(&arena.to_levels(id));
+    assert_eq!(again, id);
+    assert_eq!(arena.len(), normal);
+}
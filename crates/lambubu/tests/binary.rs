@@ -0,0 +1,220 @@
+use lambubu::BruijnLevelsTerm;
+use lambubu::Term;
+use lambubu::binary::{
+    BinaryError, BinaryReader, BinaryWriter, DEFAULT_MAX_TERM_SIZE, MAGIC, VERSION,
+};
+use std::rc::Rc;
+
+// λf.λx.f (f (… x))
+fn numeral(n: usize) -> Term {
+    let body = (0..n).fold(Term::var("x"), |acc, _| Term::app(Term::var("f"), acc));
+    Term::abs("f", Term::abs("x", body))
+}
+
+// λx.(x y)
+fn open_term() -> Term {
+    Term::abs("x", Term::app(Term::var("x"), Term::var("y")))
+}
+
+fn write_all(terms: &[Term]) -> Vec<u8> {
+    let mut writer = BinaryWriter::new(Vec::new()).unwrap();
+    for term in terms {
+        writer.write_term(term).unwrap();
+    }
+    writer.into_inner()
+}
+
+fn read_all(bytes: &[u8]) -> Result<Vec<Term>, BinaryError> {
+    BinaryReader::new(bytes)?.collect()
+}
+
+#[test]
+fn binary_header() {
+    let bytes = write_all(&[]);
+    assert_eq!(&bytes[..4], MAGIC);
+    assert_eq!(bytes[4], VERSION);
+    assert_eq!(bytes.len(), 5);
+    assert!(read_all(&bytes).unwrap().is_empty());
+}
+
+#[test]
+fn binary_round_trip() {
+    let terms = vec![
+        numeral(0),
+        numeral(3),
+        open_term(),
+        Term::var("z"),
+        Term::app(Term::var("a"), Term::var("b")),
+    ];
+    assert_eq!(read_all(&write_all(&terms)).unwrap(), terms);
+}
+
+#[test]
+fn binary_shares_subterms() {
+    // (λx.x λx.x) is written with a single identity node
+    let id = Term::abs("x", Term::var("x"));
+    let shared = write_all(&[Term::app(id.clone(), id.clone())]);
+    let single = write_all(&[id]);
+    assert_eq!(shared.len(), single.len() + 3);
+}
+
+#[test]
+fn binary_shares_across_terms() {
+    let once = write_all(&[numeral(20)]);
+    let twice = write_all(&[numeral(20), numeral(20)]);
+    assert_eq!(twice.len(), once.len() + 2);
+    assert_eq!(read_all(&twice).unwrap(), vec![numeral(20), numeral(20)]);
+}
+
+#[test]
+fn binary_smaller_than_text() {
+    let term = numeral(50);
+    assert!(write_all(std::slice::from_ref(&term)).len() < term.to_string().len());
+}
+
+#[test]
+fn binary_read_levels_keeps_sharing() {
+    let id = Term::abs("x", Term::var("x"));
+    let bytes = write_all(&[Term::app(id.clone(), id)]);
+    let mut reader = BinaryReader::new(bytes.as_slice()).unwrap();
+    let term = reader.read_levels().unwrap().unwrap();
+    let BruijnLevelsTerm::Apply(t1, t2) = term.as_ref() else {
+        panic!("expected an application, got {term:?}");
+    };
+    assert!(Rc::ptr_eq(t1, t2));
+    assert!(reader.read_levels().unwrap().is_none());
+}
+
+#[test]
+fn binary_bad_magic() {
+    assert!(matches!(
+        BinaryReader::new(&b"LMBX\x01"[..]),
+        Err(BinaryError::BadMagic)
+    ));
+    assert!(matches!(
+        BinaryReader::new(&b"LM"[..]),
+        Err(BinaryError::BadMagic)
+    ));
+}
+
+#[test]
+fn binary_unsupported_version() {
+    assert!(matches!(
+        BinaryReader::new(&b"LMBU\x07"[..]),
+        Err(BinaryError::UnsupportedVersion(7))
+    ));
+}
+
+#[test]
+fn binary_unknown_tag() {
+    assert!(matches!(
+        read_all(b"LMBU\x01\x09"),
+        Err(BinaryError::UnknownTag(9))
+    ));
+}
+
+#[test]
+fn binary_bad_references() {
+    // a variable referring to a name that was never defined
+    assert!(matches!(
+        read_all(b"LMBU\x01\x01\x00\x00"),
+        Err(BinaryError::BadReference {
+            kind: "name",
+            reference: 0
+        })
+    ));
+    // a term referring to a node before the start of the stream
+    assert!(matches!(
+        read_all(b"LMBU\x01\x04\x01"),
+        Err(BinaryError::BadReference {
+            kind: "node",
+            reference: 1
+        })
+    ));
+}
+
+#[test]
+fn binary_truncated_stream() {
+    let bytes = write_all(&[numeral(2)]);
+    assert!(matches!(
+        read_all(&bytes[..bytes.len() - 1]),
+        Err(BinaryError::Io(_))
+    ));
+}
+
+#[test]
+fn binary_huge_name_length() {
+    // a name claiming to be 2^63 bytes long, followed by nothing
+    assert!(matches!(
+        read_all(b"LMBU\x01\x00\xff\xff\xff\xff\xff\xff\xff\xff\x7f"),
+        Err(BinaryError::Io(_))
+    ));
+}
+
+#[test]
+fn binary_level_overflow() {
+    // a variable at level 2^16
+    assert!(matches!(
+        read_all(b"LMBU\x01\x00\x01x\x01\x80\x80\x04\x00"),
+        Err(BinaryError::LevelOverflow(65536))
+    ));
+}
+
+#[test]
+fn binary_level_out_of_scope() {
+    // λx.v1, where level 1 is bound by nothing
+    assert!(matches!(
+        read_all(b"LMBU\x01\x00\x01x\x01\x01\x00\x02\x00\x00\x01\x04\x01"),
+        Err(BinaryError::LevelOutOfScope { level: 1, depth: 1 })
+    ));
+}
+
+#[test]
+fn binary_level_past_u16() {
+    // λx.λy.y with x at level 65535, so y has no level left
+    assert!(matches!(
+        read_all(b"LMBU\x01\x00\x01x\x00\x01y\x01\x00\x01\x02\x00\x01\x01\x02\xff\xff\x03\x00\x01\x04\x01"),
+        Err(BinaryError::LevelOverflow(65536))
+    ));
+}
+
+// 64 applications, each using the previous one twice, describe a term of
+// 2^65 - 1 nodes in a few hundred bytes
+fn doubling_stream(applications: usize) -> Vec<u8> {
+    let mut bytes = b"LMBU\x01\x00\x01x\x01\x00\x00".to_vec();
+    for _ in 0..applications {
+        bytes.extend_from_slice(b"\x03\x01\x01");
+    }
+    bytes.extend_from_slice(b"\x04\x01");
+    bytes
+}
+
+#[test]
+fn binary_rejects_exponential_expansion() {
+    let bytes = doubling_stream(64);
+    assert!(matches!(
+        read_all(&bytes),
+        Err(BinaryError::TermTooLarge {
+            limit: DEFAULT_MAX_TERM_SIZE
+        })
+    ));
+    let mut reader = BinaryReader::new(bytes.as_slice()).unwrap();
+    assert!(reader.read_levels().unwrap().is_some());
+}
+
+#[test]
+fn binary_max_term_size() {
+    // ((x x) (x x)) ((x x) (x x)), 15 nodes
+    let bytes = doubling_stream(3);
+    let mut reader = BinaryReader::new(bytes.as_slice())
+        .unwrap()
+        .with_max_term_size(14);
+    assert!(matches!(
+        reader.read_term(),
+        Err(BinaryError::TermTooLarge { limit: 14 })
+    ));
+    let mut reader = BinaryReader::new(bytes.as_slice())
+        .unwrap()
+        .with_max_term_size(15);
+    assert_eq!(reader.read_term().unwrap().unwrap().size(), 15);
+}
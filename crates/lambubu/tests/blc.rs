@@ -0,0 +1,111 @@
+use lambubu::Term;
+use lambubu::blc::{self, BlcError};
+
+fn reduce_to_normal(mut t: Term) -> Term {
+    while !t.is_normal_form() {
+        t = t.reduce_step_normal_order();
+    }
+    t
+}
+
+fn bits(s: &str) -> Vec<bool> {
+    blc::bits_from_str(s).unwrap()
+}
+
+fn s_combinator() -> Term {
+    Term::abs(
+        "x",
+        Term::abs(
+            "y",
+            Term::abs(
+                "z",
+                Term::app(
+                    Term::app(Term::var("x"), Term::var("z")),
+                    Term::app(Term::var("y"), Term::var("z")),
+                ),
+            ),
+        ),
+    )
+}
+
+#[test]
+fn blc_encode_combinators() {
+    let id = Term::abs("x", Term::var("x"));
+    let k = Term::abs("x", Term::abs("y", Term::var("x")));
+    assert_eq!(blc::bits_to_string(&blc::encode(&id).unwrap()), "0010");
+    assert_eq!(blc::bits_to_string(&blc::encode(&k).unwrap()), "0000110");
+    assert_eq!(
+        blc::bits_to_string(&blc::encode(&s_combinator()).unwrap()),
+        "00000001011110100111010"
+    );
+}
+
+#[test]
+fn blc_encode_free_variable() {
+    let term = Term::abs("x", Term::app(Term::var("x"), Term::var("y")));
+    assert_eq!(
+        blc::encode(&term),
+        Err(BlcError::FreeVariable("y".to_string()))
+    );
+}
+
+#[test]
+fn blc_round_trip() {
+    let term = s_combinator();
+    let encoded = blc::encode(&term).unwrap();
+    let (decoded, used) = blc::decode(&encoded).unwrap();
+    assert_eq!(used, encoded.len());
+    assert!(decoded.alpha_eq(&term));
+}
+
+// trailing padding after the program is left unread
+#[test]
+fn blc_decode_ignores_padding() {
+    let packed = blc::bits_to_bytes(&bits("0010"));
+    assert_eq!(packed, vec![0b0010_0000]);
+    let (decoded, used) = blc::decode(&blc::bytes_to_bits(&packed)).unwrap();
+    assert_eq!(used, 4);
+    assert!(decoded.alpha_eq(&Term::abs("x", Term::var("x"))));
+}
+
+#[test]
+fn blc_decode_errors() {
+    assert_eq!(blc::decode(&bits("00")), Err(BlcError::UnexpectedEnd));
+    assert_eq!(
+        blc::decode(&bits("00110")),
+        Err(BlcError::UnboundIndex { index: 2, depth: 1 })
+    );
+    assert_eq!(blc::bits_from_str("01x"), None);
+}
+
+#[test]
+fn blc_bits_list_round_trip() {
+    let input = bits("1101");
+    assert_eq!(blc::decode_bits_list(&blc::bits_list(&input)), Ok(input));
+}
+
+#[test]
+fn blc_bytes_list_round_trip() {
+    let input = b"lambda".to_vec();
+    assert_eq!(blc::decode_bytes_list(&blc::bytes_list(&input)), Ok(input));
+}
+
+// λl.(l λh.λt.t) drops the first element of its input list
+#[test]
+fn blc_run_tail() {
+    let program = Term::abs(
+        "l",
+        Term::app(
+            Term::var("l"),
+            Term::abs("h", Term::abs("t", Term::var("t"))),
+        ),
+    );
+    let output = reduce_to_normal(Term::app(program, blc::bits_list(&bits("011"))));
+    assert_eq!(blc::decode_bits_list(&output), Ok(bits("11")));
+}
+
+#[test]
+fn blc_decode_not_a_list() {
+    let term = Term::abs("x", Term::var("x"));
+    assert_eq!(blc::decode_list(&term), Err(BlcError::NotAList(term)));
+}
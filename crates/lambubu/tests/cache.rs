@@ -0,0 +1,198 @@
+#![cfg(feature = "cache")]
+
+use lambubu::BruijnLevelsTerm;
+use lambubu::NormalFormCache;
+use lambubu::Strategy;
+use lambubu::Term;
+use lambubu::cache::CacheError;
+use lambubu::divergence::{EvaluationError, Evaluator};
+use lambubu::sync;
+use std::{sync::Arc, thread};
+
+fn church(n: usize) -> Term {
+    let mut body = Term::var("x");
+    for _ in 0..n {
+        body = Term::app(Term::var("f"), body);
+    }
+    Term::abs("f", Term::abs("x", body))
+}
+
+fn omega() -> Term {
+    let w = Term::abs("x", Term::app(Term::var("x"), Term::var("x")));
+    Term::app(w.clone(), w)
+}
+
+fn reduce_to_terminal(t: Term, strategy: Strategy) -> Term {
+    let mut b = BruijnLevelsTerm::from_open_term(t);
+    while !b.is_terminal(strategy) {
+        b = b.reduce_step(strategy);
+    }
+    Term::from((*b).clone())
+}
+
+fn levels(t: &Term) -> Arc<sync::BruijnLevelsTerm> {
+    sync::BruijnLevelsTerm::from_open_term(sync::Term::from(t))
+}
+
+fn cached(cache: &NormalFormCache, t: Term, strategy: Strategy) -> Term {
+    let b = cache.normalize(levels(&t), strategy, 10_000).unwrap();
+    Term::from(&sync::Term::from((*b).clone()))
+}
+
+#[test]
+fn cache_agrees_with_stepping() {
+    let terms = [
+        Term::app(church(2), church(2)),
+        Term::app(church(3), Term::abs("y", Term::var("y"))),
+        Term::app(
+            Term::var("g"),
+            Term::app(Term::abs("y", Term::var("y")), Term::var("z")),
+        ),
+        Term::abs(
+            "z",
+            Term::app(Term::abs("x", Term::var("x")), Term::var("a")),
+        ),
+    ];
+    for strategy in Strategy::ALL {
+        let cache = NormalFormCache::new(1024);
+        for term in &terms {
+            assert_eq!(
+                cached(&cache, term.clone(), strategy),
+                reduce_to_terminal(term.clone(), strategy),
+                "{strategy:?} on {term}"
+            );
+        }
+    }
+}
+
+#[test]
+fn cache_hits_alpha_equivalent_terms() {
+    let cache = NormalFormCache::new(1024);
+    let id_x = Term::app(Term::abs("x", Term::var("x")), Term::var("a"));
+    let id_y = Term::app(Term::abs("y", Term::var("y")), Term::var("a"));
+    cached(&cache, id_x, Strategy::NormalOrder);
+    let before = cache.stats();
+    assert_eq!(before.hits, 0);
+    assert_eq!(cached(&cache, id_y, Strategy::NormalOrder), Term::var("a"));
+    assert_eq!(cache.stats().hits, 1);
+}
+
+#[test]
+fn cache_keeps_free_variable_names() {
+    let cache = NormalFormCache::new(1024);
+    let to_a = Term::app(Term::abs("x", Term::var("x")), Term::var("a"));
+    let to_b = Term::app(Term::abs("x", Term::var("x")), Term::var("b"));
+    cached(&cache, to_a, Strategy::NormalOrder);
+    assert_eq!(cached(&cache, to_b, Strategy::NormalOrder), Term::var("b"));
+}
+
+#[test]
+fn cache_separates_strategies() {
+    let cache = NormalFormCache::new(1024);
+    let term = Term::abs(
+        "z",
+        Term::app(Term::abs("x", Term::var("x")), Term::var("a")),
+    );
+    let applied = Term::app(Term::abs("w", Term::var("w")), term.clone());
+    assert_eq!(cached(&cache, applied.clone(), Strategy::CallByName), term);
+    assert_eq!(
+        cached(&cache, applied, Strategy::NormalOrder),
+        Term::abs("z", Term::var("a"))
+    );
+}
+
+// shared subterms are only normalized once
+#[test]
+fn cache_reuses_subterm_results() {
+    let cache = NormalFormCache::new(1024);
+    let shared = Term::app(church(2), church(2));
+    let term = Term::app(Term::app(Term::var("pair"), shared.clone()), shared);
+    cached(&cache, term, Strategy::ApplicativeOrder);
+    assert!(cache.stats().hits >= 1);
+}
+
+#[test]
+fn cache_respects_capacity() {
+    let cache = NormalFormCache::new(2);
+    cached(
+        &cache,
+        Term::app(church(2), church(2)),
+        Strategy::NormalOrder,
+    );
+    assert_eq!(cache.stats().entries, 2);
+    cache.clear();
+    assert_eq!(cache.stats().entries, 0);
+    assert_eq!(cache.stats().misses, 0);
+}
+
+#[test]
+fn cache_max_steps() {
+    let cache = NormalFormCache::new(16);
+    assert_eq!(
+        cache.normalize(levels(&omega()), Strategy::NormalOrder, 50),
+        Err(CacheError::MaxStepsExceeded(50))
+    );
+}
+
+#[test]
+fn cache_get_and_insert() {
+    let cache = NormalFormCache::new(16);
+    let term = (*levels(&Term::abs("x", Term::var("x")))).clone();
+    assert_eq!(cache.get(&term, Strategy::NormalOrder), None);
+    cache.insert(&term, Strategy::NormalOrder, Arc::new(term.clone()));
+    assert_eq!(
+        cache.get(&term, Strategy::NormalOrder),
+        Some(Arc::new(term.clone()))
+    );
+    assert_eq!(cache.get(&term, Strategy::CallByName), None);
+    let stats = cache.stats();
+    assert_eq!((stats.hits, stats.misses), (1, 2));
+}
+
+// one cache shared by several threads
+#[test]
+fn cache_is_shared_across_threads() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<NormalFormCache>();
+
+    let cache = NormalFormCache::new(1024);
+    let term = Term::app(church(2), church(2));
+    let shared = sync::BruijnLevelsTerm::from_open_term(sync::Term::from(&term));
+    let results: Vec<_> = thread::scope(|scope| {
+        let handles: Vec<_> = (0..4)
+            .map(|_| scope.spawn(|| cache.normalize(shared.clone(), Strategy::NormalOrder, 10_000)))
+            .collect();
+        handles
+            .into_iter()
+            .map(|h| h.join().unwrap().unwrap())
+            .collect()
+    });
+    let expected = reduce_to_terminal(term, Strategy::NormalOrder);
+    for result in results {
+        assert_eq!(Term::from(&sync::Term::from((*result).clone())), expected);
+    }
+    assert!(cache.stats().entries > 0);
+}
+
+// f (2 2) (2 2): the second copy of 2 2 is looked up, not reduced again
+#[test]
+fn evaluator_consults_cache_for_repeated_subterms() {
+    let cache = Arc::new(NormalFormCache::new(1024));
+    let square = Term::app(church(2), church(2));
+    let term = Term::app(Term::app(Term::var("f"), square.clone()), square);
+    let (result, _) = Evaluator::new(Strategy::NormalOrder)
+        .with_cache(cache.clone())
+        .evaluate(term.clone())
+        .unwrap();
+    assert_eq!(result, reduce_to_terminal(term, Strategy::NormalOrder));
+    assert!(cache.stats().hits > 0);
+}
+
+// a term the cache cannot normalize is still stepped to explain why
+#[test]
+fn evaluator_with_cache_explains_divergence() {
+    let result = Evaluator::new(Strategy::NormalOrder)
+        .with_cache(Arc::new(NormalFormCache::new(1024)))
+        .evaluate(omega());
+    assert_eq!(result, Err(EvaluationError::Cycle { length: 1, step: 1 }));
+}
@@ -0,0 +1,146 @@
+use lambubu::BruijnLevelsTerm;
+use lambubu::Term;
+use lambubu::closure::{ClosureError, CompiledTerm};
+
+fn compile(t: &Term) -> CompiledTerm {
+    CompiledTerm::new(&BruijnLevelsTerm::from_open_term(t.clone()))
+}
+
+fn normalize(t: &Term) -> Term {
+    compile(t).normalize_term().unwrap().0
+}
+
+fn reduce_to_normal(mut t: Term) -> Term {
+    while !t.is_normal_form() {
+        t = t.reduce_step_normal_order();
+    }
+    t
+}
+
+fn church(n: usize) -> Term {
+    let mut body = Term::var("x");
+    for _ in 0..n {
+        body = Term::app(Term::var("f"), body);
+    }
+    Term::abs("f", Term::abs("x", body))
+}
+
+// λm.λn.λf.λx.((n f) ((m f) x))
+fn add() -> Term {
+    Term::abs(
+        "m",
+        Term::abs(
+            "n",
+            Term::abs(
+                "f",
+                Term::abs(
+                    "x",
+                    Term::app(
+                        Term::app(Term::var("n"), Term::var("f")),
+                        Term::app(Term::app(Term::var("m"), Term::var("f")), Term::var("x")),
+                    ),
+                ),
+            ),
+        ),
+    )
+}
+
+fn omega() -> Term {
+    let w = Term::abs("x", Term::app(Term::var("x"), Term::var("x")));
+    Term::app(w.clone(), w)
+}
+
+// (λx.x) a ↠ a
+#[test]
+fn closure_basic_beta() {
+    let term = Term::app(Term::abs("x", Term::var("x")), Term::var("a"));
+    assert_eq!(normalize(&term), Term::var("a"));
+}
+
+// λx.(λy.y) x ↠ λx.x, reducing under the binder
+#[test]
+fn closure_reduces_under_binders() {
+    let term = Term::abs(
+        "x",
+        Term::app(Term::abs("y", Term::var("y")), Term::var("x")),
+    );
+    assert_eq!(normalize(&term), Term::abs("x", Term::var("x")));
+}
+
+// add 2 3 ↠ 5
+#[test]
+fn closure_church_addition() {
+    let term = Term::app(Term::app(add(), church(2)), church(3));
+    assert_eq!(normalize(&term), church(5));
+}
+
+// 3 3 ↠ 27
+#[test]
+fn closure_church_exponent() {
+    let term = Term::app(church(3), church(3));
+    assert!(normalize(&term).alpha_eq(&church(27)));
+}
+
+// (λx.λy.x) y ↠ λy'.y, the free y is not captured
+#[test]
+fn closure_avoids_capture() {
+    let term = Term::app(
+        Term::abs("x", Term::abs("y", Term::var("x"))),
+        Term::var("y"),
+    );
+    assert_eq!(normalize(&term), Term::abs("y'", Term::var("y")));
+}
+
+// open terms keep their free variables and agree with normal order
+#[test]
+fn closure_matches_normal_order() {
+    let terms = [
+        Term::app(Term::app(Term::var("f"), church(2)), Term::var("a")),
+        Term::app(church(2), Term::var("g")),
+        Term::app(Term::app(add(), church(1)), Term::var("n")),
+        Term::app(
+            Term::abs("x", Term::app(Term::var("x"), Term::var("x"))),
+            Term::abs("y", Term::var("z")),
+        ),
+    ];
+    for term in terms {
+        assert_eq!(normalize(&term), reduce_to_normal(term));
+    }
+}
+
+// (λx.λy.y) Ω ↠ λy.y, the argument is never forced
+#[test]
+fn closure_is_lazy() {
+    let term = Term::app(Term::abs("x", Term::abs("y", Term::var("y"))), omega());
+    assert_eq!(normalize(&term), Term::abs("y", Term::var("y")));
+}
+
+#[test]
+fn closure_max_steps() {
+    let compiled = compile(&omega()).with_max_steps(100);
+    assert_eq!(
+        compiled.normalize().unwrap_err(),
+        ClosureError::MaxStepsExceeded(100)
+    );
+}
+
+// (λx.(x x)) ((λy.y) a) forces the shared argument once
+#[test]
+fn closure_shares_arguments() {
+    let term = Term::app(
+        Term::abs("x", Term::app(Term::var("x"), Term::var("x"))),
+        Term::app(Term::abs("y", Term::var("y")), Term::var("a")),
+    );
+    let (result, steps) = compile(&term).normalize_term().unwrap();
+    assert_eq!(result, Term::app(Term::var("a"), Term::var("a")));
+    assert_eq!(steps, 2);
+}
+
+#[test]
+fn closure_runs_repeatedly() {
+    let compiled = compile(&Term::app(Term::app(add(), church(4)), church(6)));
+    let first = compiled.normalize().unwrap();
+    let second = compiled.normalize().unwrap();
+    assert_eq!(first, second);
+    assert_eq!(Term::from((*first.0).clone()), church(10));
+}
@@ -0,0 +1,169 @@
+use lambubu::RegistryEnvironment;
+use lambubu::Term;
+use lambubu::codegen::{CodegenError, Target};
+use lambubu::compile::compile_file;
+
+// λf.λx.(f (f x))
+fn two() -> Term {
+    Term::abs(
+        "f",
+        Term::abs(
+            "x",
+            Term::app(Term::var("f"), Term::app(Term::var("f"), Term::var("x"))),
+        ),
+    )
+}
+
+// (λx.x λy.y)
+fn id_app() -> Term {
+    Term::app(
+        Term::abs("x", Term::var("x")),
+        Term::abs("y", Term::var("y")),
+    )
+}
+
+#[test]
+fn codegen_javascript_expression() {
+    assert_eq!(
+        Target::JavaScript.expression(&two()).unwrap(),
+        "(f) => (x) => f(f(x))"
+    );
+    assert_eq!(
+        Target::JavaScript.expression(&id_app()).unwrap(),
+        "((x) => x)((y) => y)"
+    );
+}
+
+#[test]
+fn codegen_python_expression() {
+    assert_eq!(
+        Target::Python.expression(&two()).unwrap(),
+        "lambda f: lambda x: f(f(x))"
+    );
+    assert_eq!(
+        Target::Python.expression(&id_app()).unwrap(),
+        "(lambda x: x)(lambda y: y)"
+    );
+}
+
+#[test]
+fn codegen_haskell_expression() {
+    assert_eq!(
+        Target::Haskell.expression(&two()).unwrap(),
+        "Fun (\\f -> Fun (\\x -> f % (f % x)))"
+    );
+    assert_eq!(
+        Target::Haskell.expression(&id_app()).unwrap(),
+        "Fun (\\x -> x) % Fun (\\y -> y)"
+    );
+}
+
+#[test]
+fn codegen_application_is_curried() {
+    // λf.λa.λb.((f a) b)
+    let term = Term::abs(
+        "f",
+        Term::abs(
+            "a",
+            Term::abs(
+                "b",
+                Term::app(Term::app(Term::var("f"), Term::var("a")), Term::var("b")),
+            ),
+        ),
+    );
+    assert_eq!(
+        Target::JavaScript.expression(&term).unwrap(),
+        "(f) => (a) => (b) => f(a)(b)"
+    );
+    assert_eq!(
+        Target::Haskell.expression(&term).unwrap(),
+        "Fun (\\f -> Fun (\\a -> Fun (\\b -> f % a % b)))"
+    );
+}
+
+#[test]
+fn codegen_names_are_mangled() {
+    // λin.λx'.(in x')
+    let term = Term::abs(
+        "in",
+        Term::abs("x'", Term::app(Term::var("in"), Term::var("x'"))),
+    );
+    assert_eq!(
+        Target::JavaScript.expression(&term).unwrap(),
+        "(_in) => (x_) => _in(x_)"
+    );
+    assert_eq!(
+        Target::Python.expression(&term).unwrap(),
+        "lambda _in: lambda x_: _in(x_)"
+    );
+    assert_eq!(
+        Target::Haskell.expression(&term).unwrap(),
+        "Fun (\\_in -> Fun (\\x' -> _in % x'))"
+    );
+}
+
+#[test]
+fn codegen_free_variable() {
+    let term = Term::abs("x", Term::app(Term::var("x"), Term::var("y")));
+    for target in [Target::JavaScript, Target::Python, Target::Haskell] {
+        assert_eq!(
+            target.expression(&term),
+            Err(CodegenError::FreeVariable("y".to_owned()))
+        );
+    }
+}
+
+#[test]
+fn codegen_generate_javascript() {
+    let mut env = RegistryEnvironment::new();
+    let terms = compile_file("TWO :: λf.λx.(f (f x)) ID :: λx.x (ID TWO)", &mut env).unwrap();
+    assert_eq!(
+        Target::JavaScript
+            .generate(&env.definitions(), &terms)
+            .unwrap(),
+        concat!(
+            "// Generated by lambubu\n",
+            "const def_TWO = (f) => (x) => f(f(x));\n",
+            "const def_ID = (x) => x;\n",
+            "const term1 = ((x) => x)((f) => (x) => f(f(x)));\n",
+        )
+    );
+}
+
+#[test]
+fn codegen_definitions_keep_file_order() {
+    let mut env = RegistryEnvironment::new();
+    compile_file("B :: λx.x A :: λy.y B :: λz.z", &mut env).unwrap();
+    assert_eq!(
+        env.definitions(),
+        [
+            ("B", &Term::abs("z", Term::var("z"))),
+            ("A", &Term::abs("y", Term::var("y")))
+        ]
+    );
+}
+
+#[test]
+fn codegen_generate_python() {
+    let terms = [two(), id_app()];
+    assert_eq!(
+        Target::Python.generate(&[], &terms).unwrap(),
+        concat!(
+            "# Generated by lambubu\n",
+            "term1 = lambda f: lambda x: f(f(x))\n",
+            "term2 = (lambda x: x)(lambda y: y)\n",
+        )
+    );
+}
+
+#[test]
+fn codegen_generate_haskell() {
+    let code = Target::Haskell
+        .generate(&[("TWO", &two())], &[id_app()])
+        .unwrap();
+    assert!(code.starts_with("-- Generated by lambubu\n"));
+    assert!(code.contains("data Value = Fun (Value -> Value) | Num Integer | Stuck\n"));
+    assert!(code.contains("\ndef_TWO :: Value\ndef_TWO = Fun (\\f -> Fun (\\x -> f % (f % x)))\n"));
+    assert!(code.contains("\nterm1 :: Value\nterm1 = Fun (\\x -> x) % Fun (\\y -> y)\n"));
+    assert!(code.ends_with("\nmain :: IO ()\nmain = mapM_ (print . toNumeral) [term1]\n"));
+}
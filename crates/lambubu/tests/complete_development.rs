@@ -0,0 +1,127 @@
+use lambubu::BruijnLevelsTerm;
+use lambubu::Term;
+
+fn id(name: &str) -> Term {
+    Term::abs(name, Term::var(name))
+}
+
+fn church(n: usize) -> Term {
+    let mut body = Term::var("x");
+    for _ in 0..n {
+        body = Term::app(Term::var("f"), body);
+    }
+    Term::abs("f", Term::abs("x", body))
+}
+
+fn mul() -> Term {
+    Term::abs(
+        "m",
+        Term::abs(
+            "n",
+            Term::abs(
+                "f",
+                Term::app(Term::var("m"), Term::app(Term::var("n"), Term::var("f"))),
+            ),
+        ),
+    )
+}
+
+fn reduce_to_normal(mut t: Term) -> Term {
+    while !t.is_normal_form() {
+        t = t.reduce_step_normal_order();
+    }
+    t
+}
+
+fn develop_to_normal(mut t: Term) -> (Term, usize) {
+    let mut steps = 0;
+    while !t.is_normal_form() {
+        t = t.complete_development();
+        steps += 1;
+    }
+    (t, steps)
+}
+
+fn bruijn_develop(t: Term) -> Term {
+    let b = BruijnLevelsTerm::from_open_term(t);
+    Term::from((*b.complete_development()).clone())
+}
+
+fn bruijn_develop_to_normal(t: Term) -> Term {
+    let mut b = BruijnLevelsTerm::from_open_term(t);
+    while !b.is_normal_form() {
+        b = b.complete_development();
+    }
+    Term::from((*b).clone())
+}
+
+// (λx.(x x)) ((λy.y) z) ⇒ z z: both redexes are contracted at once
+#[test]
+fn develop_all_redexes() {
+    let term = Term::app(
+        Term::abs("x", Term::app(Term::var("x"), Term::var("x"))),
+        Term::app(id("y"), Term::var("z")),
+    );
+    let expected = Term::app(Term::var("z"), Term::var("z"));
+    assert_eq!(term.clone().complete_development(), expected);
+    assert_eq!(bruijn_develop(term), expected);
+}
+
+// (λx.(x y)) (λz.z) ⇒ (λz.z) y: the created redex is left for the next development
+#[test]
+fn develop_does_not_contract_created_redexes() {
+    let term = Term::app(
+        Term::abs("x", Term::app(Term::var("x"), Term::var("y"))),
+        id("z"),
+    );
+    let expected = Term::app(id("z"), Term::var("y"));
+    assert_eq!(term.clone().complete_development(), expected);
+    assert_eq!(bruijn_develop(term), expected);
+}
+
+#[test]
+fn develop_normal_form_is_identity() {
+    let term = church(3);
+    assert_eq!(term.clone().complete_development(), term);
+    assert_eq!(bruijn_develop(term.clone()), term);
+}
+
+// Ω ⇒ Ω
+#[test]
+fn develop_omega() {
+    let w = Term::abs("x", Term::app(Term::var("x"), Term::var("x")));
+    let omega = Term::app(w.clone(), w);
+    assert_eq!(omega.clone().complete_development(), omega);
+    assert!(bruijn_develop(omega.clone()).alpha_eq(&omega));
+}
+
+// (λx.y) Ω ⇒ y, the divergent argument is discarded
+#[test]
+fn develop_discards_argument() {
+    let w = Term::abs("x", Term::app(Term::var("x"), Term::var("x")));
+    let term = Term::app(Term::abs("z", Term::var("y")), Term::app(w.clone(), w));
+    assert_eq!(term.clone().complete_development(), Term::var("y"));
+    assert_eq!(bruijn_develop(term), Term::var("y"));
+}
+
+// MUL 2 3 reaches the normal-order result in fewer steps
+#[test]
+fn develop_church_mul() {
+    let term = Term::app(Term::app(mul(), church(2)), church(3));
+    let (developed, steps) = develop_to_normal(term.clone());
+    assert!(developed.alpha_eq(&reduce_to_normal(term.clone())));
+    assert!(developed.alpha_eq(&church(6)));
+    let mut normal_order_steps = 0;
+    let mut t = term;
+    while !t.is_normal_form() {
+        t = t.reduce_step_normal_order();
+        normal_order_steps += 1;
+    }
+    assert!(steps < normal_order_steps);
+}
+
+#[test]
+fn bruijn_develop_church_mul() {
+    let term = Term::app(Term::app(mul(), church(3)), church(2));
+    assert!(bruijn_develop_to_normal(term).alpha_eq(&church(6)));
+}
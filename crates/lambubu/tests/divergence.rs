@@ -0,0 +1,142 @@
+use lambubu::Strategy;
+use lambubu::Term;
+use lambubu::divergence::{EvaluationError, Evaluator};
+use std::thread;
+
+fn self_apply(copies: usize) -> Term {
+    let mut body = Term::var("x");
+    for _ in 1..copies {
+        body = Term::app(body, Term::var("x"));
+    }
+    let w = Term::abs("x", body);
+    Term::app(w.clone(), w)
+}
+
+// (λx.x) a ↠ a in one step
+#[test]
+fn evaluator_normalizes() {
+    let term = Term::app(Term::abs("x", Term::var("x")), Term::var("a"));
+    assert_eq!(
+        Evaluator::new(Strategy::NormalOrder).evaluate(term),
+        Ok((Term::var("a"), 1))
+    );
+}
+
+// Ω → Ω
+#[test]
+fn evaluator_detects_omega() {
+    let result = Evaluator::new(Strategy::NormalOrder).evaluate(self_apply(2));
+    assert_eq!(result, Err(EvaluationError::Cycle { length: 1, step: 1 }));
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "diverges: cycle of length 1 at step 1"
+    );
+}
+
+// (λx.x x) (λy.y y) is alpha-equivalent to its contractum
+#[test]
+fn evaluator_detects_cycle_up_to_alpha() {
+    let term = Term::app(
+        Term::abs("x", Term::app(Term::var("x"), Term::var("x"))),
+        Term::abs("y", Term::app(Term::var("y"), Term::var("y"))),
+    );
+    assert_eq!(
+        Evaluator::new(Strategy::CallByValue).evaluate(term),
+        Err(EvaluationError::Cycle { length: 1, step: 1 })
+    );
+}
+
+// (λx.λy.y) Ω is fine under normal order but loops under applicative order
+#[test]
+fn evaluator_cycle_depends_on_strategy() {
+    let term = Term::app(
+        Term::abs("x", Term::abs("y", Term::var("y"))),
+        self_apply(2),
+    );
+    assert_eq!(
+        Evaluator::new(Strategy::NormalOrder).evaluate(term.clone()),
+        Ok((Term::abs("y", Term::var("y")), 1))
+    );
+    assert_eq!(
+        Evaluator::new(Strategy::ApplicativeOrder).evaluate(term),
+        Err(EvaluationError::Cycle { length: 1, step: 1 })
+    );
+}
+
+// (λx.x x x) (λx.x x x) grows by one copy every step
+#[test]
+fn evaluator_detects_growth() {
+    let result = Evaluator::new(Strategy::NormalOrder)
+        .with_max_steps(200)
+        .with_growth_window(100)
+        .evaluate(self_apply(3));
+    assert!(matches!(
+        result,
+        Err(EvaluationError::UnboundedGrowth { steps, .. }) if steps <= 100
+    ));
+}
+
+// Y I → W W → I (W W) → W W with W = λx.I (x x)
+#[test]
+fn evaluator_detects_longer_cycle() {
+    let inner = Term::abs(
+        "x",
+        Term::app(Term::var("f"), Term::app(Term::var("x"), Term::var("x"))),
+    );
+    let y = Term::abs("f", Term::app(inner.clone(), inner));
+    let term = Term::app(y, Term::abs("y", Term::var("y")));
+    assert_eq!(
+        Evaluator::new(Strategy::NormalOrder).evaluate(term),
+        Err(EvaluationError::Cycle { length: 2, step: 3 })
+    );
+}
+
+// I (I (... (I a))) shrinks every step, so running out of steps is not divergence
+#[test]
+fn evaluator_max_steps_without_growth() {
+    let mut term = Term::var("a");
+    for _ in 0..20 {
+        term = Term::app(Term::abs("x", Term::var("x")), term);
+    }
+    assert_eq!(
+        Evaluator::new(Strategy::NormalOrder)
+            .with_max_steps(10)
+            .evaluate(term),
+        Err(EvaluationError::MaxStepsExceeded(10))
+    );
+}
+
+// Only a hash of every sample and the sizes of the growth window are kept, so
+// a growing term runs past the window without holding every intermediate copy
+#[test]
+fn evaluator_growth_past_the_window() {
+    let result = Evaluator::new(Strategy::NormalOrder)
+        .with_max_steps(1_000)
+        .with_growth_window(100)
+        .evaluate(self_apply(3));
+    assert!(matches!(
+        result,
+        Err(EvaluationError::UnboundedGrowth { steps, .. }) if steps <= 100
+    ));
+}
+
+// (λx.x x x) (λx.x x x) grows by a copy every step and is reported as growing
+// within the default budget. The spine gets as long as the budget, so the
+// recursion over it runs on a larger stack than the test harness gives.
+#[test]
+fn evaluator_detects_growth_with_defaults() {
+    let result = thread::Builder::new()
+        .stack_size(64 << 20)
+        .spawn(|| {
+            Evaluator::new(Strategy::NormalOrder)
+                .evaluate(self_apply(3))
+                .map(|(_, steps)| steps)
+        })
+        .unwrap()
+        .join()
+        .unwrap();
+    assert!(matches!(
+        result,
+        Err(EvaluationError::UnboundedGrowth { from, to, .. }) if to > from
+    ));
+}
@@ -0,0 +1,133 @@
+use lambubu::BruijnLevelsTerm;
+use lambubu::Strategy;
+use lambubu::Term;
+
+fn omega() -> Term {
+    let w = Term::abs("x", Term::app(Term::var("x"), Term::var("x")));
+    Term::app(w.clone(), w)
+}
+
+fn bruijn_eta_step(t: Term) -> Term {
+    let b = BruijnLevelsTerm::from_open_term(t);
+    Term::from((*b.reduce_step_eta()).clone())
+}
+
+fn reduce_to_beta_eta_normal(mut t: Term, strategy: Strategy) -> Term {
+    while !t.is_terminal(strategy) {
+        t = t.reduce_step(strategy);
+    }
+    t
+}
+
+fn bruijn_reduce_to_beta_eta_normal(t: Term, strategy: Strategy) -> Term {
+    let mut b = BruijnLevelsTerm::from_open_term(t);
+    while !b.is_terminal(strategy) {
+        b = b.reduce_step(strategy);
+    }
+    Term::from((*b).clone())
+}
+
+// λx.(f x) →η f
+#[test]
+fn eta_basic() {
+    let term = Term::abs("x", Term::app(Term::var("f"), Term::var("x")));
+    assert_eq!(term.clone().reduce_step_eta(), Term::var("f"));
+    assert_eq!(bruijn_eta_step(term), Term::var("f"));
+}
+
+// λx.(x x) has no η-redex, x is free in the function part
+#[test]
+fn eta_requires_variable_not_free() {
+    let term = Term::abs("x", Term::app(Term::var("x"), Term::var("x")));
+    assert!(term.is_eta_normal_form());
+    assert_eq!(term.clone().reduce_step_eta(), term);
+    assert_eq!(bruijn_eta_step(term.clone()), term);
+}
+
+// λy.λx.(y x) →η λy.y
+#[test]
+fn eta_under_lambda() {
+    let term = Term::abs(
+        "y",
+        Term::abs("x", Term::app(Term::var("y"), Term::var("x"))),
+    );
+    let expected = Term::abs("y", Term::var("y"));
+    assert_eq!(term.clone().reduce_step_eta(), expected);
+    assert_eq!(bruijn_eta_step(term), expected);
+}
+
+// λx.((λz.z) x) →η λz.z
+#[test]
+fn eta_contracts_to_abstraction() {
+    let id = Term::abs("z", Term::var("z"));
+    let term = Term::abs("x", Term::app(id.clone(), Term::var("x")));
+    assert_eq!(term.clone().reduce_step_eta(), id);
+    assert_eq!(bruijn_eta_step(term), id);
+}
+
+#[test]
+fn eta_expand_picks_fresh_name() {
+    assert_eq!(
+        Term::var("f").eta_expand(),
+        Term::abs("x", Term::app(Term::var("f"), Term::var("x")))
+    );
+    assert_eq!(
+        Term::var("x").eta_expand(),
+        Term::abs("x'", Term::app(Term::var("x"), Term::var("x'")))
+    );
+}
+
+#[test]
+fn bruijn_eta_expand_round_trips() {
+    let term = Term::abs("y", Term::var("y"));
+    let b = BruijnLevelsTerm::from_open_term(term.clone()).eta_expand(0);
+    assert_eq!(
+        Term::from((*b).clone()),
+        Term::abs("x", Term::app(term.clone(), Term::var("x")))
+    );
+    assert_eq!(Term::from((*b.reduce_step_eta()).clone()), term);
+}
+
+// λx.(f x) is β-normal but not βη-normal
+#[test]
+fn beta_eta_normal_form_predicate() {
+    let term = Term::abs("x", Term::app(Term::var("f"), Term::var("x")));
+    assert!(term.is_normal_form());
+    assert!(!term.is_beta_eta_normal_form());
+    assert!(Term::var("f").is_beta_eta_normal_form());
+}
+
+// λx.((λy.y) f x) ↠βη f
+#[test]
+fn beta_eta_strategies() {
+    let term = Term::abs(
+        "x",
+        Term::app(
+            Term::app(Term::abs("y", Term::var("y")), Term::var("f")),
+            Term::var("x"),
+        ),
+    );
+    for strategy in [Strategy::NormalOrderEta, Strategy::ApplicativeOrderEta] {
+        assert_eq!(
+            reduce_to_beta_eta_normal(term.clone(), strategy),
+            Term::var("f")
+        );
+        assert_eq!(
+            bruijn_reduce_to_beta_eta_normal(term.clone(), strategy),
+            Term::var("f")
+        );
+    }
+}
+
+#[test]
+fn beta_eta_equality() {
+    let f = Term::var("f");
+    let eta = Term::abs("x", Term::app(f.clone(), Term::var("x")));
+    assert_eq!(eta.beta_eta_eq(&f, 100), Some(true));
+    let id_x = Term::abs("x", Term::var("x"));
+    let id_y = Term::abs("y", Term::var("y"));
+    assert_eq!(id_x.beta_eta_eq(&id_y, 100), Some(true));
+    let k = Term::abs("x", Term::abs("y", Term::var("x")));
+    assert_eq!(id_x.beta_eta_eq(&k, 100), Some(false));
+    assert_eq!(omega().beta_eta_eq(&f, 100), None);
+}
@@ -0,0 +1,118 @@
+use lambubu::ReductionGraph;
+use lambubu::Strategy;
+use lambubu::Term;
+
+fn id(name: &str) -> Term {
+    Term::abs(name, Term::var(name))
+}
+
+fn omega() -> Term {
+    let w = Term::abs("x", Term::app(Term::var("x"), Term::var("x")));
+    Term::app(w.clone(), w)
+}
+
+// (λx.(x x)) ((λy.y) z)
+fn duplicating() -> Term {
+    Term::app(
+        Term::abs("x", Term::app(Term::var("x"), Term::var("x"))),
+        Term::app(id("y"), Term::var("z")),
+    )
+}
+
+#[test]
+fn explore_normal_form() {
+    let graph = ReductionGraph::explore(id("x"), 10, 100);
+    assert_eq!(graph.nodes().len(), 1);
+    assert!(graph.edges().is_empty());
+    assert!(graph.root().normal_form);
+    assert!(graph.is_complete());
+}
+
+// (λx.(x x)) ((λy.y) z) has paths through ((λy.y) z) ((λy.y) z) and (λx.(x x)) z
+#[test]
+fn explore_diamond() {
+    let graph = ReductionGraph::explore(duplicating(), 10, 100);
+    assert!(graph.is_complete());
+    let normal_forms: Vec<_> = graph.normal_forms().collect();
+    assert_eq!(normal_forms.len(), 1);
+    assert_eq!(
+        normal_forms[0].term,
+        Term::app(Term::var("z"), Term::var("z"))
+    );
+    // root, the two children, (z ((λy.y) z)), (((λy.y) z) z) and (z z)
+    assert_eq!(graph.nodes().len(), 6);
+    assert_eq!(graph.edges().len(), 7);
+}
+
+// alpha-equivalent terms share a node, so Ω is a single node with a self loop
+#[test]
+fn explore_omega_loop() {
+    let graph = ReductionGraph::explore(omega(), 10, 100);
+    assert_eq!(graph.nodes().len(), 1);
+    assert_eq!(graph.edges().len(), 1);
+    assert_eq!((graph.edges()[0].from, graph.edges()[0].to), (0, 0));
+    assert!(graph.is_complete());
+    assert_eq!(graph.normal_forms().count(), 0);
+}
+
+#[test]
+fn explore_bounded_depth() {
+    let graph = ReductionGraph::explore(duplicating(), 1, 100);
+    assert!(!graph.is_complete());
+    assert_eq!(graph.nodes().len(), 3);
+    assert!(graph.nodes().iter().all(|node| node.depth <= 1));
+}
+
+#[test]
+fn explore_bounded_nodes() {
+    let graph = ReductionGraph::explore(duplicating(), 10, 2);
+    assert!(!graph.is_complete());
+    assert_eq!(graph.nodes().len(), 2);
+}
+
+// normal order and call by value take different first steps
+#[test]
+fn explore_strategy_paths() {
+    let graph = ReductionGraph::explore(duplicating(), 10, 100);
+    let strategy_edges = |strategy| {
+        graph
+            .edges()
+            .iter()
+            .filter(|edge| edge.strategies.contains(&strategy))
+            .count()
+    };
+    assert_eq!(strategy_edges(Strategy::NormalOrder), 3);
+    assert_eq!(strategy_edges(Strategy::CallByValue), 2);
+    let normal_form = graph.normal_forms().next().unwrap();
+    assert!(normal_form.strategies.contains(&Strategy::NormalOrder));
+    assert!(normal_form.strategies.contains(&Strategy::CallByValue));
+}
+
+#[test]
+fn explore_dot() {
+    let graph = ReductionGraph::explore(Term::app(id("x"), Term::var("a")), 10, 100);
+    assert_eq!(
+        graph.to_dot(),
+        concat!(
+            "digraph reductions {\n",
+            "    node [shape=box];\n",
+            "    n0 [label=\"(λx.x a)\\nCallByName, NormalOrder, CallByValue, ApplicativeOrder, Head, NormalOrderEta, ApplicativeOrderEta\"];\n",
+            "    n1 [label=\"a\\nCallByName, NormalOrder, CallByValue, ApplicativeOrder, Head, NormalOrderEta, ApplicativeOrderEta\", peripheries=2];\n",
+            "    n0 -> n1 [label=\"ε\", style=bold];\n",
+            "}\n",
+        )
+    );
+}
+
+#[test]
+fn explore_json() {
+    let graph = ReductionGraph::explore(Term::app(id("x"), Term::var("a")), 0, 100);
+    assert_eq!(
+        graph.to_json(),
+        concat!(
+            r#"{"nodes":[{"id":0,"term":"(λx.x a)","depth":0,"normal_form":false,"strategies":["#,
+            r#""CallByName","NormalOrder","CallByValue","ApplicativeOrder","Head","NormalOrderEta","ApplicativeOrderEta"]}],"#,
+            r#""edges":[],"complete":false}"#
+        )
+    );
+}
@@ -0,0 +1,128 @@
+use lambubu::Term;
+use lambubu::graph::{GraphError, GraphMachine};
+
+fn normalize(t: &Term) -> Term {
+    let mut machine = GraphMachine::new(t);
+    machine.normalize().unwrap();
+    machine.to_term()
+}
+
+fn reduce_to_normal(mut t: Term) -> Term {
+    while !t.is_normal_form() {
+        t = t.reduce_step_normal_order();
+    }
+    t
+}
+
+fn church(n: usize) -> Term {
+    let mut body = Term::var("x");
+    for _ in 0..n {
+        body = Term::app(Term::var("f"), body);
+    }
+    Term::abs("f", Term::abs("x", body))
+}
+
+fn omega() -> Term {
+    let w = Term::abs("x", Term::app(Term::var("x"), Term::var("x")));
+    Term::app(w.clone(), w)
+}
+
+// (λx.x) a ↠ a
+#[test]
+fn graph_basic_beta() {
+    let term = Term::app(Term::abs("x", Term::var("x")), Term::var("a"));
+    assert_eq!(normalize(&term), Term::var("a"));
+}
+
+// (λx.λy.x) a b ↠ a
+#[test]
+fn graph_k_combinator() {
+    let term = Term::app(
+        Term::app(
+            Term::abs("x", Term::abs("y", Term::var("x"))),
+            Term::var("a"),
+        ),
+        Term::var("b"),
+    );
+    assert_eq!(normalize(&term), Term::var("a"));
+}
+
+// (λx.λy.x) y ↠ λy'.y
+#[test]
+fn graph_capture_avoiding() {
+    let term = Term::app(
+        Term::abs("x", Term::abs("y", Term::var("x"))),
+        Term::var("y"),
+    );
+    assert_eq!(normalize(&term), Term::abs("y'", Term::var("y")));
+}
+
+// λz.(λx.x) a is already in WHNF
+#[test]
+fn graph_whnf_stops_at_lambda() {
+    let term = Term::abs(
+        "z",
+        Term::app(Term::abs("x", Term::var("x")), Term::var("a")),
+    );
+    let mut machine = GraphMachine::new(&term);
+    machine.whnf().unwrap();
+    assert_eq!(machine.steps(), 0);
+    assert_eq!(machine.to_term(), term);
+    machine.normalize().unwrap();
+    assert_eq!(machine.to_term(), Term::abs("z", Term::var("a")));
+}
+
+// (λx.λy.y) Ω ↠ λy.y, the argument is never forced
+#[test]
+fn graph_lazy_argument() {
+    let term = Term::app(Term::abs("x", Term::abs("y", Term::var("y"))), omega());
+    assert_eq!(normalize(&term), Term::abs("y", Term::var("y")));
+}
+
+// (λx.x x) ((λy.y) (λz.z)) takes 4 normal-order steps; the graph machine needs 3
+// because the shared argument is reduced once
+#[test]
+fn graph_shares_arguments() {
+    let id = Term::abs("z", Term::var("z"));
+    let arg = Term::app(Term::abs("y", Term::var("y")), id.clone());
+    let term = Term::app(
+        Term::abs("x", Term::app(Term::var("x"), Term::var("x"))),
+        arg,
+    );
+    let mut machine = GraphMachine::new(&term);
+    machine.normalize().unwrap();
+    assert_eq!(machine.to_term(), id);
+    assert_eq!(machine.steps(), 3);
+}
+
+// 2 2 ↠ λx.λx'.x (x (x (x x')))
+#[test]
+fn graph_church_exponent() {
+    let term = Term::app(church(2), church(2));
+    assert_eq!(normalize(&term), reduce_to_normal(term));
+}
+
+// 3 2 agrees with normal order
+#[test]
+fn graph_agrees_with_normal_order() {
+    let term = Term::app(church(3), church(2));
+    assert_eq!(normalize(&term), reduce_to_normal(term));
+}
+
+#[test]
+fn graph_stats() {
+    let term = Term::app(Term::abs("x", Term::var("x")), Term::var("a"));
+    let mut machine = GraphMachine::new(&term);
+    assert_eq!(machine.stats().live, 4);
+    machine.normalize().unwrap();
+    let stats = machine.stats();
+    assert_eq!(stats.steps, 1);
+    assert_eq!(stats.live, 1);
+    assert!(stats.allocated >= stats.live);
+}
+
+#[test]
+fn graph_max_steps() {
+    let mut machine = GraphMachine::new(&omega()).with_max_steps(100);
+    assert_eq!(machine.whnf(), Err(GraphError::MaxStepsExceeded(100)));
+}
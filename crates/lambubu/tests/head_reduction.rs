@@ -0,0 +1,124 @@
+use lambubu::BruijnLevelsTerm;
+use lambubu::Term;
+use lambubu::bohm::BohmTree;
+
+fn omega() -> Term {
+    let w = Term::abs("x", Term::app(Term::var("x"), Term::var("x")));
+    Term::app(w.clone(), w)
+}
+
+fn y_combinator() -> Term {
+    let inner = Term::abs(
+        "x",
+        Term::app(Term::var("f"), Term::app(Term::var("x"), Term::var("x"))),
+    );
+    Term::abs("f", Term::app(inner.clone(), inner))
+}
+
+fn bruijn_head_step(t: Term) -> Term {
+    let b = BruijnLevelsTerm::from_open_term(t);
+    Term::from((*b.reduce_step_head()).clone())
+}
+
+fn bruijn_weak_head_step(t: Term) -> Term {
+    let b = BruijnLevelsTerm::from_open_term(t);
+    Term::from((*b.reduce_step_weak_head()).clone())
+}
+
+// λz.Ω is in WHNF but not in HNF
+#[test]
+fn whnf_but_not_hnf() {
+    let term = Term::abs("z", omega());
+    assert!(term.is_whnf());
+    assert!(!term.is_hnf());
+    let b = BruijnLevelsTerm::from_open_term(term);
+    assert!(b.is_whnf());
+    assert!(!b.is_hnf());
+}
+
+// x Ω is in HNF but not in normal form
+#[test]
+fn hnf_but_not_normal() {
+    let term = Term::app(Term::var("x"), omega());
+    assert!(term.is_hnf());
+    assert!(!term.is_normal_form());
+    assert!(BruijnLevelsTerm::from_open_term(term).is_hnf());
+}
+
+// (λx.x) a is not in WHNF
+#[test]
+fn redex_is_not_whnf() {
+    let term = Term::app(Term::abs("x", Term::var("x")), Term::var("a"));
+    assert!(!term.is_whnf());
+    assert!(!term.is_hnf());
+}
+
+// λz.(λx.x) a →_h λz.a
+#[test]
+fn head_reduces_under_lambda() {
+    let term = Term::abs(
+        "z",
+        Term::app(Term::abs("x", Term::var("x")), Term::var("a")),
+    );
+    assert_eq!(
+        term.clone().reduce_step_head(),
+        Term::abs("z", Term::var("a"))
+    );
+    assert_eq!(bruijn_head_step(term), Term::abs("z", Term::var("a")));
+}
+
+// λz.(λx.x) a →_wh λz.(λx.x) a
+#[test]
+fn weak_head_stops_at_lambda() {
+    let term = Term::abs(
+        "z",
+        Term::app(Term::abs("x", Term::var("x")), Term::var("a")),
+    );
+    assert_eq!(term.clone().reduce_step_weak_head(), term);
+    assert_eq!(bruijn_weak_head_step(term.clone()), term);
+}
+
+// x ((λy.y) z) →_h x ((λy.y) z), arguments of a head variable are left alone
+#[test]
+fn head_does_not_reduce_arguments() {
+    let term = Term::app(
+        Term::var("x"),
+        Term::app(Term::abs("y", Term::var("y")), Term::var("z")),
+    );
+    assert_eq!(term.clone().reduce_step_head(), term);
+    assert_eq!(bruijn_head_step(term.clone()), term);
+}
+
+// ((λx.λy.x) a) b →_wh (λy.a) b
+#[test]
+fn weak_head_contracts_head_redex() {
+    let term = Term::app(
+        Term::app(
+            Term::abs("x", Term::abs("y", Term::var("x"))),
+            Term::var("a"),
+        ),
+        Term::var("b"),
+    );
+    let expected = Term::app(Term::abs("y", Term::var("a")), Term::var("b"));
+    assert_eq!(term.clone().reduce_step_weak_head(), expected);
+    assert_eq!(bruijn_weak_head_step(term), expected);
+}
+
+#[test]
+fn bohm_tree_of_omega_is_bottom() {
+    assert_eq!(BohmTree::new(omega(), 5, 100), BohmTree::Bottom);
+}
+
+// BT(λx.x Ω) = λx.(x ⊥)
+#[test]
+fn bohm_tree_with_unsolvable_argument() {
+    let term = Term::abs("x", Term::app(Term::var("x"), omega()));
+    assert_eq!(BohmTree::new(term, 5, 100).to_string(), "λx.(x ⊥)");
+}
+
+// BT(Y g) = g (g (g …))
+#[test]
+fn bohm_tree_prefix_of_fixed_point() {
+    let term = Term::app(y_combinator(), Term::var("g"));
+    assert_eq!(BohmTree::new(term, 3, 100).to_string(), "(g (g (g …)))");
+}
@@ -0,0 +1,89 @@
+use lambubu::BruijnLevelsTerm;
+use lambubu::Term;
+use std::rc::Rc;
+
+fn var(lvl: u16, name: &str) -> Rc<BruijnLevelsTerm> {
+    Rc::new(BruijnLevelsTerm::Var(lvl, Rc::from(name)))
+}
+
+fn abs(lvl: u16, body: Rc<BruijnLevelsTerm>, name: &str) -> Rc<BruijnLevelsTerm> {
+    Rc::new(BruijnLevelsTerm::Abs(lvl, body, Rc::from(name)))
+}
+
+fn app(t1: Rc<BruijnLevelsTerm>, t2: Rc<BruijnLevelsTerm>) -> Rc<BruijnLevelsTerm> {
+    Rc::new(BruijnLevelsTerm::Apply(t1, t2))
+}
+
+fn to_term(term: Rc<BruijnLevelsTerm>) -> Term {
+    Term::from((*term).clone())
+}
+
+// (λy.(x y))[x := λz.z], the body of λx.λy.(x y) at level 0:
+// y moves down to level 0 and the argument's binder lands at level 1
+#[test]
+fn levels_substitute_shifts_binders_of_body_and_argument() {
+    let body = abs(1, app(var(0, "x"), var(1, "y")), "y");
+    let result = body.substitute(0, abs(0, var(0, "z"), "z"));
+    assert_eq!(
+        result,
+        abs(0, app(abs(1, var(1, "z"), "z"), var(0, "y")), "y")
+    );
+}
+
+// (x x)[x := f] with f free at level 0, inside λx at level 1
+#[test]
+fn levels_substitute_free_argument_is_not_shifted() {
+    let body = app(var(1, "x"), var(1, "x"));
+    let result = body.substitute(1, var(0, "f"));
+    assert_eq!(result, app(var(0, "f"), var(0, "f")));
+}
+
+// variables bound above the substituted one keep their level
+#[test]
+fn levels_substitute_keeps_outer_levels() {
+    let body = abs(2, app(var(0, "a"), var(2, "y")), "y");
+    let result = body.substitute(1, var(0, "a"));
+    assert_eq!(result, abs(1, app(var(0, "a"), var(1, "y")), "y"));
+}
+
+// λy.λy.y₀ reads back as λy.λy'.y instead of capturing the inner binder
+#[test]
+fn levels_to_term_primes_capturing_binder() {
+    let term = abs(0, abs(1, var(0, "y"), "y"), "y");
+    assert_eq!(
+        to_term(term),
+        Term::abs("y", Term::abs("y'", Term::var("y")))
+    );
+}
+
+// a binder named like a free variable of its body is primed
+#[test]
+fn levels_to_term_avoids_free_variable() {
+    let term = BruijnLevelsTerm::from_open_term(Term::var("y"));
+    let term = abs(1, app(term, var(1, "y")), "y");
+    assert_eq!(
+        to_term(term),
+        Term::abs("y'", Term::app(Term::var("y"), Term::var("y'")))
+    );
+}
+
+// binders that do not capture keep their names
+#[test]
+fn levels_to_term_keeps_names_without_capture() {
+    let term = abs(0, abs(1, var(1, "y"), "y"), "y");
+    assert_eq!(
+        to_term(term),
+        Term::abs("y", Term::abs("y", Term::var("y")))
+    );
+}
+
+// (λx.λy.x) y →_no λy'.y
+#[test]
+fn levels_reduction_avoids_capture() {
+    let term = Term::app(
+        Term::abs("x", Term::abs("y", Term::var("x"))),
+        Term::var("y"),
+    );
+    let reduced = BruijnLevelsTerm::from_open_term(term).reduce_step_normal_order();
+    assert_eq!(to_term(reduced), Term::abs("y'", Term::var("y")));
+}
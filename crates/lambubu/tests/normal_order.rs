@@ -163,3 +163,20 @@ fn bruijn_no_argument_reduction() {
         Term::app(Term::var("x"), Term::var("z"))
     );
 }
+
+// (λf.λx.f (f x)) (λf.λx.f (f x)) →*_no λx.λx'.x (x (x (x x')))
+#[test]
+fn bruijn_no_church_exponent() {
+    let church_2 = Term::abs(
+        "f",
+        Term::abs(
+            "x",
+            Term::app(Term::var("f"), Term::app(Term::var("f"), Term::var("x"))),
+        ),
+    );
+    let term = Term::app(church_2.clone(), church_2);
+    assert_eq!(
+        bruijn_reduce_to_normal(term.clone()),
+        reduce_to_normal(term)
+    );
+}
@@ -0,0 +1,118 @@
+use lambubu::Strategy;
+use lambubu::Term;
+use lambubu::path::{Direction, Path};
+
+fn id(name: &str) -> Term {
+    Term::abs(name, Term::var(name))
+}
+
+fn normalize(mut t: Term) -> Term {
+    while !t.is_normal_form() {
+        t = t.reduce_step_normal_order();
+    }
+    t
+}
+
+// (λx.(x x)) ((λy.y) z)
+fn duplicating() -> Term {
+    Term::app(
+        Term::abs("x", Term::app(Term::var("x"), Term::var("x"))),
+        Term::app(id("y"), Term::var("z")),
+    )
+}
+
+#[test]
+fn redexes_of_normal_form() {
+    let term = Term::abs("x", Term::app(Term::var("x"), id("y")));
+    assert!(term.redexes().is_empty());
+}
+
+// redexes are listed leftmost-outermost first
+#[test]
+fn redexes_order() {
+    let term = duplicating();
+    let redexes = term.redexes();
+    let paths: Vec<Path> = redexes.iter().map(|(path, _)| path.clone()).collect();
+    assert_eq!(
+        paths,
+        vec![Path::root(), Path::from(vec![Direction::Argument])]
+    );
+    assert_eq!(redexes[1].1, &Term::app(id("y"), Term::var("z")));
+}
+
+// λf.((λx.x) (f ((λy.y) a)))
+#[test]
+fn redexes_under_lambda() {
+    let term = Term::abs(
+        "f",
+        Term::app(
+            id("x"),
+            Term::app(Term::var("f"), Term::app(id("y"), Term::var("a"))),
+        ),
+    );
+    let paths: Vec<String> = term
+        .redexes()
+        .iter()
+        .map(|(path, _)| path.to_string())
+        .collect();
+    assert_eq!(paths, vec!["body", "body/argument/argument"]);
+}
+
+#[test]
+fn redexes_paths_point_at_redexes() {
+    let term = duplicating();
+    for (path, redex) in term.redexes() {
+        assert_eq!(term.subterm_at(&path), Some(redex));
+        assert!(redex.is_redex());
+    }
+}
+
+// contracting the inner redex: (λx.(x x)) z
+#[test]
+fn contract_at_inner() {
+    let term = duplicating();
+    let contracted = term.contract_at(&Path::from(vec![Direction::Argument]));
+    assert_eq!(
+        contracted,
+        Some(Term::app(
+            Term::abs("x", Term::app(Term::var("x"), Term::var("x"))),
+            Term::var("z"),
+        ))
+    );
+}
+
+#[test]
+fn contract_at_not_a_redex() {
+    let term = duplicating();
+    assert_eq!(
+        term.clone()
+            .contract_at(&Path::from(vec![Direction::Function])),
+        None
+    );
+    assert_eq!(term.contract_at(&Path::from(vec![Direction::Body])), None);
+}
+
+// the root contraction is the normal order step
+#[test]
+fn contract_at_root_is_normal_order_step() {
+    let term = duplicating();
+    let (path, _) = term.next_redex(Strategy::NormalOrder).unwrap();
+    assert_eq!(
+        term.clone().contract_at(&path),
+        Some(term.reduce_step_normal_order())
+    );
+}
+
+// every choice of redex leads to the same normal form
+#[test]
+fn contract_at_confluence() {
+    let term = duplicating();
+    let normal_forms: Vec<Term> = term
+        .redexes()
+        .iter()
+        .map(|(path, _)| normalize(term.clone().contract_at(path).unwrap()))
+        .collect();
+    assert_eq!(normal_forms.len(), 2);
+    assert!(normal_forms[0].alpha_eq(&normal_forms[1]));
+    assert!(normal_forms[0].alpha_eq(&Term::app(Term::var("z"), Term::var("z"))));
+}
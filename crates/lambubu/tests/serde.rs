@@ -0,0 +1,114 @@
+#![cfg(feature = "serde")]
+
+use lambubu::BruijnLevelsTerm;
+use lambubu::Term;
+use lambubu::serialize::{SharedLevelsTerm, SharedTerm};
+use std::rc::Rc;
+
+// λx.(x y)
+fn sample() -> Term {
+    Term::abs("x", Term::app(Term::var("x"), Term::var("y")))
+}
+
+#[test]
+fn serde_term_json_schema() {
+    assert_eq!(
+        serde_json::to_string(&sample()).unwrap(),
+        r#"{"abs":{"name":"x","body":{"apply":[{"var":"x"},{"var":"y"}]}}}"#
+    );
+}
+
+#[test]
+fn serde_term_json_round_trip() {
+    let json = serde_json::to_string(&sample()).unwrap();
+    assert_eq!(serde_json::from_str::<Term>(&json).unwrap(), sample());
+}
+
+#[test]
+fn serde_levels_json_schema() {
+    let term = BruijnLevelsTerm::from_open_term(sample());
+    assert_eq!(
+        serde_json::to_string(term.as_ref()).unwrap(),
+        concat!(
+            r#"{"abs":{"level":1,"name":"x","body":{"apply":["#,
+            r#"{"var":{"level":1,"name":"x"}},{"var":{"level":0,"name":"y"}}]}}}"#
+        )
+    );
+}
+
+#[test]
+fn serde_term_cbor_round_trip() {
+    let term = BruijnLevelsTerm::from_open_term(sample());
+    let mut bytes = Vec::new();
+    ciborium::into_writer(term.as_ref(), &mut bytes).unwrap();
+    let decoded: BruijnLevelsTerm = ciborium::from_reader(bytes.as_slice()).unwrap();
+    assert_eq!(&decoded, term.as_ref());
+
+    let mut bytes = Vec::new();
+    ciborium::into_writer(&sample(), &mut bytes).unwrap();
+    let decoded: Term = ciborium::from_reader(bytes.as_slice()).unwrap();
+    assert_eq!(decoded, sample());
+}
+
+// abstraction levels must match their depth
+#[test]
+fn serde_levels_rejects_bad_abstraction_level() {
+    let json = r#"{"abs":{"level":0,"name":"x","body":{"abs":{"level":2,"name":"y","body":{"var":{"level":0,"name":"x"}}}}}}"#;
+    let err = serde_json::from_str::<BruijnLevelsTerm>(json).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("abstraction `y` has level 2 instead of 1")
+    );
+}
+
+#[test]
+fn serde_levels_rejects_unbound_variable() {
+    let json = r#"{"abs":{"level":0,"name":"x","body":{"var":{"level":3,"name":"z"}}}}"#;
+    let err = serde_json::from_str::<BruijnLevelsTerm>(json).unwrap_err();
+    assert!(err.to_string().contains("variable `z` has unbound level 3"));
+}
+
+// an abstraction at the last level leaves none for a nested one, which is an
+// error rather than an overflow
+#[test]
+fn serde_levels_rejects_level_past_u16() {
+    let json = r#"{"abs":{"level":65535,"name":"x","body":{"abs":{"level":0,"name":"y","body":{"var":{"level":0,"name":"y"}}}}}}"#;
+    let err = serde_json::from_str::<BruijnLevelsTerm>(json).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("abstraction `x` leaves no level for its body")
+    );
+}
+
+// a shared argument is written once and shared again after reading
+#[test]
+fn serde_shared_term_preserves_sharing() {
+    let shared = Rc::new(Term::abs("z", Term::var("z")));
+    let term = Rc::new(Term::Apply(shared.clone(), shared));
+    let json = serde_json::to_string(&SharedTerm(term.clone())).unwrap();
+    assert_eq!(
+        json,
+        r#"{"nodes":[{"var":"z"},{"abs":{"name":"z","body":0}},{"apply":[1,1]}],"root":2}"#
+    );
+    let SharedTerm(decoded) = serde_json::from_str(&json).unwrap();
+    assert_eq!(decoded, term);
+    let Term::Apply(t1, t2) = decoded.as_ref() else {
+        panic!()
+    };
+    assert!(Rc::ptr_eq(t1, t2));
+}
+
+#[test]
+fn serde_shared_levels_round_trip() {
+    let term = BruijnLevelsTerm::from_open_term(sample());
+    let json = serde_json::to_string(&SharedLevelsTerm(term.clone())).unwrap();
+    let SharedLevelsTerm(decoded) = serde_json::from_str(&json).unwrap();
+    assert_eq!(decoded, term);
+}
+
+#[test]
+fn serde_shared_rejects_forward_reference() {
+    let json = r#"{"nodes":[{"apply":[1,1]},{"var":"z"}],"root":0}"#;
+    let err = serde_json::from_str::<SharedTerm>(json).unwrap_err();
+    assert!(err.to_string().contains("node 1 is not defined before use"));
+}
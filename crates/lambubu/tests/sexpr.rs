@@ -0,0 +1,149 @@
+use lambubu::RegistryEnvironment;
+use lambubu::Term;
+use lambubu::compile::{CompilationError, compile_file, compile_term};
+use lambubu::sexpr::{SExprDefinition, SExprError, compile_sexpr_file, compile_sexpr_term};
+
+fn parse(input: &str) -> Term {
+    compile_sexpr_term(input, &RegistryEnvironment::new()).unwrap()
+}
+
+// λf.λx.(f (f x))
+fn two() -> Term {
+    Term::abs(
+        "f",
+        Term::abs(
+            "x",
+            Term::app(Term::var("f"), Term::app(Term::var("f"), Term::var("x"))),
+        ),
+    )
+}
+
+#[test]
+fn sexpr_variable() {
+    assert_eq!(parse("x'"), Term::var("x'"));
+}
+
+#[test]
+fn sexpr_lambda_forms() {
+    let id = Term::abs("x", Term::var("x"));
+    assert_eq!(parse("(lambda (x) x)"), id);
+    assert_eq!(parse("(lambda x x)"), id);
+    assert_eq!(parse("(λ (x) x)"), id);
+    assert_eq!(parse("(lambda (f x) (f (f x)))"), two());
+}
+
+#[test]
+fn sexpr_application_is_left_associative() {
+    assert_eq!(
+        parse("(f a b)"),
+        Term::app(Term::app(Term::var("f"), Term::var("a")), Term::var("b"))
+    );
+}
+
+#[test]
+fn sexpr_keywords_are_not_variables() {
+    assert_eq!(parse("lambdas"), Term::var("lambdas"));
+    assert_eq!(
+        parse("(define' x)"),
+        Term::app(Term::var("define'"), Term::var("x"))
+    );
+    assert_eq!(
+        parse("(defined x)"),
+        Term::app(Term::var("defined"), Term::var("x"))
+    );
+}
+
+#[test]
+fn sexpr_printer() {
+    assert_eq!(two().to_sexpr().unwrap(), "(lambda (f x) (f (f x)))");
+    assert_eq!(
+        parse("((lambda (x) x) y z)").to_sexpr().unwrap(),
+        "((lambda (x) x) y z)"
+    );
+    assert_eq!(parse("(f (g a) b)").to_sexpr().unwrap(), "(f (g a) b)");
+    assert_eq!(
+        SExprDefinition("TWO", &two()).to_string(),
+        "(define TWO (lambda (f x) (f (f x))))"
+    );
+}
+
+#[test]
+fn sexpr_round_trip() {
+    for input in [
+        "x",
+        "(lambda (x) (lambda (y) (x y)))",
+        "((lambda (x) (x x)) (lambda (x) (x x)))",
+        "(a (lambda (b) b) c)",
+    ] {
+        let term = parse(input);
+        assert_eq!(parse(&term.to_sexpr().unwrap()), term);
+    }
+}
+
+#[test]
+fn sexpr_round_trip_renames_keyword_binders() {
+    let env = RegistryEnvironment::new();
+    for input in [
+        "λlambda.lambda",
+        "λdefine.λlambda.(define lambda)",
+        "λlambda.λlambda'.(lambda lambda')",
+        "λlambda.(lambda λlambda.lambda)",
+    ] {
+        let term = compile_term(input, &env).unwrap();
+        let printed = term.to_sexpr().unwrap();
+        assert!(
+            parse(&printed).alpha_eq(&term),
+            "{input} printed as {printed}"
+        );
+    }
+    assert_eq!(
+        Term::abs("lambda", Term::var("lambda")).to_sexpr().unwrap(),
+        "(lambda (lambda') lambda')"
+    );
+}
+
+#[test]
+fn sexpr_rejects_free_keyword_variables() {
+    let term = Term::app(Term::var("define"), Term::var("x"));
+    assert_eq!(
+        term.to_sexpr(),
+        Err(SExprError::KeywordVariable("define".to_owned()))
+    );
+}
+
+#[test]
+fn sexpr_agrees_with_grammar() {
+    let env = RegistryEnvironment::new();
+    assert_eq!(
+        parse("(lambda (x y) (y (x y) z))"),
+        compile_term("λx.λy.(y (x y) z)", &env).unwrap()
+    );
+}
+
+#[test]
+fn sexpr_file_with_definitions() {
+    let source = "
+        ; Church numerals
+        (define TWO (lambda (f x) (f (f x))))
+        (define ID (lambda x x))
+        (ID TWO)
+        TWO
+    ";
+    let terms = compile_sexpr_file(source, &mut RegistryEnvironment::new()).unwrap();
+    let expected = compile_file(
+        "TWO :: λf.λx.(f (f x)) ID :: λx.x (ID TWO) TWO",
+        &mut RegistryEnvironment::new(),
+    )
+    .unwrap();
+    assert_eq!(terms, expected);
+    assert_eq!(terms[1], two());
+}
+
+#[test]
+fn sexpr_unknown_macro() {
+    let result = compile_sexpr_file("(f UNKNOWN)", &mut RegistryEnvironment::new());
+    assert!(matches!(
+        result,
+        Err(CompilationError::UnknownMacros { macro_name, .. }) if macro_name == "UNKNOWN"
+    ));
+}
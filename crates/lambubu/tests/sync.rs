@@ -0,0 +1,157 @@
+use lambubu::ReductionGraph;
+use lambubu::Strategy;
+use lambubu::Term;
+use lambubu::Trace;
+use lambubu::sync;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::thread;
+
+fn church(n: usize) -> Term {
+    let mut body = Term::var("x");
+    for _ in 0..n {
+        body = Term::app(Term::var("f"), body);
+    }
+    Term::abs("f", Term::abs("x", body))
+}
+
+fn reduce_to_normal(mut t: Term) -> Term {
+    while !t.is_normal_form() {
+        t = t.reduce_step_normal_order();
+    }
+    t
+}
+
+fn sync_reduce_to_normal(mut t: sync::Term) -> sync::Term {
+    while !t.is_normal_form() {
+        t = t.reduce_step_normal_order();
+    }
+    t
+}
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+#[test]
+fn sync_terms_are_send_and_sync() {
+    assert_send_sync::<sync::Term>();
+    assert_send_sync::<sync::BruijnLevelsTerm>();
+}
+
+#[test]
+fn sync_round_trip() {
+    let t = Term::app(church(2), Term::abs("y", Term::var("y")));
+    let s = sync::Term::from(&t);
+    assert_eq!(Term::from(&s), t);
+    assert_eq!(s.to_string(), t.to_string());
+}
+
+#[test]
+fn sync_conversion_preserves_sharing() {
+    let shared = Rc::new(church(2));
+    let t = Rc::new(Term::Apply(shared.clone(), shared));
+    let s = sync::Term::from_rc(&t);
+    let sync::Term::Apply(a, b) = s.as_ref() else {
+        unreachable!()
+    };
+    assert!(Arc::ptr_eq(a, b));
+    let back = s.to_rc();
+    let Term::Apply(a, b) = back.as_ref() else {
+        unreachable!()
+    };
+    assert!(Rc::ptr_eq(a, b));
+}
+
+#[test]
+fn sync_levels_round_trip() {
+    let t = Term::app(church(2), Term::var("a"));
+    let b = lambubu::BruijnLevelsTerm::from_open_term(t.clone());
+    let s = sync::BruijnLevelsTerm::from_rc(&b);
+    assert_eq!(s.to_rc(), b);
+    assert_eq!(sync::Term::from((*s).clone()).to_string(), t.to_string());
+}
+
+#[test]
+fn sync_strategies_agree_with_rc() {
+    let term = Term::app(
+        Term::abs("x", Term::app(Term::var("x"), Term::var("x"))),
+        Term::app(Term::abs("y", Term::var("y")), Term::var("a")),
+    );
+    for strategy in Strategy::ALL {
+        let mut t = term.clone();
+        let mut s = sync::Term::from(&term);
+        while !t.is_terminal(strategy) {
+            assert!(!s.is_terminal(strategy));
+            t = t.reduce_step(strategy);
+            s = s.reduce_step(strategy);
+            assert_eq!(Term::from(&s), t);
+        }
+        assert!(s.is_terminal(strategy));
+    }
+}
+
+#[test]
+fn sync_levels_normal_order() {
+    let term = Term::app(church(2), church(2));
+    let mut b = sync::BruijnLevelsTerm::from_open_term(sync::Term::from(&term));
+    while !b.is_normal_form() {
+        b = b.reduce_step_normal_order();
+    }
+    assert_eq!(
+        Term::from(&sync::Term::from((*b).clone())),
+        reduce_to_normal(term)
+    );
+}
+
+#[test]
+fn sync_parallel_evaluation() {
+    let terms: Vec<sync::Term> = (1..5)
+        .map(|n| sync::Term::from(&Term::app(church(n), church(2))))
+        .collect();
+    let results: Vec<sync::Term> = thread::scope(|scope| {
+        let handles: Vec<_> = terms
+            .iter()
+            .cloned()
+            .map(|t| scope.spawn(move || sync_reduce_to_normal(t)))
+            .collect();
+        handles.into_iter().map(|h| h.join().unwrap()).collect()
+    });
+    for (n, result) in (1..5).zip(results) {
+        let expected = reduce_to_normal(Term::app(church(n), church(2)));
+        assert_eq!(Term::from(&result), expected);
+    }
+}
+
+#[test]
+fn sync_terms_share_the_rc_api() {
+    // λx.(x y) renamed y → z, then walked with the visitor helpers
+    let t = Term::abs("x", Term::app(Term::var("x"), Term::var("y")));
+    let s = sync::Term::from(&t);
+    assert_eq!(
+        sync::Term::from(&t.clone().rename_free("y", "z")),
+        s.clone().rename_free("y", "z")
+    );
+    assert_eq!(
+        s.free_vars()
+            .into_iter()
+            .map(|v| v.to_string())
+            .collect::<Vec<_>>(),
+        vec!["y"]
+    );
+    assert_eq!(s.size(), t.size());
+    assert_eq!(s.depth(), t.depth());
+}
+
+// traces and reduction graphs of sync terms match those of Rc terms
+#[test]
+fn sync_terms_trace_and_explore() {
+    let t = Term::app(church(2), church(2));
+    let s = sync::Term::from(&t);
+    let trace = Trace::new(t.clone(), Strategy::NormalOrder, 100);
+    let sync_trace = Trace::new(s.clone(), Strategy::NormalOrder, 100);
+    assert_eq!(sync_trace.to_string(), trace.to_string());
+    assert_eq!(sync_trace.result, sync::Term::from(&trace.result));
+
+    let graph = ReductionGraph::explore(t, 10, 100);
+    let sync_graph = ReductionGraph::explore(s, 10, 100);
+    assert_eq!(sync_graph.to_json(), graph.to_json());
+}
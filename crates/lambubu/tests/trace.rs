@@ -0,0 +1,139 @@
+use lambubu::Strategy;
+use lambubu::Term;
+use lambubu::Trace;
+use lambubu::path::{Direction, Path};
+use lambubu::trace::Rule;
+
+fn id(name: &str) -> Term {
+    Term::abs(name, Term::var(name))
+}
+
+fn church(n: usize) -> Term {
+    let mut body = Term::var("x");
+    for _ in 0..n {
+        body = Term::app(Term::var("f"), body);
+    }
+    Term::abs("f", Term::abs("x", body))
+}
+
+fn add() -> Term {
+    let body = Term::app(
+        Term::app(Term::var("m"), Term::var("f")),
+        Term::app(Term::app(Term::var("n"), Term::var("f")), Term::var("x")),
+    );
+    Term::abs("m", Term::abs("n", Term::abs("f", Term::abs("x", body))))
+}
+
+fn samples() -> Vec<Term> {
+    vec![
+        Term::app(Term::app(add(), church(2)), church(1)),
+        Term::app(Term::abs("z", Term::var("y")), Term::app(id("x"), id("w"))),
+        Term::abs(
+            "x",
+            Term::app(Term::app(id("y"), Term::var("f")), Term::var("x")),
+        ),
+        Term::app(Term::var("g"), Term::app(id("x"), Term::var("a"))),
+    ]
+}
+
+// every traced step agrees with reduce_step of the same strategy
+#[test]
+fn trace_matches_reduce_step() {
+    for term in samples() {
+        for strategy in Strategy::ALL {
+            let trace = Trace::new(term.clone(), strategy, 100);
+            let mut current = term.clone();
+            for step in &trace.steps {
+                assert_eq!(step.term, current);
+                current = current.reduce_step(strategy);
+            }
+            assert_eq!(trace.result, current);
+            assert!(trace.is_complete());
+            assert!(current.is_terminal(strategy));
+        }
+    }
+}
+
+// (g ((λx.x) a)): the redex is the argument of the application
+#[test]
+fn trace_records_path_redex_and_contractum() {
+    let term = Term::app(Term::var("g"), Term::app(id("x"), Term::var("a")));
+    let trace = Trace::new(term, Strategy::NormalOrder, 10);
+    assert_eq!(trace.steps.len(), 1);
+    let step = &trace.steps[0];
+    assert_eq!(step.path, Path::from(vec![Direction::Argument]));
+    assert_eq!(step.rule, Rule::Beta);
+    assert_eq!(step.redex, Term::app(id("x"), Term::var("a")));
+    assert_eq!(step.contractum, Term::var("a"));
+    assert_eq!(trace.result, Term::app(Term::var("g"), Term::var("a")));
+}
+
+// (λz.y) ((λx.x) (λw.w)): call by value contracts the argument first
+#[test]
+fn trace_call_by_value_rule() {
+    let term = Term::app(Term::abs("z", Term::var("y")), Term::app(id("x"), id("w")));
+    let trace = Trace::new(term, Strategy::CallByValue, 10);
+    let paths: Vec<Path> = trace.steps.iter().map(|step| step.path.clone()).collect();
+    assert_eq!(
+        paths,
+        vec![Path::from(vec![Direction::Argument]), Path::root()]
+    );
+    assert!(trace.steps.iter().all(|step| step.rule == Rule::BetaValue));
+    assert_eq!(trace.result, Term::var("y"));
+}
+
+// λx.(((λy.y) f) x): η-contraction happens once the body is β-normal
+#[test]
+fn trace_eta_rule() {
+    let term = Term::abs(
+        "x",
+        Term::app(Term::app(id("y"), Term::var("f")), Term::var("x")),
+    );
+    let trace = Trace::new(term, Strategy::ApplicativeOrderEta, 10);
+    let rules: Vec<Rule> = trace.steps.iter().map(|step| step.rule).collect();
+    assert_eq!(rules, vec![Rule::Beta, Rule::Eta]);
+    assert_eq!(trace.steps[1].path, Path::root());
+    assert_eq!(trace.result, Term::var("f"));
+}
+
+#[test]
+fn trace_highlights_redex() {
+    let term = Term::abs(
+        "x",
+        Term::app(Term::app(id("y"), Term::var("f")), Term::var("x")),
+    );
+    let trace = Trace::new(term, Strategy::NormalOrder, 10);
+    assert_eq!(
+        trace.steps[0].to_string(),
+        "β at body/function: λx.([(λy.y f)] x)"
+    );
+    assert_eq!(
+        trace.to_string(),
+        "0. β at body/function: λx.([(λy.y f)] x)\n1. λx.(f x)"
+    );
+}
+
+#[test]
+fn trace_json() {
+    let term = Term::app(id("x"), Term::var("\"a\""));
+    let trace = Trace::new(term, Strategy::CallByName, 10);
+    assert_eq!(
+        trace.to_json(),
+        concat!(
+            r#"{"strategy":"CallByName","initial":"(λx.x \"a\")","steps":["#,
+            r#"{"term":"(λx.x \"a\")","path":[],"rule":"beta","redex":"(λx.x \"a\")","contractum":"\"a\""}"#,
+            r#"],"result":"\"a\"","complete":true}"#
+        )
+    );
+}
+
+// Ω never finishes, the trace stops at the step budget
+#[test]
+fn trace_stops_at_max_steps() {
+    let w = Term::abs("x", Term::app(Term::var("x"), Term::var("x")));
+    let omega = Term::app(w.clone(), w);
+    let trace = Trace::new(omega.clone(), Strategy::NormalOrder, 5);
+    assert_eq!(trace.steps.len(), 5);
+    assert!(!trace.is_complete());
+    assert_eq!(trace.result, omega);
+}
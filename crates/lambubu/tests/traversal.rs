@@ -0,0 +1,156 @@
+use lambubu::BruijnLevelsTerm;
+use lambubu::Term;
+use lambubu::visit::{Visitor, VisitorMut, walk, walk_mut};
+use std::collections::BTreeSet;
+use std::rc::Rc;
+
+fn names(names: &[&str]) -> BTreeSet<Rc<str>> {
+    names.iter().map(|name| Rc::from(*name)).collect()
+}
+
+// λx.((x y) λy.(y z))
+fn sample() -> Term {
+    Term::abs(
+        "x",
+        Term::app(
+            Term::app(Term::var("x"), Term::var("y")),
+            Term::abs("y", Term::app(Term::var("y"), Term::var("z"))),
+        ),
+    )
+}
+
+#[test]
+fn free_and_bound_vars() {
+    let term = sample();
+    assert_eq!(term.free_vars(), names(&["y", "z"]));
+    assert_eq!(term.bound_vars(), names(&["x", "y"]));
+    assert!(term.is_free_variable("y"));
+    assert!(!term.is_free_variable("x"));
+}
+
+#[test]
+fn bruijn_free_and_bound_vars() {
+    let term = BruijnLevelsTerm::from_open_term(sample());
+    assert_eq!(term.free_vars(), names(&["y", "z"]));
+    assert_eq!(term.bound_vars(), names(&["x", "y"]));
+}
+
+#[test]
+fn subterms_pre_order() {
+    let term = Term::app(Term::abs("x", Term::var("x")), Term::var("a"));
+    let subterms: Vec<String> = term.subterms().map(|t| t.to_string()).collect();
+    assert_eq!(subterms, vec!["(λx.x a)", "λx.x", "x", "a"]);
+}
+
+#[test]
+fn size_and_depth() {
+    let term = sample();
+    assert_eq!(term.size(), 9);
+    assert_eq!(term.depth(), 5);
+    let b = BruijnLevelsTerm::from_open_term(term);
+    assert_eq!(b.size(), 9);
+    assert_eq!(b.depth(), 5);
+}
+
+// counting applications with fold
+#[test]
+fn fold_counts_applications() {
+    let term = sample();
+    let count = term.fold(&mut |_| 0, &mut |_, body| body, &mut |a, b| a + b + 1);
+    assert_eq!(count, 3);
+    let b = BruijnLevelsTerm::from_open_term(sample());
+    let count = b.fold(&mut |_, _| 0, &mut |_, body, _| body, &mut |a, b| a + b + 1);
+    assert_eq!(count, 3);
+}
+
+// fold rebuilds the printed form
+#[test]
+fn fold_prints() {
+    let term = sample();
+    let printed = term.fold(
+        &mut |v| v.to_string(),
+        &mut |v, body| format!("λ{v}.{body}"),
+        &mut |t1, t2| format!("({t1} {t2})"),
+    );
+    assert_eq!(printed, term.to_string());
+}
+
+// map replaces every free z by w
+#[test]
+fn map_renames() {
+    let term = Term::app(Term::var("z"), Term::abs("x", Term::var("z")));
+    let mapped = term.map(&mut |t| match t {
+        Term::Var(v) if &*v == "z" => Term::var("w"),
+        other => other,
+    });
+    assert_eq!(
+        mapped,
+        Term::app(Term::var("w"), Term::abs("x", Term::var("w")))
+    );
+    assert_eq!(
+        term.rename_free("z", "w"),
+        Term::app(Term::var("w"), Term::abs("x", Term::var("w")))
+    );
+}
+
+// (λy.x)[x ↦ y] renames the binder instead of capturing y
+#[test]
+fn rename_free_avoids_capture() {
+    let term = Term::abs("y", Term::var("x"));
+    assert_eq!(term.rename_free("x", "y"), Term::abs("y'", Term::var("y")));
+}
+
+struct VarCounter(usize);
+
+impl Visitor<Term> for VarCounter {
+    fn visit(&mut self, term: &Term) {
+        if let Term::Var(_) = term {
+            self.0 += 1;
+        }
+        walk(self, term);
+    }
+}
+
+impl Visitor<BruijnLevelsTerm> for VarCounter {
+    fn visit(&mut self, term: &BruijnLevelsTerm) {
+        if let BruijnLevelsTerm::Var(_, _) = term {
+            self.0 += 1;
+        }
+        walk(self, term);
+    }
+}
+
+#[test]
+fn visitor_counts_vars() {
+    let mut counter = VarCounter(0);
+    sample().accept(&mut counter);
+    assert_eq!(counter.0, 4);
+    let mut counter = VarCounter(0);
+    BruijnLevelsTerm::from_open_term(sample()).accept(&mut counter);
+    assert_eq!(counter.0, 4);
+}
+
+struct Uppercase;
+
+impl VisitorMut<Term> for Uppercase {
+    fn visit_mut(&mut self, term: &mut Term) {
+        match term {
+            Term::Var(v) => *v = v.to_uppercase().into(),
+            Term::Abs(v, _) => *v = v.to_uppercase().into(),
+            _ => {}
+        }
+        walk_mut(self, term);
+    }
+}
+
+// mutating a shared subterm leaves the other owner untouched
+#[test]
+fn visitor_mut_copies_on_write() {
+    let shared = Rc::new(Term::abs("x", Term::var("x")));
+    let original = Term::Apply(shared.clone(), Rc::new(Term::var("a")));
+    let mut term = original.clone();
+    term.accept_mut(&mut Uppercase);
+    assert_eq!(term.to_string(), "(λX.X A)");
+    assert_eq!(original.to_string(), "(λx.x a)");
+    assert_eq!(shared.to_string(), "λx.x");
+}
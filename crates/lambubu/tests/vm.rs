@@ -0,0 +1,184 @@
+use lambubu::BruijnLevelsTerm;
+use lambubu::Term;
+use lambubu::vm::{DEFAULT_MAX_STEPS, Instruction, Program, Vm, VmError};
+
+fn compile(t: &Term) -> Program {
+    Program::compile(&BruijnLevelsTerm::from_open_term(t.clone()))
+}
+
+fn normalize(t: &Term) -> Term {
+    Vm::new(&compile(t)).normalize_term().unwrap()
+}
+
+fn reduce_to_normal(mut t: Term) -> Term {
+    while !t.is_normal_form() {
+        t = t.reduce_step_normal_order();
+    }
+    t
+}
+
+fn church(n: usize) -> Term {
+    let mut body = Term::var("x");
+    for _ in 0..n {
+        body = Term::app(Term::var("f"), body);
+    }
+    Term::abs("f", Term::abs("x", body))
+}
+
+// λm.λn.λf.λx.((n f) ((m f) x))
+fn add() -> Term {
+    Term::abs(
+        "m",
+        Term::abs(
+            "n",
+            Term::abs(
+                "f",
+                Term::abs(
+                    "x",
+                    Term::app(
+                        Term::app(Term::var("n"), Term::var("f")),
+                        Term::app(Term::app(Term::var("m"), Term::var("f")), Term::var("x")),
+                    ),
+                ),
+            ),
+        ),
+    )
+}
+
+fn omega() -> Term {
+    let w = Term::abs("x", Term::app(Term::var("x"), Term::var("x")));
+    Term::app(w.clone(), w)
+}
+
+// (λx.x) a
+#[test]
+fn vm_compile_identity_application() {
+    let program = compile(&Term::app(Term::abs("x", Term::var("x")), Term::var("a")));
+    assert_eq!(
+        program.code(),
+        [
+            Instruction::Grab(0),
+            Instruction::Access(0),
+            Instruction::Apply,
+            Instruction::Access(0),
+            Instruction::Push,
+            Instruction::Closure(0),
+            Instruction::Return,
+        ]
+    );
+    assert_eq!(program.entry(), 3);
+}
+
+#[test]
+fn vm_disassemble() {
+    let program = compile(&Term::app(
+        Term::abs("x", Term::var("x")),
+        Term::abs("y", Term::var("y")),
+    ));
+    assert_eq!(
+        program.disassemble(),
+        concat!(
+            " 0000  GRAB y\n",
+            " 0001  ACCESS 0\n",
+            " 0002  APPLY\n",
+            " 0003  CLOSURE 0000\n",
+            " 0004  RETURN\n",
+            " 0005  GRAB x\n",
+            " 0006  ACCESS 0\n",
+            " 0007  APPLY\n",
+            ">0008  CLOSURE 0003\n",
+            " 0009  PUSH\n",
+            " 0010  CLOSURE 0005\n",
+            " 0011  RETURN\n",
+        )
+    );
+}
+
+// (λx.x) a ↠ a
+#[test]
+fn vm_basic_beta() {
+    let term = Term::app(Term::abs("x", Term::var("x")), Term::var("a"));
+    assert_eq!(normalize(&term), Term::var("a"));
+}
+
+// add 2 3 ↠ 5
+#[test]
+fn vm_church_addition() {
+    let term = Term::app(Term::app(add(), church(2)), church(3));
+    assert_eq!(normalize(&term), church(5));
+}
+
+// 3 3 ↠ 27
+#[test]
+fn vm_church_exponent() {
+    let term = Term::app(church(3), church(3));
+    assert!(normalize(&term).alpha_eq(&church(27)));
+}
+
+// open terms, capture and reduction under binders agree with normal order
+#[test]
+fn vm_matches_normal_order() {
+    let terms = [
+        Term::app(Term::app(Term::var("f"), church(2)), Term::var("a")),
+        Term::app(church(2), Term::var("g")),
+        Term::app(Term::app(add(), church(1)), Term::var("n")),
+        Term::app(
+            Term::abs("x", Term::abs("y", Term::var("x"))),
+            Term::var("y"),
+        ),
+        Term::abs(
+            "x",
+            Term::app(Term::abs("y", Term::var("y")), Term::var("x")),
+        ),
+    ];
+    for term in terms {
+        assert_eq!(normalize(&term), reduce_to_normal(term));
+    }
+}
+
+// (λx.λy.y) Ω ↠ λy.y, the argument is never entered
+#[test]
+fn vm_is_lazy() {
+    let term = Term::app(Term::abs("x", Term::abs("y", Term::var("y"))), omega());
+    assert_eq!(normalize(&term), Term::abs("y", Term::var("y")));
+}
+
+#[test]
+fn vm_heap_limit() {
+    let program = compile(&omega());
+    let mut vm = Vm::new(&program).with_heap_limit(1000);
+    assert_eq!(vm.normalize(), Err(VmError::HeapExhausted(1000)));
+}
+
+#[test]
+fn vm_max_steps() {
+    let program = compile(&omega());
+    let mut vm = Vm::new(&program).with_max_steps(100);
+    assert_eq!(vm.normalize(), Err(VmError::MaxStepsExceeded(100)));
+    let mut vm = Vm::new(&program);
+    assert_eq!(
+        vm.normalize(),
+        Err(VmError::MaxStepsExceeded(DEFAULT_MAX_STEPS))
+    );
+}
+
+// 2 2 2 needs a stack frame for every pending argument
+#[test]
+fn vm_stack_limit() {
+    let term = Term::app(Term::app(church(2), church(2)), church(2));
+    let program = compile(&term);
+    let mut vm = Vm::new(&program).with_stack_limit(2);
+    assert_eq!(vm.normalize(), Err(VmError::StackOverflow(2)));
+    let mut vm = Vm::new(&program).with_stack_limit(64);
+    assert!(vm.normalize_term().unwrap().alpha_eq(&church(16)));
+}
+
+#[test]
+fn vm_runs_repeatedly() {
+    let program = compile(&Term::app(Term::app(add(), church(4)), church(6)));
+    let mut vm = Vm::new(&program);
+    assert_eq!(vm.normalize_term().unwrap(), church(10));
+    let (steps, heap) = (vm.steps(), vm.heap_size());
+    assert_eq!(vm.normalize_term().unwrap(), church(10));
+    assert_eq!((vm.steps(), vm.heap_size()), (steps, heap));
+}
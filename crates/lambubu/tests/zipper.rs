@@ -0,0 +1,151 @@
+use lambubu::BruijnLevelsTerm;
+use lambubu::Term;
+use lambubu::Zipper;
+use lambubu::path::{Direction, Path};
+use lambubu::sync;
+use std::rc::Rc;
+use std::sync::Arc;
+
+fn id(name: &str) -> Term {
+    Term::abs(name, Term::var(name))
+}
+
+// λf.((f ((λx.x) a)) b)
+fn sample() -> Rc<Term> {
+    Rc::new(Term::abs(
+        "f",
+        Term::app(
+            Term::app(Term::var("f"), Term::app(id("x"), Term::var("a"))),
+            Term::var("b"),
+        ),
+    ))
+}
+
+#[test]
+fn zipper_moves() {
+    let mut zipper = Zipper::new(sample());
+    assert!(zipper.is_root());
+    assert!(!zipper.up());
+    assert!(zipper.down());
+    assert!(zipper.down());
+    assert!(!zipper.left());
+    assert!(zipper.right());
+    assert_eq!(zipper.focus().as_ref(), &Term::var("b"));
+    assert!(!zipper.right());
+    assert!(zipper.left());
+    assert!(zipper.down());
+    assert_eq!(zipper.focus().as_ref(), &Term::var("f"));
+    assert!(!zipper.down());
+    zipper.top();
+    assert!(zipper.is_root());
+    assert_eq!(zipper.focus(), &sample());
+}
+
+#[test]
+fn zipper_path_round_trip() {
+    let path = Path::from(vec![
+        Direction::Body,
+        Direction::Function,
+        Direction::Argument,
+    ]);
+    let zipper = Zipper::at(sample(), &path).unwrap();
+    assert_eq!(zipper.path(), path);
+    assert_eq!(zipper.focus().as_ref(), &Term::app(id("x"), Term::var("a")));
+    assert_eq!(Some(zipper.focus().as_ref()), sample().subterm_at(&path));
+}
+
+#[test]
+fn zipper_invalid_path() {
+    let path = Path::from(vec![Direction::Function]);
+    assert!(Zipper::at(sample(), &path).is_none());
+}
+
+// contracting the redex found by redexes() through the zipper
+#[test]
+fn zipper_replace_redex() {
+    let term = sample();
+    let (path, _) = term.redexes().into_iter().next().unwrap();
+    let mut zipper = Zipper::at(term.clone(), &path).unwrap();
+    let contracted = (**zipper.focus())
+        .clone()
+        .contract_at(&Path::root())
+        .unwrap();
+    zipper.replace(Rc::new(contracted));
+    let result = zipper.into_root();
+    assert_eq!(
+        Some(result.as_ref().clone()),
+        (*term).clone().contract_at(&path)
+    );
+}
+
+// replacing a subterm keeps untouched siblings shared
+#[test]
+fn zipper_preserves_sharing() {
+    let term = sample();
+    let mut zipper = Zipper::at(
+        term.clone(),
+        &Path::from(vec![Direction::Body, Direction::Argument]),
+    )
+    .unwrap();
+    zipper.replace(Rc::new(Term::var("c")));
+    let result = zipper.into_root();
+    let (Term::Abs(_, old_body), Term::Abs(_, new_body)) = (term.as_ref(), result.as_ref()) else {
+        panic!()
+    };
+    let (Term::Apply(old_fun, _), Term::Apply(new_fun, new_arg)) =
+        (old_body.as_ref(), new_body.as_ref())
+    else {
+        panic!()
+    };
+    assert!(Rc::ptr_eq(old_fun, new_fun));
+    assert_eq!(new_arg.as_ref(), &Term::var("c"));
+}
+
+// walking without edits gives back the very same root
+#[test]
+fn zipper_unchanged_root_is_shared() {
+    let term = sample();
+    let mut zipper = Zipper::new(term.clone());
+    zipper.down();
+    zipper.down();
+    zipper.right();
+    assert!(Rc::ptr_eq(&zipper.into_root(), &term));
+}
+
+#[test]
+fn bruijn_zipper_modify() {
+    let term = BruijnLevelsTerm::from_open_term((*sample()).clone());
+    let path = Path::from(vec![
+        Direction::Body,
+        Direction::Function,
+        Direction::Argument,
+    ]);
+    let mut zipper = Zipper::at(term, &path).unwrap();
+    zipper.modify(|redex| redex.reduce_step_normal_order());
+    let result = zipper.into_root();
+    let expected = Term::abs(
+        "f",
+        Term::app(Term::app(Term::var("f"), Term::var("a")), Term::var("b")),
+    );
+    assert_eq!(Term::from((*result).clone()), expected);
+}
+
+// the same moves work on sync terms, and rebuilding leaves the original alone
+#[test]
+fn sync_zipper_modify() {
+    let term = Arc::new(sync::Term::from(sample().as_ref()));
+    let path = Path::from(vec![
+        Direction::Body,
+        Direction::Function,
+        Direction::Argument,
+    ]);
+    let mut zipper = Zipper::at(term.clone(), &path).unwrap();
+    zipper.modify(|redex| Arc::new((*redex).clone().reduce_step_normal_order()));
+    let result = zipper.into_root();
+    let expected = Term::abs(
+        "f",
+        Term::app(Term::app(Term::var("f"), Term::var("a")), Term::var("b")),
+    );
+    assert_eq!(*result, sync::Term::from(&expected));
+    assert_eq!(*term, sync::Term::from(sample().as_ref()));
+}
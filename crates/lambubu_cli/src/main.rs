@@ -1,27 +1,357 @@
-use std::io::{self, Read};
+use std::{
+    collections::BTreeMap,
+    fs::{self, File},
+    io::{self, BufReader, BufWriter, Read, Write},
+    num::NonZeroUsize,
+    panic::{self, AssertUnwindSafe},
+    path::PathBuf,
+    process,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+};
 
-use lambubu::{CompoundEnvironment, compile::compile_file};
+use clap::{Parser, ValueEnum};
+use lambubu::{
+    CompoundEnvironment, NormalFormCache, Strategy, Term,
+    binary::{BinaryReader, BinaryWriter},
+    blc,
+    codegen::Target,
+    compile::compile_file,
+    divergence::Evaluator,
+    sexpr::compile_sexpr_file,
+    sync,
+};
 use lambubu_church::ChurchEnvironment;
+use lambubu_types::principal_type;
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Syntax {
+    /// `λx.(f x)`, as in `grammar.pest`
+    Lambda,
+    /// `(lambda (x) (f x))`
+    Sexpr,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum EmitTarget {
+    Javascript,
+    Python,
+    Haskell,
+}
+
+impl From<EmitTarget> for Target {
+    fn from(value: EmitTarget) -> Self {
+        match value {
+            EmitTarget::Javascript => Target::JavaScript,
+            EmitTarget::Python => Target::Python,
+            EmitTarget::Haskell => Target::Haskell,
+        }
+    }
+}
+
+/// Normal forms remembered across the terms of one run.
+const CACHE_CAPACITY: usize = 1 << 16;
+
+/// Stack size of the evaluation workers. Reduction recurses on the structure
+/// of the term, so deep terms need more than the default 2 MiB.
+const WORKER_STACK_SIZE: usize = 64 << 20;
+
+const SEXPR_EXTENSIONS: [&str; 4] = ["sexp", "scm", "lisp", "lsp"];
+
+#[derive(Parser)]
+struct Args {
+    /// File with the terms to evaluate, defaults to stdin
+    #[arg(conflicts_with_all = ["blc", "load_binary"])]
+    input: Option<PathBuf>,
+    /// Syntax of the input and the printed normal forms, detected from the
+    /// extension of the input file by default
+    #[arg(long, value_enum)]
+    syntax: Option<Syntax>,
+    /// Maximum number of reduction steps spent on each term
+    #[arg(long, default_value_t = 10_000)]
+    max_steps: usize,
+    /// Number of worker threads, defaults to the available parallelism
+    #[arg(long)]
+    jobs: Option<NonZeroUsize>,
+    /// Run a binary lambda calculus program on stdin instead of evaluating terms
+    #[arg(long, value_name = "PROGRAM")]
+    blc: Option<PathBuf>,
+    /// Pass stdin and output of the BLC program as lists of bytes instead of bits
+    #[arg(long, requires = "blc")]
+    bytes: bool,
+    /// Print the size of the BLC program and the number of steps to stderr
+    #[arg(long, requires = "blc")]
+    stats: bool,
+    /// Read the terms to evaluate from a binary term file instead of stdin
+    #[arg(long, value_name = "PATH", conflicts_with = "blc")]
+    load_binary: Option<PathBuf>,
+    /// Also write the normal forms to a binary term file
+    #[arg(long, value_name = "PATH", conflicts_with = "blc")]
+    emit_binary: Option<PathBuf>,
+    /// Print the definitions and terms as source code instead of evaluating them
+    #[arg(long, value_enum, value_name = "TARGET", conflicts_with_all = ["blc", "load_binary", "emit_binary"])]
+    emit: Option<EmitTarget>,
+    /// Print the principal type of each definition and term instead of
+    /// evaluating them
+    #[arg(long, conflicts_with_all = ["blc", "load_binary", "emit_binary", "emit"])]
+    types: bool,
+}
+
+impl Args {
+    fn syntax(&self) -> Syntax {
+        let by_extension = || {
+            let extension = self.input.as_ref()?.extension()?.to_str()?;
+            SEXPR_EXTENSIONS
+                .contains(&extension)
+                .then_some(Syntax::Sexpr)
+        };
+        self.syntax.or_else(by_extension).unwrap_or(Syntax::Lambda)
+    }
+}
 
 fn standard_environment() -> CompoundEnvironment {
     CompoundEnvironment::new(vec![Box::new(ChurchEnvironment)])
 }
 
+fn evaluate(
+    term: &sync::Term,
+    max_steps: usize,
+    cache: &Arc<NormalFormCache>,
+) -> Result<sync::Term, String> {
+    Evaluator::new(Strategy::NormalOrder)
+        .with_max_steps(max_steps)
+        .with_cache(cache.clone())
+        .evaluate(Term::from(term))
+        .map(|(term, _)| sync::Term::from(&term))
+        .map_err(|err| err.to_string())
+}
+
+/// Evaluates one term, turning a panic into an error for that term so the
+/// other terms are still printed.
+fn evaluate_isolated(
+    term: &sync::Term,
+    max_steps: usize,
+    cache: &Arc<NormalFormCache>,
+) -> Result<sync::Term, String> {
+    panic::catch_unwind(AssertUnwindSafe(|| evaluate(term, max_steps, cache))).unwrap_or_else(
+        |payload| {
+            let message = payload
+                .downcast_ref::<&str>()
+                .copied()
+                .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+                .unwrap_or("unknown cause");
+            Err(format!("evaluation failed: {message}"))
+        },
+    )
+}
+
+fn load_terms(args: &Args) -> Result<Vec<sync::Term>, String> {
+    if let Some(path) = &args.load_binary {
+        let file = File::open(path).map_err(|err| err.to_string())?;
+        return BinaryReader::new(BufReader::new(file))
+            .map_err(|err| err.to_string())?
+            .map(|term| term.map(|term| sync::Term::from(&term)))
+            .collect::<Result<_, _>>()
+            .map_err(|err| err.to_string());
+    }
+    let source = read_source(args)?;
+    compile_source(args, &source, &mut standard_environment())
+        .map(|terms| terms.iter().map(sync::Term::from).collect())
+}
+
+fn read_source(args: &Args) -> Result<String, String> {
+    match &args.input {
+        Some(path) => fs::read_to_string(path).map_err(|err| err.to_string()),
+        None => {
+            let mut buffer = String::new();
+            io::stdin()
+                .read_to_string(&mut buffer)
+                .map_err(|err| err.to_string())?;
+            Ok(buffer)
+        }
+    }
+}
+
+fn compile_source(
+    args: &Args,
+    source: &str,
+    env: &mut CompoundEnvironment,
+) -> Result<Vec<Term>, String> {
+    match args.syntax() {
+        Syntax::Lambda => compile_file(source, env),
+        Syntax::Sexpr => compile_sexpr_file(source, env),
+    }
+    .map_err(|err| err.to_string())
+}
+
+fn run_emit(args: &Args, target: Target) -> Result<(), String> {
+    let source = read_source(args)?;
+    let mut env = standard_environment();
+    let terms = compile_source(args, &source, &mut env)?;
+    let code = target
+        .generate(&env.definitions(), &terms)
+        .map_err(|err| err.to_string())?;
+    print!("{code}");
+    Ok(())
+}
+
+fn run_types(args: &Args) -> Result<(), String> {
+    let source = read_source(args)?;
+    let mut env = standard_environment();
+    let terms = compile_source(args, &source, &mut env)?;
+    for (name, term) in env.definitions() {
+        match principal_type(term) {
+            Ok(scheme) => println!("{name} : {scheme}"),
+            Err(err) => eprintln!("{name} : {err}"),
+        }
+    }
+    for (i, term) in terms.iter().enumerate() {
+        let num = i + 1;
+        match principal_type(term) {
+            Ok(scheme) => println!("{num}. {scheme}"),
+            Err(err) => eprintln!("{num}. {err}"),
+        }
+    }
+    Ok(())
+}
+
+fn binary_writer(args: &Args) -> Result<Option<BinaryWriter<BufWriter<File>>>, String> {
+    let Some(path) = &args.emit_binary else {
+        return Ok(None);
+    };
+    let file = File::create(path).map_err(|err| err.to_string())?;
+    BinaryWriter::new(BufWriter::new(file))
+        .map(Some)
+        .map_err(|err| err.to_string())
+}
+
+fn fail(err: impl std::fmt::Display) -> ! {
+    eprintln!("{err}");
+    process::exit(1);
+}
+
+fn run_blc(args: &Args, program: &PathBuf) -> Result<(), String> {
+    let source = fs::read(program).map_err(|err| err.to_string())?;
+    let bits = match std::str::from_utf8(&source)
+        .ok()
+        .and_then(blc::bits_from_str)
+    {
+        Some(bits) => bits,
+        None => blc::bytes_to_bits(&source),
+    };
+    let (program, size) = blc::decode(&bits).map_err(|err| err.to_string())?;
+    let mut input = Vec::new();
+    io::stdin()
+        .read_to_end(&mut input)
+        .map_err(|err| err.to_string())?;
+    let input = if args.bytes {
+        blc::bytes_list(&input)
+    } else {
+        let bits = blc::bits_from_str(&String::from_utf8_lossy(&input))
+            .ok_or("input must consist of 0 and 1 characters; use --bytes for raw input")?;
+        blc::bits_list(&bits)
+    };
+    let (output, steps) = Evaluator::new(Strategy::NormalOrder)
+        .with_max_steps(args.max_steps)
+        .evaluate(Term::app(program, input))
+        .map_err(|err| err.to_string())?;
+    if args.stats {
+        eprintln!("program size: {size} bits, {steps} steps");
+    }
+    let output = if args.bytes {
+        blc::decode_bytes_list(&output)
+    } else {
+        blc::decode_bits_list(&output).map(|bits| blc::bits_to_string(&bits).into_bytes())
+    }
+    .map_err(|err| err.to_string())?;
+    io::stdout()
+        .write_all(&output)
+        .map_err(|err| err.to_string())
+}
+
 fn main() {
-    let mut buffer = String::new();
-    io::stdin().read_to_string(&mut buffer).unwrap();
-    let terms = compile_file(&buffer, &mut standard_environment()).unwrap();
-    for (i, mut term) in terms.into_iter().enumerate() {
-        const MAX_STEPS: usize = 10_000;
-        let mut steps = 0;
-        while !term.is_normal_form() {
-            term = term.reduce_step_normal_order();
-            steps += 1;
-            if steps >= MAX_STEPS {
-                eprintln!("max steps exceeded");
-                return;
+    let args = Args::parse();
+    if let Some(program) = &args.blc {
+        if let Err(err) = run_blc(&args, program) {
+            fail(err);
+        }
+        return;
+    }
+    if let Some(target) = args.emit {
+        if let Err(err) = run_emit(&args, target.into()) {
+            fail(err);
+        }
+        return;
+    }
+    if args.types {
+        if let Err(err) = run_types(&args) {
+            fail(err);
+        }
+        return;
+    }
+    let terms = load_terms(&args).unwrap_or_else(|err| fail(err));
+    let mut writer = binary_writer(&args).unwrap_or_else(|err| fail(err));
+    let jobs = args
+        .jobs
+        .or_else(|| thread::available_parallelism().ok())
+        .map_or(1, NonZeroUsize::get)
+        .min(terms.len().max(1));
+
+    let next = AtomicUsize::new(0);
+    let cache = Arc::new(NormalFormCache::new(CACHE_CAPACITY));
+    let (sender, receiver) = mpsc::channel();
+    thread::scope(|scope| {
+        for _ in 0..jobs {
+            let sender = sender.clone();
+            let (next, terms, cache) = (&next, &terms, &cache);
+            thread::Builder::new()
+                .stack_size(WORKER_STACK_SIZE)
+                .spawn_scoped(scope, move || {
+                    loop {
+                        let i = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(term) = terms.get(i) else { break };
+                        let result = evaluate_isolated(term, args.max_steps, cache);
+                        if sender.send((i, result)).is_err() {
+                            break;
+                        }
+                    }
+                })
+                .unwrap_or_else(|err| fail(err));
+        }
+        drop(sender);
+
+        let mut pending = BTreeMap::new();
+        let mut printed = 0;
+        for (i, result) in receiver {
+            pending.insert(i, result);
+            while let Some(result) = pending.remove(&printed) {
+                let num = printed + 1;
+                match result {
+                    Ok(term) => {
+                        match args.syntax() {
+                            Syntax::Lambda => println!("{num}. {term}"),
+                            Syntax::Sexpr => match Term::from(&term).to_sexpr() {
+                                Ok(sexpr) => println!("{num}. {sexpr}"),
+                                Err(err) => eprintln!("{num}. {err}"),
+                            },
+                        }
+                        if let Some(writer) = &mut writer {
+                            writer
+                                .write_term(&Term::from(&term))
+                                .unwrap_or_else(|err| fail(err));
+                        }
+                    }
+                    Err(err) => eprintln!("{num}. {err}"),
+                }
+                printed += 1;
             }
         }
-        println!("{num}. {term}", num = i + 1);
+    });
+    if let Some(writer) = &mut writer {
+        writer.flush().unwrap_or_else(|err| fail(err));
     }
 }
@@ -0,0 +1,54 @@
+use crate::{Combinator, Primitive};
+use lambubu::Term;
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Basis {
+    Ski,
+    Skibcw,
+}
+
+pub fn translate(term: &Term, basis: Basis) -> Combinator {
+    match term {
+        Term::Var(v) => Combinator::Var(v.clone()),
+        Term::Abs(v, body) => abstract_var(v, translate(body, basis), basis),
+        Term::Apply(t1, t2) => Combinator::app(translate(t1, basis), translate(t2, basis)),
+    }
+}
+
+fn prim(p: Primitive) -> Combinator {
+    Combinator::Primitive(p)
+}
+
+/// Bracket abstraction `[x]c` with the K, η and (for [`Basis::Skibcw`])
+/// B, C and W optimizations.
+fn abstract_var(x: &str, c: Combinator, basis: Basis) -> Combinator {
+    if !c.is_free_variable(x) {
+        return Combinator::app(prim(Primitive::K), c);
+    }
+    match c {
+        Combinator::Var(_) => prim(Primitive::I),
+        Combinator::Apply(c1, c2) => {
+            let in_function = c1.is_free_variable(x);
+            let is_argument = matches!(c2.as_ref(), Combinator::Var(v) if &**v == x);
+            match (basis, in_function, c2.is_free_variable(x)) {
+                (_, false, _) if is_argument => (*c1).clone(),
+                (Basis::Skibcw, true, _) if is_argument => {
+                    Combinator::app(prim(Primitive::W), abstract_var(x, (*c1).clone(), basis))
+                }
+                (Basis::Skibcw, false, true) => Combinator::app(
+                    Combinator::app(prim(Primitive::B), (*c1).clone()),
+                    abstract_var(x, (*c2).clone(), basis),
+                ),
+                (Basis::Skibcw, true, false) => Combinator::app(
+                    Combinator::app(prim(Primitive::C), abstract_var(x, (*c1).clone(), basis)),
+                    (*c2).clone(),
+                ),
+                _ => Combinator::app(
+                    Combinator::app(prim(Primitive::S), abstract_var(x, (*c1).clone(), basis)),
+                    abstract_var(x, (*c2).clone(), basis),
+                ),
+            }
+        }
+        Combinator::Primitive(_) => unreachable!(),
+    }
+}
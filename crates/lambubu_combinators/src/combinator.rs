@@ -0,0 +1,110 @@
+use lambubu::Term;
+use std::{
+    fmt::{self, Display, Formatter},
+    rc::Rc,
+};
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Primitive {
+    S,
+    K,
+    I,
+    B,
+    C,
+    W,
+}
+
+impl Primitive {
+    pub fn arity(self) -> usize {
+        match self {
+            Primitive::I => 1,
+            Primitive::K | Primitive::W => 2,
+            Primitive::S | Primitive::B | Primitive::C => 3,
+        }
+    }
+
+    pub fn to_term(self) -> Term {
+        let (x, y, z) = (Term::var("x"), Term::var("y"), Term::var("z"));
+        let body = match self {
+            Primitive::S => Term::app(Term::app(x, z.clone()), Term::app(y, z)),
+            Primitive::K => x,
+            Primitive::I => x,
+            Primitive::B => Term::app(x, Term::app(y, z)),
+            Primitive::C => Term::app(Term::app(x, z), y),
+            Primitive::W => Term::app(Term::app(x, y.clone()), y),
+        };
+        let binders: &[&str] = match self.arity() {
+            1 => &["x"],
+            2 => &["x", "y"],
+            _ => &["x", "y", "z"],
+        };
+        binders
+            .iter()
+            .rev()
+            .fold(body, |body, binder| Term::abs(binder, body))
+    }
+}
+
+impl Display for Primitive {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Combinator {
+    Primitive(Primitive),
+    Var(Rc<str>),
+    Apply(Rc<Combinator>, Rc<Combinator>),
+}
+
+impl Display for Combinator {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Combinator::Primitive(p) => write!(f, "{p}"),
+            Combinator::Var(v) => write!(f, "{v}"),
+            Combinator::Apply(c1, c2) => write!(f, "({c1} {c2})"),
+        }
+    }
+}
+
+impl Combinator {
+    pub fn var(name: &str) -> Self {
+        Combinator::Var(Rc::from(name))
+    }
+
+    pub fn app(c1: Combinator, c2: Combinator) -> Self {
+        Combinator::Apply(Rc::new(c1), Rc::new(c2))
+    }
+
+    pub fn is_free_variable(&self, name: &str) -> bool {
+        match self {
+            Combinator::Primitive(_) => false,
+            Combinator::Var(v) => &**v == name,
+            Combinator::Apply(c1, c2) => c1.is_free_variable(name) || c2.is_free_variable(name),
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        match self {
+            Combinator::Apply(c1, c2) => 1 + c1.size() + c2.size(),
+            _ => 1,
+        }
+    }
+
+    /// Reads the combinator back as a lambda term by replacing every
+    /// primitive with its defining abstraction. The result is not normalized.
+    pub fn to_term(&self) -> Term {
+        match self {
+            Combinator::Primitive(p) => p.to_term(),
+            Combinator::Var(v) => Term::Var(v.clone()),
+            Combinator::Apply(c1, c2) => Term::app(c1.to_term(), c2.to_term()),
+        }
+    }
+}
+
+impl From<Primitive> for Combinator {
+    fn from(value: Primitive) -> Self {
+        Combinator::Primitive(value)
+    }
+}
@@ -0,0 +1,7 @@
+pub mod bracket;
+pub mod combinator;
+pub mod machine;
+
+pub use bracket::{Basis, translate};
+pub use combinator::{Combinator, Primitive};
+pub use machine::CombinatorMachine;
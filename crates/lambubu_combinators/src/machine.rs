@@ -0,0 +1,175 @@
+use crate::{Combinator, Primitive};
+use std::{collections::HashMap, rc::Rc};
+
+type NodeId = usize;
+
+#[derive(Clone, Debug)]
+enum Node {
+    Primitive(Primitive),
+    Var(Rc<str>),
+    Apply(NodeId, NodeId),
+    Indirection(NodeId),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum MachineError {
+    #[error("max steps exceeded after {0} steps")]
+    MaxStepsExceeded(usize),
+}
+
+/// Combinator graph reducer.
+///
+/// Every rewrite overwrites the root of the redex in place, so arguments that
+/// `S` and `W` duplicate stay shared and are reduced at most once.
+#[derive(Clone, Debug)]
+pub struct CombinatorMachine {
+    nodes: Vec<Node>,
+    root: NodeId,
+    steps: usize,
+    max_steps: Option<usize>,
+}
+
+impl CombinatorMachine {
+    pub fn new(combinator: &Combinator) -> Self {
+        let mut machine = Self {
+            nodes: Vec::new(),
+            root: 0,
+            steps: 0,
+            max_steps: None,
+        };
+        machine.root = machine.build(combinator, &mut HashMap::new());
+        machine
+    }
+
+    pub fn with_max_steps(mut self, max_steps: usize) -> Self {
+        self.max_steps = Some(max_steps);
+        self
+    }
+
+    fn alloc(&mut self, node: Node) -> NodeId {
+        self.nodes.push(node);
+        self.nodes.len() - 1
+    }
+
+    fn build(
+        &mut self,
+        combinator: &Combinator,
+        built: &mut HashMap<*const Combinator, NodeId>,
+    ) -> NodeId {
+        if let Some(node) = built.get(&(combinator as *const Combinator)) {
+            return *node;
+        }
+        let node = match combinator {
+            Combinator::Primitive(p) => self.alloc(Node::Primitive(*p)),
+            Combinator::Var(v) => self.alloc(Node::Var(v.clone())),
+            Combinator::Apply(c1, c2) => {
+                let c1 = self.build(c1, built);
+                let c2 = self.build(c2, built);
+                self.alloc(Node::Apply(c1, c2))
+            }
+        };
+        built.insert(combinator as *const Combinator, node);
+        node
+    }
+
+    fn follow(&self, mut node: NodeId) -> NodeId {
+        while let Node::Indirection(next) = self.nodes[node] {
+            node = next;
+        }
+        node
+    }
+
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    pub fn size(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn whnf(&mut self) -> Result<(), MachineError> {
+        self.whnf_node(self.root)?;
+        Ok(())
+    }
+
+    pub fn normalize(&mut self) -> Result<(), MachineError> {
+        self.normalize_node(self.root)
+    }
+
+    fn argument(&self, node: NodeId) -> NodeId {
+        match self.nodes[node] {
+            Node::Apply(_, arg) => arg,
+            _ => unreachable!(),
+        }
+    }
+
+    fn whnf_node(&mut self, node: NodeId) -> Result<Vec<NodeId>, MachineError> {
+        loop {
+            let mut spine = Vec::new();
+            let mut current = self.follow(node);
+            while let Node::Apply(function, _) = self.nodes[current] {
+                spine.push(current);
+                current = self.follow(function);
+            }
+            let Node::Primitive(p) = self.nodes[current] else {
+                return Ok(spine);
+            };
+            if spine.len() < p.arity() {
+                return Ok(spine);
+            }
+            if self.max_steps.is_some_and(|max| self.steps >= max) {
+                return Err(MachineError::MaxStepsExceeded(self.steps));
+            }
+            let args: Vec<NodeId> = spine
+                .iter()
+                .rev()
+                .take(p.arity())
+                .map(|app| self.argument(*app))
+                .collect();
+            let redex = spine[spine.len() - p.arity()];
+            self.nodes[redex] = match (p, args.as_slice()) {
+                (Primitive::I, [x]) | (Primitive::K, [x, _]) => Node::Indirection(*x),
+                (Primitive::S, [f, g, x]) => {
+                    let fx = self.alloc(Node::Apply(*f, *x));
+                    let gx = self.alloc(Node::Apply(*g, *x));
+                    Node::Apply(fx, gx)
+                }
+                (Primitive::B, [f, g, x]) => {
+                    let gx = self.alloc(Node::Apply(*g, *x));
+                    Node::Apply(*f, gx)
+                }
+                (Primitive::C, [f, g, x]) => {
+                    let fx = self.alloc(Node::Apply(*f, *x));
+                    Node::Apply(fx, *g)
+                }
+                (Primitive::W, [f, x]) => {
+                    let fx = self.alloc(Node::Apply(*f, *x));
+                    Node::Apply(fx, *x)
+                }
+                _ => unreachable!(),
+            };
+            self.steps += 1;
+        }
+    }
+
+    fn normalize_node(&mut self, node: NodeId) -> Result<(), MachineError> {
+        for app in self.whnf_node(node)? {
+            let arg = self.argument(app);
+            self.normalize_node(arg)?;
+        }
+        Ok(())
+    }
+
+    pub fn to_combinator(&self) -> Combinator {
+        self.read_back(self.root)
+    }
+
+    fn read_back(&self, node: NodeId) -> Combinator {
+        match &self.nodes[self.follow(node)] {
+            Node::Primitive(p) => Combinator::Primitive(*p),
+            Node::Var(v) => Combinator::Var(v.clone()),
+            Node::Apply(c1, c2) => Combinator::app(self.read_back(*c1), self.read_back(*c2)),
+            Node::Indirection(_) => unreachable!(),
+        }
+    }
+}
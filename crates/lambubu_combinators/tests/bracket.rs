@@ -0,0 +1,122 @@
+use lambubu::Term;
+use lambubu_combinators::{Basis, Combinator, translate};
+
+fn reduce_to_normal(mut t: Term) -> Term {
+    while !t.is_normal_form() {
+        t = t.reduce_step_normal_order();
+    }
+    t
+}
+
+fn round_trip(term: &Term, basis: Basis) -> Term {
+    reduce_to_normal(translate(term, basis).to_term())
+}
+
+fn s_combinator() -> Term {
+    Term::abs(
+        "x",
+        Term::abs(
+            "y",
+            Term::abs(
+                "z",
+                Term::app(
+                    Term::app(Term::var("x"), Term::var("z")),
+                    Term::app(Term::var("y"), Term::var("z")),
+                ),
+            ),
+        ),
+    )
+}
+
+// λx.x ↦ I
+#[test]
+fn translate_identity() {
+    let term = Term::abs("x", Term::var("x"));
+    assert_eq!(translate(&term, Basis::Ski).to_string(), "I");
+}
+
+// λx.y ↦ (K y)
+#[test]
+fn translate_constant() {
+    let term = Term::abs("x", Term::var("y"));
+    assert_eq!(translate(&term, Basis::Ski).to_string(), "(K y)");
+}
+
+// λx.(f x) ↦ f by the η rule
+#[test]
+fn translate_eta() {
+    let term = Term::abs("x", Term::app(Term::var("f"), Term::var("x")));
+    assert_eq!(translate(&term, Basis::Ski), Combinator::var("f"));
+}
+
+// λx.(x x) ↦ ((S I) I) with SKI, (W I) with BCW
+#[test]
+fn translate_self_application() {
+    let term = Term::abs("x", Term::app(Term::var("x"), Term::var("x")));
+    assert_eq!(translate(&term, Basis::Ski).to_string(), "((S I) I)");
+    assert_eq!(translate(&term, Basis::Skibcw).to_string(), "(W I)");
+}
+
+// λx.(f (g x)) ↦ ((B f) g), λx.((f x) g) ↦ ((C f) g)
+#[test]
+fn translate_b_and_c() {
+    let composed = Term::abs(
+        "x",
+        Term::app(Term::var("f"), Term::app(Term::var("g"), Term::var("x"))),
+    );
+    assert_eq!(translate(&composed, Basis::Skibcw).to_string(), "((B f) g)");
+    let flipped = Term::abs(
+        "x",
+        Term::app(Term::app(Term::var("f"), Term::var("x")), Term::var("g")),
+    );
+    assert_eq!(translate(&flipped, Basis::Skibcw).to_string(), "((C f) g)");
+}
+
+// λx.λy.(y x) ↦ ((S (K (S I))) K) with SKI, (C I) with BCW
+#[test]
+fn translate_bcw_is_smaller() {
+    let term = Term::abs(
+        "x",
+        Term::abs("y", Term::app(Term::var("y"), Term::var("x"))),
+    );
+    assert_eq!(
+        translate(&term, Basis::Ski).to_string(),
+        "((S (K (S I))) K)"
+    );
+    assert_eq!(translate(&term, Basis::Skibcw).to_string(), "(C I)");
+}
+
+// the S combinator itself is recovered exactly
+#[test]
+fn translate_s_combinator() {
+    for basis in [Basis::Ski, Basis::Skibcw] {
+        assert_eq!(translate(&s_combinator(), basis).to_string(), "S");
+    }
+}
+
+// translating and reading back gives a βη-equal term
+#[test]
+fn translate_round_trip() {
+    let terms = [
+        s_combinator(),
+        Term::abs(
+            "x",
+            Term::abs("y", Term::app(Term::var("y"), Term::var("x"))),
+        ),
+        Term::abs(
+            "f",
+            Term::abs(
+                "x",
+                Term::app(Term::var("f"), Term::app(Term::var("f"), Term::var("x"))),
+            ),
+        ),
+    ];
+    for term in terms {
+        for basis in [Basis::Ski, Basis::Skibcw] {
+            assert_eq!(
+                round_trip(&term, basis).beta_eta_eq(&term, 1_000),
+                Some(true)
+            );
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use lambubu::{CompoundEnvironment, Term, compile::compile_file};
+use lambubu_church::ChurchEnvironment;
+use lambubu_combinators::machine::MachineError;
+use lambubu_combinators::{Basis, Combinator, CombinatorMachine, Primitive, translate};
+
+fn compile(source: &str) -> Vec<Term> {
+    let mut env = CompoundEnvironment::new(vec![Box::new(ChurchEnvironment)]);
+    compile_file(source, &mut env).unwrap()
+}
+
+fn normalize(combinator: &Combinator) -> Combinator {
+    let mut machine = CombinatorMachine::new(combinator).with_max_steps(10_000);
+    machine.normalize().unwrap();
+    machine.to_combinator()
+}
+
+fn reduce_to_normal(mut t: Term) -> Term {
+    while !t.is_normal_form() {
+        t = t.reduce_step_normal_order();
+    }
+    t
+}
+
+// ((S K) K) a →* a
+#[test]
+fn machine_skk() {
+    let skk = Combinator::app(
+        Combinator::app(Primitive::S.into(), Primitive::K.into()),
+        Primitive::K.into(),
+    );
+    let term = Combinator::app(skk, Combinator::var("a"));
+    assert_eq!(normalize(&term), Combinator::var("a"));
+}
+
+// each primitive applied to variables performs its rewrite
+#[test]
+fn machine_primitives() {
+    let cases = [
+        (Primitive::B, "(f (g x))"),
+        (Primitive::C, "((f x) g)"),
+        (Primitive::W, "((f g) g)"),
+        (Primitive::S, "((f x) (g x))"),
+    ];
+    for (p, expected) in cases {
+        let mut term = Combinator::from(p);
+        for v in ["f", "g", "x"].iter().take(p.arity()) {
+            term = Combinator::app(term, Combinator::var(v));
+        }
+        assert_eq!(normalize(&term).to_string(), expected);
+    }
+}
+
+// (ADD 2 3) f x in combinators normalizes to f (f (f (f (f x))))
+#[test]
+fn machine_church_add() {
+    let terms = compile("((ADD 2 3) f x)");
+    let expected = reduce_to_normal(terms[0].clone());
+    for basis in [Basis::Ski, Basis::Skibcw] {
+        let result = normalize(&translate(&terms[0], basis));
+        assert_eq!(result.to_term(), expected);
+    }
+}
+
+// the same program evaluated both ways
+#[test]
+fn machine_matches_lambda_evaluation() {
+    let terms = compile(
+        "MUL :: \\m.\\n.\\f.(m (n f))
+        (IF TRUE a b)
+        ((MUL 3 2) f x)
+        ((SUCC (SUCC 1)) g y)",
+    );
+    for term in terms {
+        let expected = reduce_to_normal(term.clone());
+        let result = normalize(&translate(&term, Basis::Skibcw));
+        assert_eq!(result.to_term(), expected);
+    }
+}
+
+// (λx.y) Ω: K discards the divergent argument
+#[test]
+fn machine_lazy() {
+    let terms = compile("(\\x.y ((\\x.(x x)) (\\x.(x x))))");
+    let result = normalize(&translate(&terms[0], Basis::Ski));
+    assert_eq!(result, Combinator::var("y"));
+}
+
+#[test]
+fn machine_max_steps() {
+    let terms = compile("((\\x.(x x)) (\\x.(x x)))");
+    let mut machine = CombinatorMachine::new(&translate(&terms[0], Basis::Ski)).with_max_steps(100);
+    assert_eq!(
+        machine.normalize(),
+        Err(MachineError::MaxStepsExceeded(100))
+    );
+}
+
+// W duplicates its argument by sharing, so the redex inside is contracted once
+#[test]
+fn machine_shares_duplicated_arguments() {
+    let redex = Combinator::app(Primitive::I.into(), Combinator::var("a"));
+    let term = Combinator::app(
+        Combinator::app(Primitive::W.into(), Combinator::var("f")),
+        redex,
+    );
+    let mut machine = CombinatorMachine::new(&term);
+    machine.normalize().unwrap();
+    assert_eq!(machine.to_combinator().to_string(), "((f a) a)");
+    assert_eq!(machine.steps(), 2);
+}
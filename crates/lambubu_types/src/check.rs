@@ -0,0 +1,205 @@
+use crate::{Type, TypedTerm};
+use pest::Span;
+use std::{collections::HashMap, rc::Rc};
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum TypeError<'a> {
+    #[error("Unbound variable {name} at {span:?}")]
+    UnboundVariable { name: String, span: Span<'a> },
+    #[error("Type mismatch: expected {expected}, found {found} at {span:?}")]
+    Mismatch {
+        expected: Type,
+        found: Type,
+        span: Span<'a>,
+    },
+    #[error("Infinite type {var} = {ty} at {span:?}")]
+    InfiniteType { var: Type, ty: Type, span: Span<'a> },
+}
+
+pub(crate) enum UnifyError {
+    Mismatch,
+    Occurs(usize, Type),
+}
+
+/// Substitution built up by unification, as a binding per type variable.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Unifier {
+    bindings: Vec<Option<Type>>,
+}
+
+impl Unifier {
+    pub(crate) fn fresh(&mut self) -> Type {
+        self.bindings.push(None);
+        Type::Var(self.bindings.len() - 1)
+    }
+
+    fn shallow(&self, ty: &Type) -> Type {
+        let mut ty = ty.clone();
+        while let Type::Var(n) = ty {
+            match &self.bindings[n] {
+                Some(bound) => ty = bound.clone(),
+                None => break,
+            }
+        }
+        ty
+    }
+
+    /// Applies the substitution throughout `ty`.
+    pub(crate) fn resolve(&self, ty: &Type) -> Type {
+        match self.shallow(ty) {
+            Type::Arrow(t1, t2) => Type::arrow(self.resolve(&t1), self.resolve(&t2)),
+            ty => ty,
+        }
+    }
+
+    pub(crate) fn unify(&mut self, t1: &Type, t2: &Type) -> Result<(), UnifyError> {
+        match (self.shallow(t1), self.shallow(t2)) {
+            (Type::Var(n), Type::Var(m)) if n == m => Ok(()),
+            (Type::Var(n), ty) | (ty, Type::Var(n)) => {
+                let ty = self.resolve(&ty);
+                if ty.occurs(n) {
+                    return Err(UnifyError::Occurs(n, ty));
+                }
+                self.bindings[n] = Some(ty);
+                Ok(())
+            }
+            (Type::Base(a), Type::Base(b)) if a == b => Ok(()),
+            (Type::Arrow(a1, r1), Type::Arrow(a2, r2)) => {
+                self.unify(&a1, &a2)?;
+                self.unify(&r1, &r2)
+            }
+            _ => Err(UnifyError::Mismatch),
+        }
+    }
+
+    /// Turns a failed unification of `expected` with `found` into an error at
+    /// `span`, numbering the variables of both types together.
+    pub(crate) fn error<'a>(
+        &self,
+        error: UnifyError,
+        expected: &Type,
+        found: &Type,
+        span: Span<'a>,
+    ) -> TypeError<'a> {
+        let mut names = HashMap::new();
+        match error {
+            UnifyError::Mismatch => TypeError::Mismatch {
+                expected: self.resolve(expected).renamed(&mut names),
+                found: self.resolve(found).renamed(&mut names),
+                span,
+            },
+            UnifyError::Occurs(var, ty) => TypeError::InfiniteType {
+                var: Type::Var(var).renamed(&mut names),
+                ty: ty.renamed(&mut names),
+                span,
+            },
+        }
+    }
+}
+
+/// Infers the most general simple type of `term`, or its annotated parts.
+#[derive(Default)]
+struct Inference {
+    unifier: Unifier,
+    context: Vec<(Rc<str>, Type)>,
+    annotation_vars: HashMap<usize, Type>,
+}
+
+impl Inference {
+    /// Replaces the variables of an annotation with variables of the
+    /// unifier, the same number standing for the same variable throughout
+    /// the term.
+    fn annotation(&mut self, ty: &Type) -> Type {
+        match ty {
+            Type::Base(_) => ty.clone(),
+            Type::Var(n) => {
+                let unifier = &mut self.unifier;
+                self.annotation_vars
+                    .entry(*n)
+                    .or_insert_with(|| unifier.fresh())
+                    .clone()
+            }
+            Type::Arrow(t1, t2) => Type::arrow(self.annotation(t1), self.annotation(t2)),
+        }
+    }
+
+    fn infer<'a>(&mut self, term: &TypedTerm<'a>) -> Result<Type, TypeError<'a>> {
+        match term {
+            TypedTerm::Var(name, span) => self
+                .context
+                .iter()
+                .rev()
+                .find(|(v, _)| v == name)
+                .map(|(_, ty)| ty.clone())
+                .ok_or_else(|| TypeError::UnboundVariable {
+                    name: name.to_string(),
+                    span: *span,
+                }),
+            TypedTerm::Abs(name, annotation, body, _) => {
+                let ty = match annotation {
+                    Some(ty) => self.annotation(ty),
+                    None => self.unifier.fresh(),
+                };
+                self.context.push((name.clone(), ty.clone()));
+                let body = self.infer(body);
+                self.context.pop();
+                Ok(Type::arrow(ty, body?))
+            }
+            TypedTerm::Apply(t1, t2, span) => {
+                let function = self.infer(t1)?;
+                let argument = self.infer(t2)?;
+                match self.unifier.shallow(&function) {
+                    Type::Arrow(parameter, result) => {
+                        self.unifier.unify(&parameter, &argument).map_err(|err| {
+                            self.unifier.error(err, &parameter, &argument, t2.span())
+                        })?;
+                        Ok((*result).clone())
+                    }
+                    _ => {
+                        let result = self.unifier.fresh();
+                        let expected = Type::arrow(argument, result.clone());
+                        self.unifier
+                            .unify(&function, &expected)
+                            .map_err(|err| self.unifier.error(err, &expected, &function, *span))?;
+                        Ok(result)
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Infers the principal simple type of a closed term. Unannotated binders
+/// get the most general type the term allows, left as type variables.
+pub fn infer<'a>(term: &TypedTerm<'a>) -> Result<Type, TypeError<'a>> {
+    let mut inference = Inference::default();
+    let ty = inference.infer(term)?;
+    Ok(inference.unifier.resolve(&ty).normalized())
+}
+
+fn instantiate(pattern: &Type, target: &Type, bindings: &mut HashMap<usize, Type>) -> bool {
+    match (pattern, target) {
+        (Type::Var(n), _) => bindings.entry(*n).or_insert_with(|| target.clone()) == target,
+        (Type::Base(a), Type::Base(b)) => a == b,
+        (Type::Arrow(a1, r1), Type::Arrow(a2, r2)) => {
+            instantiate(a1, a2, bindings) && instantiate(r1, r2, bindings)
+        }
+        _ => false,
+    }
+}
+
+/// Checks that a closed term has type `expected`, that is, that `expected`
+/// is an instance of its principal type. Variables in `expected` only match
+/// themselves.
+pub fn check<'a>(term: &TypedTerm<'a>, expected: &Type) -> Result<(), TypeError<'a>> {
+    let found = infer(term)?;
+    if instantiate(&found, expected, &mut HashMap::new()) {
+        Ok(())
+    } else {
+        Err(TypeError::Mismatch {
+            expected: expected.clone(),
+            found,
+            span: term.span(),
+        })
+    }
+}
@@ -0,0 +1,134 @@
+use crate::{Type, TypedTerm};
+use lambubu::{compile::CompilationError, env::TermEnvironment};
+use pest::Parser;
+use pest_derive::Parser;
+use std::{collections::HashMap, rc::Rc};
+
+#[derive(Parser)]
+#[grammar = "base.pest"]
+#[grammar = "typed.pest"]
+struct TypedParser;
+
+type Pair<'a> = pest::iterators::Pair<'a, Rule>;
+
+/// The definitions of a file in order, and its terms.
+#[derive(Clone, Debug)]
+pub struct TypedFile<'a> {
+    pub definitions: Vec<(&'a str, TypedTerm<'a>)>,
+    pub terms: Vec<TypedTerm<'a>>,
+}
+
+fn compile_type(pair: Pair<'_>) -> Type {
+    match pair.as_rule() {
+        Rule::BaseType => Type::base(pair.as_str()),
+        Rule::Type => {
+            let mut atoms: Vec<_> = pair.into_inner().map(compile_type).collect();
+            let last = atoms.pop().unwrap();
+            atoms
+                .into_iter()
+                .rev()
+                .fold(last, |result, atom| Type::arrow(atom, result))
+        }
+        _ => unreachable!(),
+    }
+}
+
+fn compile_pair<'a>(
+    pair: Pair<'a>,
+    definitions: &HashMap<&'a str, TypedTerm<'a>>,
+    env: &impl TermEnvironment,
+) -> Result<TypedTerm<'a>, CompilationError<'a>> {
+    let span = pair.as_span();
+    match pair.as_rule() {
+        Rule::Variable => Ok(TypedTerm::Var(Rc::from(pair.as_str()), span)),
+        Rule::Abstraction => {
+            let mut inner = pair.into_inner();
+            let var_name = inner.next().unwrap().as_str();
+            let mut next = inner.next().unwrap();
+            let annotation = match next.as_rule() {
+                Rule::Type => {
+                    let annotation = compile_type(next);
+                    next = inner.next().unwrap();
+                    Some(annotation)
+                }
+                _ => None,
+            };
+            let term = compile_pair(next, definitions, env)?;
+
+            Ok(TypedTerm::Abs(
+                Rc::from(var_name),
+                annotation,
+                Rc::new(term),
+                span,
+            ))
+        }
+        Rule::Application => {
+            let mut inner = pair.into_inner();
+            let first = inner.next().unwrap();
+            let start = first.as_span().start_pos();
+            let mut result = compile_pair(first, definitions, env)?;
+
+            for i in inner {
+                let span = start.span(&i.as_span().end_pos());
+                let argument = compile_pair(i, definitions, env)?;
+                result = TypedTerm::Apply(Rc::new(result), Rc::new(argument), span);
+            }
+
+            Ok(result)
+        }
+        Rule::MacrosName => match definitions.get(pair.as_str()) {
+            Some(term) => Ok(term.clone()),
+            None => env
+                .resolve_term(pair.as_str())
+                .map(|term| TypedTerm::from_term(&term, span))
+                .ok_or(CompilationError::UnknownMacros {
+                    macro_name: pair.as_str().to_owned(),
+                    span,
+                }),
+        },
+        Rule::Definition => Err(CompilationError::UnexpectedDefinition(span)),
+        _ => unreachable!(),
+    }
+}
+
+pub fn compile_typed_term<'a>(
+    input: &'a str,
+    env: &impl TermEnvironment,
+) -> Result<TypedTerm<'a>, CompilationError<'a>> {
+    let parse_result = TypedParser::parse(Rule::Term, input)
+        .unwrap()
+        .next()
+        .unwrap();
+
+    compile_pair(parse_result, &HashMap::new(), env)
+}
+
+/// Compiles a file, resolving macros first to its own definitions and then
+/// to `env`, whose terms are unannotated.
+pub fn compile_typed_file<'a>(
+    input: &'a str,
+    env: &impl TermEnvironment,
+) -> Result<TypedFile<'a>, CompilationError<'a>> {
+    let parse_result = TypedParser::parse(Rule::File, input).unwrap();
+    let mut definitions = HashMap::new();
+    let mut result = TypedFile {
+        definitions: Vec::new(),
+        terms: Vec::new(),
+    };
+
+    for pair in parse_result {
+        match pair.as_rule() {
+            Rule::Definition => {
+                let mut inner = pair.into_inner();
+                let macro_name = inner.next().unwrap().as_str();
+                let term = compile_pair(inner.next().unwrap(), &definitions, env)?;
+                definitions.insert(macro_name, term.clone());
+                result.definitions.push((macro_name, term));
+            }
+            Rule::EOI => {}
+            _ => result.terms.push(compile_pair(pair, &definitions, env)?),
+        }
+    }
+
+    Ok(result)
+}
@@ -0,0 +1,151 @@
+use crate::{
+    Type,
+    check::{Unifier, UnifyError},
+};
+use lambubu::Term;
+use std::{
+    collections::{BTreeSet, HashMap},
+    fmt::{self, Display, Formatter},
+    rc::Rc,
+};
+
+/// A type with all of its variables universally quantified.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Scheme(pub Type);
+
+impl Display for Scheme {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let vars = self.vars();
+        if !vars.is_empty() {
+            let vars: Vec<_> = vars
+                .into_iter()
+                .map(|var| Type::Var(var).to_string())
+                .collect();
+            write!(f, "∀{}. ", vars.join(" "))?;
+        }
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Scheme {
+    pub fn vars(&self) -> BTreeSet<usize> {
+        fn collect(ty: &Type, vars: &mut BTreeSet<usize>) {
+            match ty {
+                Type::Base(_) => {}
+                Type::Var(n) => {
+                    vars.insert(*n);
+                }
+                Type::Arrow(t1, t2) => {
+                    collect(t1, vars);
+                    collect(t2, vars);
+                }
+            }
+        }
+        let mut vars = BTreeSet::new();
+        collect(&self.0, &mut vars);
+        vars
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum HmError {
+    #[error("Unbound variable {0}")]
+    UnboundVariable(String),
+    #[error("Cannot unify {expected} with {found} in {term}")]
+    Mismatch {
+        expected: Type,
+        found: Type,
+        term: Term,
+    },
+    #[error("Occurs check failed: {var} occurs in {ty} in {term}")]
+    InfiniteType { var: Type, ty: Type, term: Term },
+}
+
+struct AlgorithmW<'e> {
+    unifier: Unifier,
+    env: &'e HashMap<Rc<str>, Scheme>,
+    context: Vec<(Rc<str>, Type)>,
+}
+
+impl AlgorithmW<'_> {
+    fn instantiate(&mut self, scheme: &Scheme) -> Type {
+        let fresh: HashMap<usize, Type> = scheme
+            .vars()
+            .into_iter()
+            .map(|var| (var, self.unifier.fresh()))
+            .collect();
+        substitute(&scheme.0, &fresh)
+    }
+
+    fn unify(&mut self, expected: &Type, found: &Type, term: &Term) -> Result<(), HmError> {
+        self.unifier.unify(expected, found).map_err(|err| {
+            let mut names = HashMap::new();
+            match err {
+                UnifyError::Mismatch => HmError::Mismatch {
+                    expected: self.unifier.resolve(expected).renamed(&mut names),
+                    found: self.unifier.resolve(found).renamed(&mut names),
+                    term: term.clone(),
+                },
+                UnifyError::Occurs(var, ty) => HmError::InfiniteType {
+                    var: Type::Var(var).renamed(&mut names),
+                    ty: ty.renamed(&mut names),
+                    term: term.clone(),
+                },
+            }
+        })
+    }
+
+    fn infer(&mut self, term: &Term) -> Result<Type, HmError> {
+        match term {
+            Term::Var(name) => {
+                if let Some((_, ty)) = self.context.iter().rev().find(|(v, _)| v == name) {
+                    return Ok(ty.clone());
+                }
+                match self.env.get(name) {
+                    Some(scheme) => Ok(self.instantiate(scheme)),
+                    None => Err(HmError::UnboundVariable(name.to_string())),
+                }
+            }
+            Term::Abs(name, body) => {
+                let ty = self.unifier.fresh();
+                self.context.push((name.clone(), ty.clone()));
+                let body = self.infer(body);
+                self.context.pop();
+                Ok(Type::arrow(ty, body?))
+            }
+            Term::Apply(t1, t2) => {
+                let function = self.infer(t1)?;
+                let argument = self.infer(t2)?;
+                let result = self.unifier.fresh();
+                self.unify(&Type::arrow(argument, result.clone()), &function, term)?;
+                Ok(result)
+            }
+        }
+    }
+}
+
+fn substitute(ty: &Type, fresh: &HashMap<usize, Type>) -> Type {
+    match ty {
+        Type::Base(_) => ty.clone(),
+        Type::Var(n) => fresh.get(n).cloned().unwrap_or_else(|| ty.clone()),
+        Type::Arrow(t1, t2) => Type::arrow(substitute(t1, fresh), substitute(t2, fresh)),
+    }
+}
+
+/// Infers the principal type scheme of a closed term with algorithm W.
+pub fn principal_type(term: &Term) -> Result<Scheme, HmError> {
+    principal_type_in(term, &HashMap::new())
+}
+
+/// Infers the principal type scheme of `term`, whose free variables are
+/// typed by `env`. Each occurrence of a free variable gets a fresh instance
+/// of its scheme, so it can be used at several types.
+pub fn principal_type_in(term: &Term, env: &HashMap<Rc<str>, Scheme>) -> Result<Scheme, HmError> {
+    let mut w = AlgorithmW {
+        unifier: Unifier::default(),
+        env,
+        context: Vec::new(),
+    };
+    let ty = w.infer(term)?;
+    Ok(Scheme(w.unifier.resolve(&ty).normalized()))
+}
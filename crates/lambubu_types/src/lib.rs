@@ -0,0 +1,12 @@
+pub mod check;
+pub mod compile;
+pub mod hm;
+pub mod syntax;
+pub mod system_f;
+pub mod types;
+
+pub use check::{TypeError, check, infer};
+pub use compile::{TypedFile, compile_typed_file, compile_typed_term};
+pub use hm::{HmError, Scheme, principal_type, principal_type_in};
+pub use syntax::TypedTerm;
+pub use types::Type;
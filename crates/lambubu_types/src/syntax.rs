@@ -0,0 +1,61 @@
+use crate::Type;
+use lambubu::Term;
+use pest::Span;
+use std::{
+    fmt::{self, Display, Formatter},
+    rc::Rc,
+};
+
+/// A term whose abstractions may carry a type annotation, keeping the source
+/// span of every node for error reporting.
+#[derive(Clone, Debug)]
+pub enum TypedTerm<'a> {
+    Var(Rc<str>, Span<'a>),
+    Abs(Rc<str>, Option<Type>, Rc<TypedTerm<'a>>, Span<'a>),
+    Apply(Rc<TypedTerm<'a>>, Rc<TypedTerm<'a>>, Span<'a>),
+}
+
+impl Display for TypedTerm<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            TypedTerm::Var(v, _) => write!(f, "{v}"),
+            TypedTerm::Abs(v, Some(ty), body, _) => write!(f, "λ{v}:{ty}.{body}"),
+            TypedTerm::Abs(v, None, body, _) => write!(f, "λ{v}.{body}"),
+            TypedTerm::Apply(t1, t2, _) => write!(f, "({t1} {t2})"),
+        }
+    }
+}
+
+impl<'a> TypedTerm<'a> {
+    pub fn span(&self) -> Span<'a> {
+        match self {
+            TypedTerm::Var(_, span)
+            | TypedTerm::Abs(_, _, _, span)
+            | TypedTerm::Apply(_, _, span) => *span,
+        }
+    }
+
+    /// An unannotated copy of `term` with every node at `span`.
+    pub fn from_term(term: &Term, span: Span<'a>) -> Self {
+        match term {
+            Term::Var(v) => TypedTerm::Var(v.clone(), span),
+            Term::Abs(v, body) => {
+                TypedTerm::Abs(v.clone(), None, Rc::new(Self::from_term(body, span)), span)
+            }
+            Term::Apply(t1, t2) => TypedTerm::Apply(
+                Rc::new(Self::from_term(t1, span)),
+                Rc::new(Self::from_term(t2, span)),
+                span,
+            ),
+        }
+    }
+
+    /// Drops the annotations.
+    pub fn erase(&self) -> Term {
+        match self {
+            TypedTerm::Var(v, _) => Term::Var(v.clone()),
+            TypedTerm::Abs(v, _, body, _) => Term::Abs(v.clone(), Rc::new(body.erase())),
+            TypedTerm::Apply(t1, t2, _) => Term::Apply(Rc::new(t1.erase()), Rc::new(t2.erase())),
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use super::{Term, Type, types::fresh_name};
+use std::{borrow::Cow, rc::Rc};
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum TypeError {
+    #[error("Unbound variable {0}")]
+    UnboundVariable(String),
+    #[error("Unbound type variable {0}")]
+    UnboundTypeVariable(String),
+    #[error("Type mismatch: expected {expected}, found {found} in {term}")]
+    Mismatch {
+        expected: Type,
+        found: Type,
+        term: Term,
+    },
+    #[error("{term} has type {ty}, which is not a function")]
+    NotAFunction { ty: Type, term: Term },
+    #[error("{term} has type {ty}, which is not polymorphic")]
+    NotPolymorphic { ty: Type, term: Term },
+}
+
+/// The term variables in scope with their types, and the type variables.
+#[derive(Default)]
+struct Checker {
+    context: Vec<(Rc<str>, Type)>,
+    types: Vec<Rc<str>>,
+}
+
+impl Checker {
+    fn well_formed(&self, ty: &Type) -> Result<(), TypeError> {
+        match ty
+            .free_vars()
+            .into_iter()
+            .find(|var| !self.types.contains(var))
+        {
+            Some(var) => Err(TypeError::UnboundTypeVariable(var.to_string())),
+            None => Ok(()),
+        }
+    }
+
+    fn type_of(&mut self, term: &Term) -> Result<Type, TypeError> {
+        match term {
+            Term::Var(name) => self
+                .context
+                .iter()
+                .rev()
+                .find(|(v, _)| v == name)
+                .map(|(_, ty)| ty.clone())
+                .ok_or_else(|| TypeError::UnboundVariable(name.to_string())),
+            Term::Abs(name, ty, body) => {
+                self.well_formed(ty)?;
+                self.context.push((name.clone(), ty.clone()));
+                let body = self.type_of(body);
+                self.context.pop();
+                Ok(Type::arrow(ty.clone(), body?))
+            }
+            Term::Apply(t1, t2) => {
+                let function = self.type_of(t1)?;
+                let argument = self.type_of(t2)?;
+                match function {
+                    Type::Arrow(parameter, result) if *parameter == argument => {
+                        Ok((*result).clone())
+                    }
+                    Type::Arrow(parameter, _) => Err(TypeError::Mismatch {
+                        expected: (*parameter).clone(),
+                        found: argument,
+                        term: (**t2).clone(),
+                    }),
+                    ty => Err(TypeError::NotAFunction {
+                        ty,
+                        term: (**t1).clone(),
+                    }),
+                }
+            }
+            Term::TypeAbs(var, body) => {
+                // A type variable already in scope may appear in the types of
+                // the context, so the inner one is renamed apart.
+                let (var, body) = if self.types.contains(var) {
+                    let fresh = fresh_name(var, |n| {
+                        self.types.iter().any(|t| &**t == n) || body.mentions_type(n)
+                    });
+                    let body = body.substitute_type(var, &Type::Var(fresh.clone()));
+                    (fresh, Cow::Owned(body))
+                } else {
+                    (var.clone(), Cow::Borrowed(body.as_ref()))
+                };
+                self.types.push(var.clone());
+                let body = self.type_of(&body);
+                self.types.pop();
+                Ok(Type::forall(var, body?))
+            }
+            Term::TypeApply(t, ty) => {
+                self.well_formed(ty)?;
+                match self.type_of(t)? {
+                    Type::Forall(var, body) => Ok(body.substitute(&var, ty)),
+                    found => Err(TypeError::NotPolymorphic {
+                        ty: found,
+                        term: (**t).clone(),
+                    }),
+                }
+            }
+        }
+    }
+}
+
+/// Computes the type of a closed term.
+pub fn type_of(term: &Term) -> Result<Type, TypeError> {
+    Checker::default().type_of(term)
+}
+
+/// Checks that a closed term has type `expected`, up to renaming of bound
+/// type variables.
+pub fn check(term: &Term, expected: &Type) -> Result<(), TypeError> {
+    let found = type_of(term)?;
+    if found == *expected {
+        Ok(())
+    } else {
+        Err(TypeError::Mismatch {
+            expected: expected.clone(),
+            found,
+            term: term.clone(),
+        })
+    }
+}
@@ -0,0 +1,13 @@
+type Bool = ∀a.a → a → a
+type Nat = ∀a.(a → a) → a → a
+
+TRUE :: Λa.λt:a.λf:a.t
+FALSE :: Λa.λt:a.λf:a.f
+IF :: Λa.λb:Bool.λt:a.λf:a.(b [a] t f)
+NOT :: λb:Bool.(b [Bool] FALSE TRUE)
+
+ZERO :: Λa.λf:a → a.λx:a.x
+SUCC :: λn:Nat.Λa.λf:a → a.λx:a.(f (n [a] f x))
+ADD :: λm:Nat.λn:Nat.Λa.λf:a → a.λx:a.(m [a] f (n [a] f x))
+MUL :: λm:Nat.λn:Nat.Λa.λf:a → a.(m [a] (n [a] f))
+ISZERO :: λn:Nat.(n [Bool] λb:Bool.FALSE TRUE)
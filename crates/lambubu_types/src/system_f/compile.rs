@@ -0,0 +1,197 @@
+use super::{Term, Type};
+use lambubu::compile::CompilationError;
+use pest::Parser;
+use pest_derive::Parser;
+use std::{collections::HashMap, rc::Rc};
+
+#[derive(Parser)]
+#[grammar = "base.pest"]
+#[grammar = "system_f.pest"]
+struct SystemFParser;
+
+type Pair<'a> = pest::iterators::Pair<'a, Rule>;
+
+/// Booleans and natural numbers with their `Bool` and `Nat` types.
+pub const CHURCH_PRELUDE: &str = include_str!("church.f");
+
+/// Typed definitions and type aliases that System F sources can refer to.
+#[derive(Clone, Debug, Default)]
+pub struct Environment {
+    terms: HashMap<String, Term>,
+    order: Vec<String>,
+    types: HashMap<String, Type>,
+    numerals: bool,
+}
+
+impl Environment {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The definitions of [`CHURCH_PRELUDE`], with every number resolving to
+    /// its numeral of type `Nat`.
+    pub fn church() -> Self {
+        let mut env = Self {
+            numerals: true,
+            ..Self::default()
+        };
+        compile_file(CHURCH_PRELUDE, &mut env).unwrap();
+        env
+    }
+
+    pub fn resolve_term(&self, name: &str) -> Option<Term> {
+        if let Some(term) = self.terms.get(name) {
+            return Some(term.clone());
+        }
+        let number: u32 = name.parse().ok().filter(|_| self.numerals)?;
+        Some(numeral(number))
+    }
+
+    pub fn resolve_type(&self, name: &str) -> Option<Type> {
+        self.types.get(name).cloned()
+    }
+
+    /// Redefining a name replaces its term but keeps its position in
+    /// [`Environment::definitions`].
+    pub fn add_term(&mut self, name: String, term: Term) {
+        if self.terms.insert(name.clone(), term).is_none() {
+            self.order.push(name);
+        }
+    }
+
+    pub fn add_type(&mut self, name: String, ty: Type) {
+        self.types.insert(name, ty);
+    }
+
+    /// Terms added with [`Environment::add_term`], in the order they were
+    /// first defined.
+    pub fn definitions(&self) -> Vec<(&str, &Term)> {
+        self.order
+            .iter()
+            .map(|name| (name.as_str(), &self.terms[name]))
+            .collect()
+    }
+}
+
+/// `Λa.λf:a → a.λx:a.(f … (f x))`
+fn numeral(number: u32) -> Term {
+    let a = Type::var("a");
+    let mut body = Term::Var(Rc::from("x"));
+    for _ in 0..number {
+        body = Term::Apply(Rc::new(Term::Var(Rc::from("f"))), Rc::new(body));
+    }
+    let body = Term::Abs(Rc::from("x"), a.clone(), Rc::new(body));
+    let body = Term::Abs(Rc::from("f"), Type::arrow(a.clone(), a), Rc::new(body));
+    Term::TypeAbs(Rc::from("a"), Rc::new(body))
+}
+
+fn compile_type(pair: Pair<'_>, env: &Environment) -> Type {
+    match pair.as_rule() {
+        Rule::Variable => Type::var(pair.as_str()),
+        Rule::TypeName => env
+            .resolve_type(pair.as_str())
+            .unwrap_or_else(|| Type::base(pair.as_str())),
+        Rule::Forall => {
+            let mut inner: Vec<_> = pair.into_inner().collect();
+            let body = compile_type(inner.pop().unwrap(), env);
+            inner
+                .into_iter()
+                .rev()
+                .fold(body, |body, var| Type::forall(Rc::from(var.as_str()), body))
+        }
+        Rule::Type => {
+            let mut atoms: Vec<_> = pair.into_inner().map(|p| compile_type(p, env)).collect();
+            let last = atoms.pop().unwrap();
+            atoms
+                .into_iter()
+                .rev()
+                .fold(last, |result, atom| Type::arrow(atom, result))
+        }
+        _ => unreachable!(),
+    }
+}
+
+fn compile_pair<'a>(pair: Pair<'a>, env: &Environment) -> Result<Term, CompilationError<'a>> {
+    match pair.as_rule() {
+        Rule::Variable => Ok(Term::Var(Rc::from(pair.as_str()))),
+        Rule::Abstraction => {
+            let mut inner = pair.into_inner();
+            let var_name = inner.next().unwrap().as_str();
+            let ty = compile_type(inner.next().unwrap(), env);
+            let term = compile_pair(inner.next().unwrap(), env)?;
+
+            Ok(Term::Abs(Rc::from(var_name), ty, Rc::new(term)))
+        }
+        Rule::TypeAbstraction => {
+            let mut inner: Vec<_> = pair.into_inner().collect();
+            let term = compile_pair(inner.pop().unwrap(), env)?;
+
+            Ok(inner.into_iter().rev().fold(term, |term, var| {
+                Term::TypeAbs(Rc::from(var.as_str()), Rc::new(term))
+            }))
+        }
+        Rule::Application => {
+            let mut inner = pair.into_inner();
+            let mut result = compile_pair(inner.next().unwrap(), env)?;
+
+            for i in inner {
+                result = match i.as_rule() {
+                    Rule::Type => Term::TypeApply(Rc::new(result), compile_type(i, env)),
+                    _ => Term::Apply(Rc::new(result), Rc::new(compile_pair(i, env)?)),
+                };
+            }
+
+            Ok(result)
+        }
+        Rule::MacrosName => {
+            env.resolve_term(pair.as_str())
+                .ok_or(CompilationError::UnknownMacros {
+                    macro_name: pair.as_str().to_owned(),
+                    span: pair.as_span(),
+                })
+        }
+        Rule::Definition | Rule::TypeDefinition => {
+            Err(CompilationError::UnexpectedDefinition(pair.as_span()))
+        }
+        _ => unreachable!(),
+    }
+}
+
+pub fn compile_term<'a>(input: &'a str, env: &Environment) -> Result<Term, CompilationError<'a>> {
+    let parse_result = SystemFParser::parse(Rule::Term, input)
+        .unwrap()
+        .next()
+        .unwrap();
+
+    compile_pair(parse_result, env)
+}
+
+/// Compiles a file, adding its definitions and type aliases to `env`.
+pub fn compile_file<'a>(
+    input: &'a str,
+    env: &mut Environment,
+) -> Result<Vec<Term>, CompilationError<'a>> {
+    let parse_result = SystemFParser::parse(Rule::File, input).unwrap();
+    let mut result = Vec::new();
+
+    for pair in parse_result {
+        match pair.as_rule() {
+            Rule::Definition => {
+                let mut inner = pair.into_inner();
+                let macro_name = inner.next().unwrap().as_str();
+                let term = compile_pair(inner.next().unwrap(), env)?;
+                env.add_term(macro_name.to_owned(), term);
+            }
+            Rule::TypeDefinition => {
+                let mut inner = pair.into_inner();
+                let type_name = inner.next().unwrap().as_str();
+                let ty = compile_type(inner.next().unwrap(), env);
+                env.add_type(type_name.to_owned(), ty);
+            }
+            Rule::EOI => {}
+            _ => result.push(compile_pair(pair, env)?),
+        }
+    }
+
+    Ok(result)
+}
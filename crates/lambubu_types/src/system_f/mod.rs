@@ -0,0 +1,12 @@
+//! System F: the polymorphic λ-calculus with explicit type abstraction and
+//! application. Checked terms erase to untyped [`lambubu::Term`]s.
+
+mod check;
+mod compile;
+mod term;
+mod types;
+
+pub use check::{TypeError, check, type_of};
+pub use compile::{CHURCH_PRELUDE, Environment, compile_file, compile_term};
+pub use term::Term;
+pub use types::Type;
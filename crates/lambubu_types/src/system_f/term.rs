@@ -0,0 +1,83 @@
+use super::{Type, types::fresh_name};
+use std::{
+    fmt::{self, Display, Formatter},
+    rc::Rc,
+};
+
+/// A System F term: annotated abstractions, type abstractions `Λa.t` and
+/// type applications `(t [T])`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Term {
+    Var(Rc<str>),
+    Abs(Rc<str>, Type, Rc<Term>),
+    Apply(Rc<Term>, Rc<Term>),
+    TypeAbs(Rc<str>, Rc<Term>),
+    TypeApply(Rc<Term>, Type),
+}
+
+impl Display for Term {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Term::Var(v) => write!(f, "{v}"),
+            Term::Abs(v, ty, body) => write!(f, "λ{v}:{ty}.{body}"),
+            Term::Apply(t1, t2) => write!(f, "({t1} {t2})"),
+            Term::TypeAbs(v, body) => write!(f, "Λ{v}.{body}"),
+            Term::TypeApply(t, ty) => write!(f, "({t} [{ty}])"),
+        }
+    }
+}
+
+impl Term {
+    /// Drops the types, leaving an untyped term with the same reductions.
+    pub fn erase(&self) -> lambubu::Term {
+        match self {
+            Term::Var(v) => lambubu::Term::Var(v.clone()),
+            Term::Abs(v, _, body) => lambubu::Term::Abs(v.clone(), Rc::new(body.erase())),
+            Term::Apply(t1, t2) => lambubu::Term::Apply(Rc::new(t1.erase()), Rc::new(t2.erase())),
+            Term::TypeAbs(_, body) | Term::TypeApply(body, _) => body.erase(),
+        }
+    }
+
+    pub(crate) fn mentions_type(&self, name: &str) -> bool {
+        match self {
+            Term::Var(_) => false,
+            Term::Abs(_, ty, body) => ty.mentions(name) || body.mentions_type(name),
+            Term::Apply(t1, t2) => t1.mentions_type(name) || t2.mentions_type(name),
+            Term::TypeAbs(var, body) => &**var == name || body.mentions_type(name),
+            Term::TypeApply(t, ty) => t.mentions_type(name) || ty.mentions(name),
+        }
+    }
+
+    /// Replaces the type variable `var` in the annotations and type
+    /// arguments, renaming the type abstractions that would capture.
+    pub fn substitute_type(&self, var: &str, replacement: &Type) -> Term {
+        match self {
+            Term::Var(_) => self.clone(),
+            Term::Abs(v, ty, body) => Term::Abs(
+                v.clone(),
+                ty.substitute(var, replacement),
+                Rc::new(body.substitute_type(var, replacement)),
+            ),
+            Term::Apply(t1, t2) => Term::Apply(
+                Rc::new(t1.substitute_type(var, replacement)),
+                Rc::new(t2.substitute_type(var, replacement)),
+            ),
+            Term::TypeAbs(name, _) if &**name == var => self.clone(),
+            Term::TypeAbs(name, body) if replacement.free_vars().contains(name) => {
+                let fresh = fresh_name(name, |n| {
+                    n == var || replacement.mentions(n) || body.mentions_type(n)
+                });
+                let body = body.substitute_type(name, &Type::Var(fresh.clone()));
+                Term::TypeAbs(fresh, Rc::new(body.substitute_type(var, replacement)))
+            }
+            Term::TypeAbs(name, body) => Term::TypeAbs(
+                name.clone(),
+                Rc::new(body.substitute_type(var, replacement)),
+            ),
+            Term::TypeApply(t, ty) => Term::TypeApply(
+                Rc::new(t.substitute_type(var, replacement)),
+                ty.substitute(var, replacement),
+            ),
+        }
+    }
+}
@@ -0,0 +1,142 @@
+use std::{
+    collections::HashSet,
+    fmt::{self, Display, Formatter},
+    rc::Rc,
+};
+
+const SYMBOL_ARROW: char = '→';
+const SYMBOL_FORALL: char = '∀';
+
+/// System F types. Uppercase names that are not aliases stay opaque base
+/// types. Equality is α-equivalence.
+#[derive(Clone, Debug)]
+pub enum Type {
+    Base(Rc<str>),
+    Var(Rc<str>),
+    Arrow(Rc<Type>, Rc<Type>),
+    Forall(Rc<str>, Rc<Type>),
+}
+
+impl Display for Type {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Type::Base(name) | Type::Var(name) => write!(f, "{name}"),
+            Type::Arrow(t1, t2) if matches!(t1.as_ref(), Type::Arrow(..) | Type::Forall(..)) => {
+                write!(f, "({t1}) {SYMBOL_ARROW} {t2}")
+            }
+            Type::Arrow(t1, t2) => write!(f, "{t1} {SYMBOL_ARROW} {t2}"),
+            Type::Forall(var, body) => {
+                write!(f, "{SYMBOL_FORALL}{var}")?;
+                let mut body = body;
+                while let Type::Forall(var, inner) = body.as_ref() {
+                    write!(f, " {var}")?;
+                    body = inner;
+                }
+                write!(f, ". {body}")
+            }
+        }
+    }
+}
+
+impl PartialEq for Type {
+    fn eq(&self, other: &Type) -> bool {
+        alpha_eq(self, other, &mut Vec::new())
+    }
+}
+
+impl Eq for Type {}
+
+fn alpha_eq(t1: &Type, t2: &Type, bound: &mut Vec<(Rc<str>, Rc<str>)>) -> bool {
+    match (t1, t2) {
+        (Type::Base(a), Type::Base(b)) => a == b,
+        (Type::Var(a), Type::Var(b)) => match bound.iter().rev().find(|(x, y)| x == a || y == b) {
+            Some((x, y)) => x == a && y == b,
+            None => a == b,
+        },
+        (Type::Arrow(a1, r1), Type::Arrow(a2, r2)) => {
+            alpha_eq(a1, a2, bound) && alpha_eq(r1, r2, bound)
+        }
+        (Type::Forall(a, b1), Type::Forall(b, b2)) => {
+            bound.push((a.clone(), b.clone()));
+            let result = alpha_eq(b1, b2, bound);
+            bound.pop();
+            result
+        }
+        _ => false,
+    }
+}
+
+/// Primes `name` until it is not `taken`.
+pub(crate) fn fresh_name(name: &str, taken: impl Fn(&str) -> bool) -> Rc<str> {
+    let mut fresh = name.to_owned();
+    while taken(&fresh) {
+        fresh.push('\'');
+    }
+    Rc::from(fresh)
+}
+
+impl Type {
+    pub fn base(name: &str) -> Type {
+        Type::Base(Rc::from(name))
+    }
+
+    pub fn var(name: &str) -> Type {
+        Type::Var(Rc::from(name))
+    }
+
+    pub fn arrow(t1: Type, t2: Type) -> Type {
+        Type::Arrow(Rc::new(t1), Rc::new(t2))
+    }
+
+    pub fn forall(var: Rc<str>, body: Type) -> Type {
+        Type::Forall(var, Rc::new(body))
+    }
+
+    pub fn free_vars(&self) -> HashSet<Rc<str>> {
+        match self {
+            Type::Base(_) => HashSet::new(),
+            Type::Var(name) => HashSet::from([name.clone()]),
+            Type::Arrow(t1, t2) => &t1.free_vars() | &t2.free_vars(),
+            Type::Forall(var, body) => {
+                let mut vars = body.free_vars();
+                vars.remove(var);
+                vars
+            }
+        }
+    }
+
+    /// Whether `name` appears anywhere in the type, bound or free.
+    pub(crate) fn mentions(&self, name: &str) -> bool {
+        match self {
+            Type::Base(_) => false,
+            Type::Var(var) => &**var == name,
+            Type::Arrow(t1, t2) => t1.mentions(name) || t2.mentions(name),
+            Type::Forall(var, body) => &**var == name || body.mentions(name),
+        }
+    }
+
+    /// Replaces the free occurrences of `var` by `replacement`, renaming the
+    /// binders that would capture its variables.
+    pub fn substitute(&self, var: &str, replacement: &Type) -> Type {
+        match self {
+            Type::Base(_) => self.clone(),
+            Type::Var(name) if &**name == var => replacement.clone(),
+            Type::Var(_) => self.clone(),
+            Type::Arrow(t1, t2) => Type::arrow(
+                t1.substitute(var, replacement),
+                t2.substitute(var, replacement),
+            ),
+            Type::Forall(name, _) if &**name == var => self.clone(),
+            Type::Forall(name, body) if replacement.free_vars().contains(name) => {
+                let fresh = fresh_name(name, |n| {
+                    n == var || replacement.mentions(n) || body.mentions(n)
+                });
+                let body = body.substitute(name, &Type::Var(fresh.clone()));
+                Type::forall(fresh, body.substitute(var, replacement))
+            }
+            Type::Forall(name, body) => {
+                Type::forall(name.clone(), body.substitute(var, replacement))
+            }
+        }
+    }
+}
@@ -0,0 +1,64 @@
+use std::{
+    collections::HashMap,
+    fmt::{self, Display, Formatter},
+    rc::Rc,
+};
+
+const SYMBOL_ARROW: char = '→';
+
+/// Simple types. Variables stand for any type and come from inference;
+/// they are displayed as `a`, `b`, ….
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Type {
+    Base(Rc<str>),
+    Var(usize),
+    Arrow(Rc<Type>, Rc<Type>),
+}
+
+impl Display for Type {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Type::Base(name) => write!(f, "{name}"),
+            Type::Var(n) if *n < 26 => write!(f, "{}", (b'a' + *n as u8) as char),
+            Type::Var(n) => write!(f, "t{n}"),
+            Type::Arrow(t1, t2) if matches!(t1.as_ref(), Type::Arrow(_, _)) => {
+                write!(f, "({t1}) {SYMBOL_ARROW} {t2}")
+            }
+            Type::Arrow(t1, t2) => write!(f, "{t1} {SYMBOL_ARROW} {t2}"),
+        }
+    }
+}
+
+impl Type {
+    pub fn base(name: &str) -> Type {
+        Type::Base(Rc::from(name))
+    }
+
+    pub fn arrow(t1: Type, t2: Type) -> Type {
+        Type::Arrow(Rc::new(t1), Rc::new(t2))
+    }
+
+    pub fn occurs(&self, var: usize) -> bool {
+        match self {
+            Type::Base(_) => false,
+            Type::Var(n) => *n == var,
+            Type::Arrow(t1, t2) => t1.occurs(var) || t2.occurs(var),
+        }
+    }
+
+    /// Renumbers type variables from 0 in order of appearance.
+    pub fn normalized(&self) -> Type {
+        self.renamed(&mut HashMap::new())
+    }
+
+    pub(crate) fn renamed(&self, names: &mut HashMap<usize, usize>) -> Type {
+        match self {
+            Type::Base(_) => self.clone(),
+            Type::Var(n) => {
+                let next = names.len();
+                Type::Var(*names.entry(*n).or_insert(next))
+            }
+            Type::Arrow(t1, t2) => Type::arrow(t1.renamed(names), t2.renamed(names)),
+        }
+    }
+}
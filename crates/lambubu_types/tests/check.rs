@@ -0,0 +1,127 @@
+use lambubu::RegistryEnvironment;
+use lambubu_church::ChurchEnvironment;
+use lambubu_types::{Type, TypeError, TypedTerm, check, compile_typed_term, infer};
+use std::rc::Rc;
+
+fn parse(input: &str) -> TypedTerm<'_> {
+    compile_typed_term(input, &ChurchEnvironment).unwrap()
+}
+
+fn type_of(input: &str) -> String {
+    infer(&parse(input)).unwrap().to_string()
+}
+
+fn a() -> Type {
+    Type::base("A")
+}
+
+#[test]
+fn check_annotated_terms() {
+    assert_eq!(type_of("λx:A.x"), "A → A");
+    assert_eq!(type_of("λf:A → B.λx:A.(f x)"), "(A → B) → A → B");
+    assert_eq!(type_of("λx:A.λy:B.x"), "A → B → A");
+}
+
+#[test]
+fn check_infers_unannotated_terms() {
+    assert_eq!(type_of("λx.x"), "a → a");
+    assert_eq!(type_of("λx.λy.x"), "a → b → a");
+    assert_eq!(
+        type_of("λx.λy.λz.((x z) (y z))"),
+        "(a → b → c) → (a → b) → a → c"
+    );
+}
+
+#[test]
+fn check_annotations_constrain_inference() {
+    assert_eq!(type_of("λf.λx:A.(f x)"), "(A → a) → A → a");
+    assert_eq!(type_of("(λx.x λy:B.y)"), "B → B");
+}
+
+#[test]
+fn check_church_numerals() {
+    assert_eq!(type_of("2"), "(a → a) → a → a");
+    assert_eq!(type_of("(SUCC 2)"), "(a → a) → a → a");
+}
+
+#[test]
+fn check_against_expected_type() {
+    let id = parse("λx.x");
+    assert!(check(&id, &Type::arrow(a(), a())).is_ok());
+    assert!(check(&id, &Type::arrow(a(), Type::base("B"))).is_err());
+    assert!(
+        check(
+            &parse("λx:A.x"),
+            &Type::arrow(Type::base("B"), Type::base("B"))
+        )
+        .is_err()
+    );
+}
+
+#[test]
+fn check_mismatch_points_to_argument() {
+    let input = "λf:A → A.λy:B.(f y)";
+    let env = RegistryEnvironment::new();
+    let Err(TypeError::Mismatch {
+        expected,
+        found,
+        span,
+    }) = infer(&compile_typed_term(input, &env).unwrap())
+    else {
+        panic!("expected a mismatch");
+    };
+    assert_eq!((expected, found), (a(), Type::base("B")));
+    assert_eq!(span.as_str(), "y");
+    assert_eq!(span.start(), 21);
+}
+
+#[test]
+fn check_applying_a_base_type() {
+    let Err(TypeError::Mismatch {
+        expected,
+        found,
+        span,
+    }) = infer(&parse("λx:A.(x x)"))
+    else {
+        panic!("expected a mismatch");
+    };
+    assert_eq!(expected.to_string(), "A → a");
+    assert_eq!(found, a());
+    assert_eq!(span.as_str(), "x x");
+}
+
+#[test]
+fn check_self_application_is_infinite() {
+    let Err(err @ TypeError::InfiniteType { .. }) = infer(&parse("λx.(x x)")) else {
+        panic!("expected an infinite type");
+    };
+    assert!(err.to_string().starts_with("Infinite type a = a → b"));
+}
+
+#[test]
+fn check_unbound_variable() {
+    let Err(TypeError::UnboundVariable { name, span }) = infer(&parse("λx.(x y)")) else {
+        panic!("expected an unbound variable");
+    };
+    assert_eq!(name, "y");
+    assert_eq!(span.start(), 7);
+}
+
+// λf:t9 → t9.λx:t9.(f x), built by hand since the parser has no type variables
+#[test]
+fn check_annotation_with_type_variables() {
+    let span = pest::Span::new("", 0, 0).unwrap();
+    let var = |name: &str| Rc::new(TypedTerm::Var(Rc::from(name), span));
+    let t9 = Type::Var(9);
+    let body = TypedTerm::Apply(var("f"), var("x"), span);
+    let term = TypedTerm::Abs(
+        Rc::from("f"),
+        Some(Type::arrow(t9.clone(), t9.clone())),
+        Rc::new(TypedTerm::Abs(Rc::from("x"), Some(t9), Rc::new(body), span)),
+        span,
+    );
+    assert_eq!(infer(&term).unwrap().to_string(), "(a → a) → a → a");
+
+    let term = TypedTerm::Abs(Rc::from("x"), Some(Type::Var(usize::MAX)), var("x"), span);
+    assert_eq!(infer(&term).unwrap().to_string(), "a → a");
+}
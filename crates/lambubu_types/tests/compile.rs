@@ -0,0 +1,117 @@
+use lambubu::RegistryEnvironment;
+use lambubu::Term;
+use lambubu::compile::{BASE_GRAMMAR, CompilationError, compile_term};
+use lambubu_church::ChurchEnvironment;
+use lambubu_types::{Type, TypedTerm, compile_typed_file, compile_typed_term};
+
+fn parse(input: &str) -> TypedTerm<'_> {
+    compile_typed_term(input, &RegistryEnvironment::new()).unwrap()
+}
+
+fn a() -> Type {
+    Type::base("A")
+}
+
+fn b() -> Type {
+    Type::base("B")
+}
+
+#[test]
+fn typed_annotation() {
+    let TypedTerm::Abs(name, Some(ty), _, _) = parse("\\x:A->B.x") else {
+        panic!("expected an annotated abstraction");
+    };
+    assert_eq!(&*name, "x");
+    assert_eq!(ty, Type::arrow(a(), b()));
+}
+
+#[test]
+fn typed_arrow_is_right_associative() {
+    let TypedTerm::Abs(_, Some(ty), _, _) = parse("λf:A → B → A.f") else {
+        panic!("expected an annotated abstraction");
+    };
+    assert_eq!(ty, Type::arrow(a(), Type::arrow(b(), a())));
+    let TypedTerm::Abs(_, Some(ty), _, _) = parse("λf:(A → B) → A.f") else {
+        panic!("expected an annotated abstraction");
+    };
+    assert_eq!(ty, Type::arrow(Type::arrow(a(), b()), a()));
+    assert_eq!(ty.to_string(), "(A → B) → A");
+}
+
+#[test]
+fn typed_annotations_are_optional() {
+    let TypedTerm::Abs(_, None, body, _) = parse("λx.λy:Nat.(x y)") else {
+        panic!("expected an unannotated abstraction");
+    };
+    assert!(
+        matches!(body.as_ref(), TypedTerm::Abs(_, Some(Type::Base(name)), _, _) if &**name == "Nat")
+    );
+}
+
+#[test]
+fn typed_erase_agrees_with_untyped_grammar() {
+    let env = RegistryEnvironment::new();
+    assert_eq!(
+        parse("λf:A → A.λx:A.(f (f x))").erase(),
+        compile_term("λf.λx.(f (f x))", &env).unwrap()
+    );
+}
+
+#[test]
+fn typed_display() {
+    assert_eq!(parse("λf:A → A.λx.(f x)").to_string(), "λf:A → A.λx.(f x)");
+}
+
+#[test]
+fn typed_spans() {
+    let input = "λx:A.(x y)";
+    let TypedTerm::Abs(_, _, body, span) = parse(input) else {
+        panic!("expected an abstraction");
+    };
+    assert_eq!(span.as_str(), input);
+    let TypedTerm::Apply(_, argument, span) = body.as_ref() else {
+        panic!("expected an application");
+    };
+    assert_eq!(span.as_str(), "x y");
+    assert_eq!(argument.span().start(), 9);
+}
+
+#[test]
+fn typed_file_definitions() {
+    let file = compile_typed_file(
+        "ID :: λx:A.x\nTWICE :: λf:A → A.λx:A.(f (f x))\n(TWICE ID)",
+        &RegistryEnvironment::new(),
+    )
+    .unwrap();
+    let names: Vec<_> = file.definitions.iter().map(|(name, _)| *name).collect();
+    assert_eq!(names, ["ID", "TWICE"]);
+    assert_eq!(file.terms.len(), 1);
+    assert_eq!(
+        file.terms[0].erase(),
+        Term::app(file.definitions[1].1.erase(), file.definitions[0].1.erase())
+    );
+}
+
+#[test]
+fn typed_untyped_macros() {
+    let file = compile_typed_file("(SUCC 2)", &ChurchEnvironment).unwrap();
+    assert_eq!(
+        file.terms[0].erase(),
+        Term::app(ChurchEnvironment.succ(), ChurchEnvironment.numeral(2))
+    );
+}
+
+#[test]
+fn typed_unknown_macro() {
+    let result = compile_typed_file("(λx:A.x UNKNOWN)", &RegistryEnvironment::new());
+    let Err(CompilationError::UnknownMacros { macro_name, span }) = result else {
+        panic!("expected an unknown macro");
+    };
+    assert_eq!(macro_name, "UNKNOWN");
+    assert_eq!(span.start(), 9);
+}
+
+#[test]
+fn base_grammar_matches_lambubu() {
+    assert_eq!(include_str!("../src/base.pest"), BASE_GRAMMAR);
+}
@@ -0,0 +1,94 @@
+use lambubu::RegistryEnvironment;
+use lambubu::Term;
+use lambubu::compile::compile_term;
+use lambubu_church::ChurchEnvironment;
+use lambubu_types::{HmError, Scheme, Type, principal_type, principal_type_in};
+use std::{collections::HashMap, rc::Rc};
+
+fn parse(input: &str) -> Term {
+    compile_term(input, &RegistryEnvironment::new()).unwrap()
+}
+
+fn type_of(term: &Term) -> String {
+    principal_type(term).unwrap().to_string()
+}
+
+#[test]
+fn hm_identity() {
+    assert_eq!(type_of(&parse("λx.x")), "∀a. a → a");
+}
+
+#[test]
+fn hm_const() {
+    assert_eq!(type_of(&parse("λx.λy.x")), "∀a b. a → b → a");
+}
+
+#[test]
+fn hm_church_numerals() {
+    let nat = "∀a. (a → a) → a → a";
+    assert_eq!(type_of(&parse("λf.λx.x")), "∀a b. a → b → b");
+    assert_eq!(
+        type_of(&ChurchEnvironment.numeral(1)),
+        "∀a b. (a → b) → a → b"
+    );
+    assert_eq!(type_of(&ChurchEnvironment.numeral(2)), nat);
+    assert_eq!(type_of(&ChurchEnvironment.numeral(5)), nat);
+}
+
+#[test]
+fn hm_succ() {
+    assert_eq!(
+        type_of(&ChurchEnvironment.succ()),
+        "∀a b c. ((a → b) → c → a) → (a → b) → c → b"
+    );
+}
+
+#[test]
+fn hm_add() {
+    assert_eq!(
+        type_of(&ChurchEnvironment.add()),
+        "∀a b c d. (a → b → c) → (a → c → d) → a → b → d"
+    );
+}
+
+#[test]
+fn hm_self_application_fails_occurs_check() {
+    let Err(HmError::InfiniteType { var, ty, term }) = principal_type(&parse("λx.(x x)")) else {
+        panic!("expected an infinite type");
+    };
+    assert_eq!(var, Type::Var(0));
+    assert_eq!(ty.to_string(), "a → b");
+    assert_eq!(term, parse("(x x)"));
+}
+
+#[test]
+fn hm_unbound_variable() {
+    assert_eq!(
+        principal_type(&parse("λx.(f x)")),
+        Err(HmError::UnboundVariable("f".to_owned()))
+    );
+}
+
+#[test]
+fn hm_environment_is_polymorphic() {
+    // `id` is used at `a → a` and at `a`, which needs a fresh instance each time
+    let mut env = HashMap::new();
+    env.insert(Rc::from("id"), principal_type(&parse("λx.x")).unwrap());
+    let scheme = principal_type_in(&parse("((id id) (id λy.y))"), &env).unwrap();
+    assert_eq!(scheme.to_string(), "∀a. a → a");
+}
+
+#[test]
+fn hm_environment_base_types() {
+    // not : Bool → Bool applied to a numeral
+    let mut env = HashMap::new();
+    let bool = Type::base("Bool");
+    env.insert(Rc::from("not"), Scheme(Type::arrow(bool.clone(), bool)));
+    assert_eq!(
+        principal_type_in(&parse("λb.(not b)"), &env).unwrap(),
+        Scheme(Type::arrow(Type::base("Bool"), Type::base("Bool")))
+    );
+    let Err(HmError::Mismatch { .. }) = principal_type_in(&parse("(not λx.x)"), &env) else {
+        panic!("expected a mismatch");
+    };
+}
@@ -0,0 +1,168 @@
+use lambubu::{Strategy, divergence::Evaluator};
+use lambubu_church::ChurchEnvironment;
+use lambubu_types::system_f::{
+    Environment, Term, Type, TypeError, check, compile_file, compile_term, type_of,
+};
+
+fn parse(input: &str) -> Term {
+    compile_term(input, &Environment::church()).unwrap()
+}
+
+fn type_string(input: &str) -> String {
+    type_of(&parse(input)).unwrap().to_string()
+}
+
+fn nat() -> Type {
+    Environment::church().resolve_type("Nat").unwrap()
+}
+
+fn normalize(term: &Term) -> lambubu::Term {
+    Evaluator::new(Strategy::NormalOrder)
+        .evaluate(term.erase())
+        .unwrap()
+        .0
+}
+
+#[test]
+fn system_f_polymorphic_identity() {
+    assert_eq!(type_string("Λa.λx:a.x"), "∀a. a → a");
+    assert_eq!(type_string("Λa b.λx:a.λy:b.x"), "∀a b. a → b → a");
+    assert_eq!(
+        type_string("(Λa.λx:a.x [Nat])"),
+        "(∀a. (a → a) → a → a) → ∀a. (a → a) → a → a"
+    );
+}
+
+#[test]
+fn system_f_types_are_alpha_equivalent() {
+    assert_eq!(type_of(&parse("Λb.λf:b → b.λx:b.x")).unwrap(), nat());
+    assert_ne!(type_of(&parse("Λb.λf:b → b.λx:b.f")).unwrap(), nat());
+}
+
+#[test]
+fn system_f_church_prelude_types() {
+    let env = Environment::church();
+    let nat = nat();
+    let boolean = env.resolve_type("Bool").unwrap();
+    let arrow = |t1: &Type, t2: &Type| Type::arrow(t1.clone(), t2.clone());
+    for (name, ty) in [
+        ("TRUE", boolean.clone()),
+        ("NOT", arrow(&boolean, &boolean)),
+        ("ZERO", nat.clone()),
+        ("SUCC", arrow(&nat, &nat)),
+        ("ADD", arrow(&nat, &arrow(&nat, &nat))),
+        ("MUL", arrow(&nat, &arrow(&nat, &nat))),
+        ("ISZERO", arrow(&nat, &boolean)),
+    ] {
+        assert_eq!(
+            check(&env.resolve_term(name).unwrap(), &ty),
+            Ok(()),
+            "{name}"
+        );
+    }
+    assert_eq!(nat.to_string(), "∀a. (a → a) → a → a");
+}
+
+#[test]
+fn system_f_numerals() {
+    assert_eq!(check(&parse("3"), &nat()), Ok(()));
+    assert_eq!(parse("2").to_string(), "Λa.λf:a → a.λx:a.(f (f x))");
+}
+
+#[test]
+fn system_f_erasure_evaluates_with_untyped_reducers() {
+    // ADD 2 3 erases to the untyped addition and reduces to 5
+    let term = parse("(ADD 2 3)");
+    assert_eq!(check(&term, &nat()), Ok(()));
+    assert!(normalize(&term).alpha_eq(&ChurchEnvironment.numeral(5)));
+    assert!(parse("SUCC").erase().alpha_eq(&ChurchEnvironment.succ()));
+    // ISZERO 0 reduces to TRUE
+    assert!(normalize(&parse("(ISZERO 0)")).alpha_eq(&parse("TRUE").erase()));
+}
+
+#[test]
+fn system_f_self_application() {
+    // untypable in the simply typed calculus, fine with a polymorphic argument
+    assert_eq!(
+        type_string("λx:∀a.a → a.(x [∀a.a → a] x)"),
+        "(∀a. a → a) → ∀a. a → a"
+    );
+}
+
+#[test]
+fn system_f_type_application_avoids_capture() {
+    // instantiating `a` with `b` must not be captured by the inner `∀b`
+    assert_eq!(
+        type_of(&parse("Λb.(Λa.λx:a.Λb.λy:b.x [b])")).unwrap(),
+        type_of(&parse("Λc.λx:c.Λd.λy:d.x")).unwrap()
+    );
+    // the inner `Λa` is renamed apart from the `a` of `x`
+    assert_eq!(type_string("Λa.λx:a.Λa.x"), "∀a. a → ∀a'. a");
+}
+
+#[test]
+fn system_f_type_errors() {
+    assert_eq!(
+        type_of(&parse("λx:a.x")),
+        Err(TypeError::UnboundTypeVariable("a".to_owned()))
+    );
+    assert_eq!(
+        type_of(&parse("(SUCC TRUE)")),
+        Err(TypeError::Mismatch {
+            expected: nat(),
+            found: Environment::church().resolve_type("Bool").unwrap(),
+            term: parse("TRUE"),
+        })
+    );
+    assert!(matches!(
+        type_of(&parse("(SUCC [Nat])")),
+        Err(TypeError::NotPolymorphic { .. })
+    ));
+    assert!(matches!(
+        type_of(&parse("(ZERO 0)")),
+        Err(TypeError::NotAFunction { .. })
+    ));
+}
+
+#[test]
+fn system_f_file_definitions() {
+    let mut env = Environment::new();
+    let terms = compile_file(
+        concat!(
+            "type Id = forall a. a -> a\n",
+            "ID :: /\\a.\\x:a.x\n",
+            "CONST :: /\\a./\\b.\\x:a.\\y:b.x\n",
+            "ID :: /\\b.\\y:b.y\n",
+            "(ID [Id] ID)",
+        ),
+        &mut env,
+    )
+    .unwrap();
+    let names: Vec<_> = env.definitions().iter().map(|(name, _)| *name).collect();
+    assert_eq!(names, ["ID", "CONST"]);
+    let id = env.resolve_type("Id").unwrap();
+    assert_eq!(check(&terms[0], &id), Ok(()));
+    assert!(env.resolve_term("2").is_none());
+}
+
+// Renaming a binder apart from the replacement must not pick the substituted
+// variable itself: (∀b.b)[b' := b] is ∀b''.b'', not ∀b'.b
+#[test]
+fn system_f_substitution_renames_past_the_variable() {
+    let var = |name: &str| Type::var(name);
+    let forall = |name: &str, body: Type| Type::forall(name.into(), body);
+    let substituted = forall("b", var("b")).substitute("b'", &var("b"));
+    assert_eq!(substituted, forall("a", var("a")));
+    assert!(substituted.free_vars().is_empty());
+
+    let term = parse("Λb.λx:b.x").substitute_type("b'", &var("b"));
+    assert_eq!(type_of(&term), type_of(&parse("Λb.λx:b.x")));
+
+    assert_eq!(
+        type_of(&parse("λf:∀b' b.b.Λb.(f [b])")).unwrap(),
+        Type::arrow(
+            forall("b'", forall("b", var("b"))),
+            forall("b", forall("c", var("c")))
+        )
+    );
+}